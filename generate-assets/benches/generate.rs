@@ -0,0 +1,179 @@
+//! Benchmarks of the generator over a synthetic asset folder, much larger than bevy-assets:
+//! parsing the asset files, building the tree of sections, and writing the outputs.
+//!
+//! Run them with `cargo bench`, and compare two branches with `--save-baseline` and
+//! `--baseline`. No request is made, the assets are scanned without their metadata.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use generate_assets::{
+    api::write_api, index::AssetIndex, markdown::write_markdown, scan_assets,
+    schema::parse_asset_file, snapshot::Snapshot, walk::SymlinkPolicy, ErrorMode, Section,
+};
+
+const SECTIONS: usize = 10;
+const SUBSECTIONS: usize = 4;
+const ASSETS_PER_SUBSECTION: usize = 50;
+const ASSETS: usize = SECTIONS * SUBSECTIONS * ASSETS_PER_SUBSECTION;
+
+const TAGS: &[&str] = &["2d", "3d", "ai", "animation", "audio", "camera"];
+const VERSIONS: &[&str] = &["0.11", "0.12", "0.13", "0.14"];
+
+/// A folder of the temporary directory, emptied.
+fn bench_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "generate-assets-bench-{name}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The content of the `index`-th asset file, setting most of the fields of an asset.
+fn asset_file(index: usize) -> String {
+    let link = if index.is_multiple_of(3) {
+        format!("https://crates.io/crates/bench_asset_{index}")
+    } else {
+        format!("https://github.com/bench-user/bench-asset-{index}")
+    };
+    format!(
+        "name = \"Bench asset {index}\"\n\
+        description = \"Synthetic asset number {index}, with a description long enough to \
+        be cut in the cards and the listings of its section.\"\n\
+        link = \"{link}\"\n\
+        order = {order}\n\
+        licenses = [\"MIT\", \"Apache-2.0\"]\n\
+        bevy_versions = [\"{version}\"]\n\
+        tags = [\"{tag1}\", \"{tag2}\"]\n\
+        platforms = [\"linux\", \"windows\", \"wasm\"]\n",
+        order = index % 7,
+        version = VERSIONS[index % VERSIONS.len()],
+        tag1 = TAGS[index % TAGS.len()],
+        tag2 = TAGS[(index + 1) % TAGS.len()],
+    )
+}
+
+/// Writes [`ASSETS`] asset files to `dir`, in [`SECTIONS`] sections of [`SUBSECTIONS`]
+/// sub-sections each.
+fn write_asset_tree(dir: &Path) {
+    let mut index = 0;
+    for section in 0..SECTIONS {
+        let section_dir = dir.join(format!("Section {section}"));
+        fs::create_dir_all(&section_dir).unwrap();
+        fs::write(
+            section_dir.join("_category.toml"),
+            format!("order = {section}\ndescription = \"Section {section}\"\n"),
+        )
+        .unwrap();
+        for subsection in 0..SUBSECTIONS {
+            let subsection_dir = section_dir.join(format!("Subsection {subsection}"));
+            fs::create_dir_all(&subsection_dir).unwrap();
+            for _ in 0..ASSETS_PER_SUBSECTION {
+                fs::write(
+                    subsection_dir.join(format!("asset_{index}.toml")),
+                    asset_file(index),
+                )
+                .unwrap();
+                index += 1;
+            }
+        }
+    }
+}
+
+fn scan(asset_dir: &Path) -> Section {
+    scan_assets(
+        asset_dir,
+        &Default::default(),
+        SymlinkPolicy::Follow,
+        ErrorMode::FailFast,
+        None,
+    )
+    .unwrap()
+}
+
+fn parse(c: &mut Criterion) {
+    let dir = bench_dir("parse");
+    let asset_dir = dir.join("assets");
+    write_asset_tree(&asset_dir);
+    let file = asset_dir.join("Section 0/Subsection 0/asset_0.toml");
+
+    let mut group = c.benchmark_group("parse");
+    group.bench_function("asset file", |b| {
+        b.iter(|| parse_asset_file(&file).unwrap());
+    });
+    group.throughput(Throughput::Elements(ASSETS as u64));
+    group.sample_size(20);
+    group.bench_function("asset tree", |b| b.iter(|| scan(&asset_dir)));
+    group.finish();
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+fn emit(c: &mut Criterion) {
+    let dir = bench_dir("emit");
+    let asset_dir = dir.join("assets");
+    write_asset_tree(&asset_dir);
+    let mut root = scan(&asset_dir);
+    root.set_related();
+    let index = AssetIndex::new(&root);
+    let snapshot = dir.join("snapshot.json");
+    Snapshot::new(&asset_dir, root.clone())
+        .write(&snapshot)
+        .unwrap();
+    let output = dir.join("output");
+
+    let mut group = c.benchmark_group("emit");
+    group.throughput(Throughput::Elements(ASSETS as u64));
+    group.sample_size(20);
+    group.bench_function("index", |b| {
+        b.iter(|| serde_json::to_string(&AssetIndex::new(&root)).unwrap());
+    });
+    group.bench_function("api", |b| {
+        b.iter_batched(
+            || {
+                let _ = fs::remove_dir_all(&output);
+            },
+            |()| write_api(&index, &output).unwrap(),
+            BatchSize::PerIteration,
+        );
+    });
+    group.bench_function("markdown", |b| {
+        b.iter_batched(
+            || {
+                let _ = fs::remove_dir_all(&output);
+            },
+            |()| write_markdown(&root, &output).unwrap(),
+            BatchSize::PerIteration,
+        );
+    });
+    // The whole emit phase, through the binary, as the content pages are written by it
+    group.sample_size(10);
+    group.bench_function("content", |b| {
+        b.iter_batched(
+            || {
+                let _ = fs::remove_dir_all(&output);
+            },
+            |()| {
+                let status = Command::new(env!("CARGO_BIN_EXE_generate-assets"))
+                    .current_dir(&dir)
+                    .args(["-qq", "emit", "snapshot.json", "--content-dir", "output"])
+                    .status()
+                    .unwrap();
+                assert!(status.success());
+            },
+            BatchSize::PerIteration,
+        );
+    });
+    group.finish();
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, parse, emit);
+criterion_main!(benches);
@@ -3,6 +3,7 @@
 # Switch to script's directory, letting it be called from any folder.
 cd $(dirname $0)
 
-git clone --depth=1 https://github.com/bevyengine/bevy-assets assets
+# The history of the assets is needed for statistics, but not the content of old files
+git clone --filter=blob:none https://github.com/bevyengine/bevy-assets assets
 
-cargo run --release --bin generate -- assets ../content
+cargo run --release --bin generate-assets -- generate --asset-dir assets --content-dir ../content --badge-dir ../static/assets/badges --api-dir ../static/api --failure-report failures.json
@@ -0,0 +1,121 @@
+//! Static JSON API, letting external tools query the ecosystem without scraping the website.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::{
+    bevy_releases::BevyVersion,
+    index::{AssetIndex, IndexedAsset},
+};
+
+/// Version of the API, in the path of every file. Increase it on every breaking change of
+/// [`IndexedAsset`], keeping the previous version around while tools migrate.
+pub const API_VERSION: &str = "v1";
+
+/// The assets supporting a Bevy version, written as `by-bevy-version/<version>.json`.
+#[derive(Serialize, Debug)]
+struct VersionAssets<'a> {
+    version: &'a BevyVersion,
+    assets: Vec<&'a IndexedAsset>,
+}
+
+/// Writes the API files of `index` to `dir`:
+///
+/// - `v1/assets/index.json`, with every asset, like `assets.json`.
+/// - `v1/assets/by-bevy-version/<version>.json`, with the assets supporting a Bevy version,
+///   for each version listed by [`AssetIndex::bevy_versions`].
+/// - `v1/<path>.json` for each asset, following its [`IndexedAsset::path`], like
+///   `v1/assets/games/foo.json`.
+pub fn write_api(index: &AssetIndex, dir: &Path) -> anyhow::Result<()> {
+    let assets_dir = dir.join(API_VERSION).join("assets");
+    write_json(&assets_dir.join("index.json"), index)?;
+
+    let by_path: HashMap<_, _> = index
+        .assets
+        .iter()
+        .map(|asset| (asset.path.as_str(), asset))
+        .collect();
+    for listing in index.bevy_versions() {
+        let assets = VersionAssets {
+            version: &listing.version,
+            assets: listing
+                .assets
+                .iter()
+                .filter_map(|path| by_path.get(path.as_str()).copied())
+                .collect(),
+        };
+        write_json(
+            &assets_dir
+                .join("by-bevy-version")
+                .join(format!("{}.json", listing.version)),
+            &assets,
+        )?;
+    }
+
+    for asset in &index.assets {
+        write_json(
+            &dir.join(API_VERSION).join(format!("{}.json", asset.path)),
+            asset,
+        )?;
+    }
+    Ok(())
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Creating {}", parent.display()))?;
+    }
+    fs::write(path, serde_json::to_string(value)?)
+        .with_context(|| format!("Writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::INDEX_FORMAT_VERSION;
+
+    fn asset(path: &str, versions: &[&str]) -> IndexedAsset {
+        IndexedAsset {
+            name: path.to_string(),
+            path: path.to_string(),
+            bevy_versions: versions.iter().map(|v| v.parse().unwrap()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn writes_index_versions_and_assets() {
+        let dir = std::env::temp_dir().join(format!("generate-assets-api-{}", std::process::id()));
+        let index = AssetIndex {
+            version: INDEX_FORMAT_VERSION,
+            assets: vec![
+                asset("assets/games/foo", &["0.13"]),
+                asset("assets/assets/2d/bar", &["0.12.1", "0.13"]),
+            ],
+        };
+        write_api(&index, &dir).unwrap();
+
+        let read = |path: &str| -> serde_json::Value {
+            serde_json::from_str(&fs::read_to_string(dir.join(path)).unwrap()).unwrap()
+        };
+        assert_eq!(
+            read("v1/assets/index.json")["assets"][1]["path"],
+            "assets/assets/2d/bar"
+        );
+        assert_eq!(read("v1/assets/games/foo.json")["name"], "assets/games/foo");
+        let version = read("v1/assets/by-bevy-version/0.12.json");
+        assert_eq!(version["version"], "0.12");
+        assert_eq!(version["assets"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            read("v1/assets/by-bevy-version/0.13.json")["assets"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
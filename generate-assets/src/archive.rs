@@ -0,0 +1,181 @@
+//! Record of the assets removed from bevy-assets, so their history isn't lost and assets
+//! submitted again can be recognized.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    git::RemovedFile, links::canonical_link, schema::AssetFormat, walk::is_asset_file, Asset,
+};
+
+/// Every removed asset, written as `archive.json`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Archive {
+    /// Removed assets, from the most recently removed.
+    pub assets: Vec<ArchivedAsset>,
+}
+
+/// The last known metadata of a removed asset.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedAsset {
+    pub name: String,
+    pub link: String,
+    pub description: String,
+    /// Names of the sections that contained the asset, from the top-level one.
+    pub sections: Vec<String>,
+    pub licenses: Vec<String>,
+    pub bevy_versions: Vec<String>,
+    /// When the asset was removed, as a `YYYY-MM-DD` date.
+    pub removed: String,
+}
+
+/// The fields of a removed asset file. Unlike [`Asset`], unknown fields are allowed, since
+/// the file may follow an older schema.
+#[derive(Deserialize)]
+struct RemovedAsset {
+    name: String,
+    link: String,
+    #[serde(default)]
+    description: String,
+    licenses: Option<Vec<String>>,
+    bevy_versions: Option<Vec<String>>,
+}
+
+impl Archive {
+    /// Reads the archive persisted at `path`, which is empty if the file doesn't exist yet.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Archive::default());
+        }
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Parsing {}", path.display()))
+    }
+
+    /// Adds the asset files of `asset_dir` among `files`, read with
+    /// [`crate::git::read_removed_files`], returning how many weren't archived yet.
+    pub fn add_removed_files(&mut self, asset_dir: &Path, files: &[RemovedFile]) -> usize {
+        let Ok(asset_dir) = asset_dir.canonicalize() else {
+            return 0;
+        };
+        let mut added = 0;
+        for file in files {
+            let Ok(relative) = file.path.strip_prefix(&asset_dir) else {
+                continue;
+            };
+            if !is_asset_file(&file.path) {
+                continue;
+            }
+            let Some(format) = AssetFormat::from_path(&file.path) else {
+                continue;
+            };
+            let Ok(asset) = format.parse::<RemovedAsset>(&file.path, &file.content) else {
+                continue;
+            };
+
+            let archived = ArchivedAsset {
+                name: asset.name,
+                link: asset.link,
+                description: asset.description,
+                sections: relative
+                    .parent()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|folder| folder.to_str().map(str::to_string))
+                    .collect(),
+                licenses: asset.licenses.unwrap_or_default(),
+                bevy_versions: asset.bevy_versions.unwrap_or_default(),
+                removed: file.removed.clone(),
+            };
+            let known = self.assets.iter().any(|asset| {
+                asset.name == archived.name
+                    && asset.link == archived.link
+                    && asset.removed == archived.removed
+            });
+            if !known {
+                self.assets.push(archived);
+                added += 1;
+            }
+        }
+
+        self.assets.sort_by(|asset1, asset2| {
+            (&asset2.removed, &asset1.name).cmp(&(&asset1.removed, &asset2.name))
+        });
+        added
+    }
+
+    /// The archived asset each of `assets` was submitted again as, comparing their links.
+    pub fn resubmissions<'a>(
+        &'a self,
+        assets: &[&'a Asset],
+    ) -> Vec<(&'a Asset, &'a ArchivedAsset)> {
+        assets
+            .iter()
+            .filter_map(|asset| {
+                let link = canonical_link(&asset.link);
+                let archived = self
+                    .assets
+                    .iter()
+                    .find(|archived| canonical_link(&archived.link) == link)?;
+                Some((*asset, archived))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn removed(path: &Path, removed: &str, content: &str) -> RemovedFile {
+        RemovedFile {
+            path: path.to_path_buf(),
+            removed: removed.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn archives_removed_asset_files_once() {
+        let asset_dir =
+            std::env::temp_dir().join(format!("generate-assets-archive-{}", std::process::id()));
+        fs::create_dir_all(&asset_dir).unwrap();
+        let asset_dir = asset_dir.canonicalize().unwrap();
+        let files = [
+            removed(
+                &asset_dir.join("Assets/2D/foo.toml"),
+                "2024-02-01",
+                "name = \"Foo\"\nlink = \"https://github.com/user/foo\"\ndescription = \"A foo\"\nold_field = 1\n",
+            ),
+            removed(&asset_dir.join("Assets/2D/_category.toml"), "2024-02-01", "order = 1\n"),
+            removed(
+                &PathBuf::from("/elsewhere/bar.toml"),
+                "2024-02-01",
+                "name = \"Bar\"\nlink = \"https://bar.rs\"\n",
+            ),
+            removed(&asset_dir.join("Games/broken.toml"), "2023-01-01", "name ="),
+        ];
+
+        let mut archive = Archive::default();
+        assert_eq!(archive.add_removed_files(&asset_dir, &files), 1);
+        assert_eq!(archive.add_removed_files(&asset_dir, &files), 0);
+        assert_eq!(
+            archive.assets,
+            [ArchivedAsset {
+                name: "Foo".to_string(),
+                link: "https://github.com/user/foo".to_string(),
+                description: "A foo".to_string(),
+                sections: vec!["Assets".to_string(), "2D".to_string()],
+                licenses: vec![],
+                bevy_versions: vec![],
+                removed: "2024-02-01".to_string(),
+            }]
+        );
+
+        let _ = fs::remove_dir_all(&asset_dir);
+    }
+}
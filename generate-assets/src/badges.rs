@@ -0,0 +1,126 @@
+//! Compatibility badges that plugin authors can embed in their README.
+
+use crate::{
+    bevy_releases::{newest_known_version, BevyVersion},
+    Asset, MetadataLocation,
+};
+
+/// Approximate width of a character of the badge font, in pixels.
+const CHAR_WIDTH: usize = 7;
+/// Horizontal padding around each half of the badge, in pixels.
+const PADDING: usize = 10;
+const LABEL_COLOR: &str = "#555";
+const MESSAGE_COLOR: &str = "#4c1";
+
+/// A badge of a crates.io asset, like "bevy | 0.13 compatible".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    /// Name of the crate, used as the file name of the badge.
+    pub crate_name: String,
+    /// The newest Bevy version the crate supports.
+    pub bevy_version: BevyVersion,
+}
+
+impl Badge {
+    /// The badge of `asset`, if it is a crate, by the link its metadata is fetched from or its
+    /// [`crate_name`](Asset::crate_name), and supports a known Bevy version.
+    pub fn new(asset: &Asset) -> Option<Self> {
+        let location = MetadataLocation::from_link(asset.metadata_link());
+        let crate_name = match (&asset.crate_name, location) {
+            (Some(crate_name), _) => crate_name.clone(),
+            (None, Ok(MetadataLocation::CratesIo { crate_name })) => crate_name,
+            _ => return None,
+        };
+        let bevy_version = newest_known_version(asset.bevy_versions.as_deref()?)?;
+
+        Some(Badge {
+            crate_name,
+            bevy_version,
+        })
+    }
+
+    /// Renders the badge as an SVG image, in the style of shields.io.
+    pub fn svg(&self) -> String {
+        svg("bevy", &format!("{} compatible", self.bevy_version))
+    }
+}
+
+fn svg(label: &str, message: &str) -> String {
+    let label_width = label.chars().count() * CHAR_WIDTH + 2 * PADDING;
+    let message_width = message.chars().count() * CHAR_WIDTH + 2 * PADDING;
+    let width = label_width + message_width;
+    let label = escape(label);
+    let message = escape(message);
+    let label_x = label_width / 2;
+    let message_x = label_width + message_width / 2;
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+  <g clip-path="url(#r)">
+    <rect width="{label_width}" height="20" fill="{LABEL_COLOR}"/>
+    <rect x="{label_width}" width="{message_width}" height="20" fill="{MESSAGE_COLOR}"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(link: &str, versions: &[&str]) -> Asset {
+        Asset {
+            link: link.parse().unwrap(),
+            bevy_versions: Some(versions.iter().map(|v| v.parse().unwrap()).collect()),
+            ..Asset::named("bevy_foo")
+        }
+    }
+
+    #[test]
+    fn uses_newest_supported_version() {
+        let badge = Badge::new(&asset(
+            "https://crates.io/crates/bevy_foo",
+            &["0.11", "0.12.1", "0.99"],
+        ))
+        .unwrap();
+        assert_eq!(badge.crate_name, "bevy_foo");
+        assert_eq!(badge.bevy_version, BevyVersion::release(0, 12));
+        assert!(badge.svg().contains("<title>bevy: 0.12 compatible</title>"));
+
+        let badge = Badge::new(&asset(
+            "https://crates.io/crates/bevy_foo",
+            &["main", "0.13"],
+        ));
+        assert_eq!(badge.unwrap().bevy_version, BevyVersion::Main);
+    }
+
+    #[test]
+    fn only_for_crates_with_known_versions() {
+        assert_eq!(
+            Badge::new(&asset("https://github.com/user/bevy_foo", &["0.12"])),
+            None
+        );
+        assert_eq!(
+            Badge::new(&asset("https://crates.io/crates/bevy_foo", &["0.99"])),
+            None
+        );
+
+        let mut repository = asset("https://github.com/user/foo", &["0.12"]);
+        repository.crate_name = Some("bevy_foo".to_string());
+        assert_eq!(Badge::new(&repository).unwrap().crate_name, "bevy_foo");
+    }
+}
@@ -0,0 +1,301 @@
+//! The Bevy versions supported by assets, and their validation.
+
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Every minor release of Bevy, from oldest to newest. Add new releases here.
+pub const BEVY_RELEASES: &[&str] = &[
+    "0.1", "0.2", "0.3", "0.4", "0.5", "0.6", "0.7", "0.8", "0.9", "0.10", "0.11", "0.12", "0.13",
+];
+
+/// Version for assets tracking the main branch of Bevy.
+pub const BEVY_MAIN: &str = "main";
+
+/// Whether `version` is a minor release of Bevy, or `main`.
+pub fn is_known_version(version: &str) -> bool {
+    version == BEVY_MAIN || BEVY_RELEASES.contains(&version)
+}
+
+/// Suggests the known version closest to an unknown `version`, like `0.12` for `0.12.1`
+/// or `^0.12`, and the latest release for `latest`.
+pub fn suggest_version(version: &str) -> Option<&'static str> {
+    let version = version
+        .trim()
+        .trim_start_matches(['^', '~', '=', 'v'])
+        .trim();
+
+    if version.eq_ignore_ascii_case("latest") || version.eq_ignore_ascii_case("stable") {
+        return BEVY_RELEASES.last().copied();
+    }
+    if version.eq_ignore_ascii_case(BEVY_MAIN) || version.eq_ignore_ascii_case("master") {
+        return Some(BEVY_MAIN);
+    }
+
+    let mut parts = version.split('.');
+    let minor = format!("{}.{}", parts.next()?, parts.next()?);
+    BEVY_RELEASES
+        .iter()
+        .find(|release| **release == minor)
+        .copied()
+}
+
+/// A version of Bevy supported by an asset: a release like `0.13` or `0.13.1`, or the main
+/// branch, which is newer than every release.
+///
+/// Versions are compared by their numbers, a minor release coming before its patches, so
+/// `0.9` < `0.10` < `0.10.1` < `main`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BevyVersion {
+    Release {
+        major: u32,
+        minor: u32,
+        /// Only set for patch releases, `0.13` meaning every patch of `0.13`.
+        patch: Option<u32>,
+    },
+    Main,
+}
+
+impl BevyVersion {
+    /// The minor release `major.minor`, like `0.13`.
+    pub const fn release(major: u32, minor: u32) -> Self {
+        BevyVersion::Release {
+            major,
+            minor,
+            patch: None,
+        }
+    }
+
+    /// The newest release in [`BEVY_RELEASES`].
+    pub fn latest_release() -> Self {
+        BEVY_RELEASES
+            .last()
+            .and_then(|release| release.parse().ok())
+            .expect("The releases are valid versions")
+    }
+
+    /// The minor release of this version, like `0.13` for `0.13.1`, so versions of the same
+    /// release can be grouped and deduplicated.
+    #[must_use]
+    pub fn minor(self) -> Self {
+        match self {
+            BevyVersion::Release { major, minor, .. } => BevyVersion::release(major, minor),
+            BevyVersion::Main => BevyVersion::Main,
+        }
+    }
+
+    /// Whether this version is a minor release in [`BEVY_RELEASES`], or `main`, the versions
+    /// asset files should list.
+    pub fn is_known(self) -> bool {
+        is_known_version(&self.to_string())
+    }
+
+    /// The version of a requirement of a `Cargo.toml` dependency, like `0.13` for `^0.13`,
+    /// `=0.13.1` or `0.13.0-rc.2`. Requirements with a range, like `>=0.12, <0.14`, don't
+    /// give one.
+    pub fn from_requirement(requirement: &str) -> Option<Self> {
+        let version = requirement
+            .trim()
+            .trim_start_matches(['^', '~', '=', 'v'])
+            .trim();
+        let version = version
+            .split_once('-')
+            .map_or(version, |(version, _)| version);
+        match version.parse().ok()? {
+            BevyVersion::Release {
+                major,
+                minor,
+                patch: Some(0),
+            } => Some(BevyVersion::release(major, minor)),
+            version => Some(version),
+        }
+    }
+}
+
+impl fmt::Display for BevyVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BevyVersion::Release {
+                major,
+                minor,
+                patch: None,
+            } => write!(f, "{major}.{minor}"),
+            BevyVersion::Release {
+                major,
+                minor,
+                patch: Some(patch),
+            } => write!(f, "{major}.{minor}.{patch}"),
+            BevyVersion::Main => write!(f, "{BEVY_MAIN}"),
+        }
+    }
+}
+
+/// Why a string isn't a [`BevyVersion`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBevyVersionError(pub String);
+
+impl fmt::Display for ParseBevyVersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Bevy version \"{}\" must be a release like \"{}\", or \"{}\"",
+            self.0,
+            BEVY_RELEASES.last().unwrap(),
+            BEVY_MAIN
+        )?;
+        match suggest_version(&self.0) {
+            Some(suggestion) => write!(f, ", did you mean \"{suggestion}\"?"),
+            None => write!(f, "."),
+        }
+    }
+}
+
+impl std::error::Error for ParseBevyVersionError {}
+
+impl FromStr for BevyVersion {
+    type Err = ParseBevyVersionError;
+
+    /// Parses `main`, a minor release like `0.13`, or a patch release like `0.13.1`.
+    fn from_str(version: &str) -> Result<Self, Self::Err> {
+        if version == BEVY_MAIN {
+            return Ok(BevyVersion::Main);
+        }
+        let error = || ParseBevyVersionError(version.to_string());
+        let number = |part: Option<&str>| -> Result<u32, ParseBevyVersionError> {
+            let part = part.ok_or_else(error)?;
+            // Leading signs and zeros would display differently
+            if !part.bytes().all(|byte| byte.is_ascii_digit())
+                || part.len() > 1 && part.starts_with('0')
+            {
+                return Err(error());
+            }
+            part.parse().map_err(|_| error())
+        };
+
+        let mut parts = version.split('.');
+        let major = number(parts.next())?;
+        let minor = number(parts.next())?;
+        let patch = parts.next().map(|patch| number(Some(patch))).transpose()?;
+        if parts.next().is_some() {
+            return Err(error());
+        }
+        Ok(BevyVersion::Release {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl Serialize for BevyVersion {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BevyVersion {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserializes a list of versions, leaving out the ones that aren't valid, like the `git`
+/// written for git dependencies before [`BevyVersion`] existed.
+pub(crate) fn deserialize_valid_versions<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<BevyVersion>, D::Error> {
+    Ok(Vec::<String>::deserialize(deserializer)?
+        .iter()
+        .filter_map(|version| version.parse().ok())
+        .collect())
+}
+
+/// The newest of `versions` that is a minor release in [`BEVY_RELEASES`] or `main`, patch
+/// releases counting as their minor release.
+pub fn newest_known_version(versions: &[BevyVersion]) -> Option<BevyVersion> {
+    versions
+        .iter()
+        .map(|version| version.minor())
+        .filter(|version| version.is_known())
+        .max()
+}
+
+/// `versions` separated by `, `.
+pub fn join_versions(versions: &[BevyVersion]) -> String {
+    versions
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_known_versions() {
+        assert!(is_known_version("0.12"));
+        assert!(is_known_version("main"));
+        assert!(!is_known_version("0.12.1"));
+
+        assert_eq!(suggest_version("0.12.1"), Some("0.12"));
+        assert_eq!(suggest_version("^0.11"), Some("0.11"));
+        assert_eq!(suggest_version("latest"), BEVY_RELEASES.last().copied());
+        assert_eq!(suggest_version("0.99"), None);
+    }
+
+    #[test]
+    fn parses_and_displays_versions() {
+        for version in ["0.13", "0.13.1", "1.0", "main"] {
+            assert_eq!(version.parse::<BevyVersion>().unwrap().to_string(), version);
+        }
+        for version in [
+            "", "0", "0.13.1.2", "^0.13", "0.013", "latest", "git", "Main",
+        ] {
+            assert!(version.parse::<BevyVersion>().is_err(), "{}", version);
+        }
+        assert_eq!(
+            "^0.12".parse::<BevyVersion>().unwrap_err().to_string(),
+            "Bevy version \"^0.12\" must be a release like \"0.13\", or \"main\", did you mean \"0.12\"?"
+        );
+
+        assert_eq!(
+            BevyVersion::from_requirement("^0.12"),
+            Some(BevyVersion::release(0, 12))
+        );
+        assert_eq!(
+            BevyVersion::from_requirement("=0.12.0-rc.1"),
+            Some(BevyVersion::release(0, 12))
+        );
+        assert_eq!(
+            BevyVersion::from_requirement("0.12.1").map(|version| version.to_string()),
+            Some("0.12.1".to_string())
+        );
+        assert_eq!(BevyVersion::from_requirement(">=0.12, <0.14"), None);
+    }
+
+    #[test]
+    fn compares_versions() {
+        let mut versions: Vec<BevyVersion> = ["main", "0.10.1", "0.9", "0.10", "0.10.1"]
+            .iter()
+            .map(|version| version.parse().unwrap())
+            .collect();
+        versions.sort();
+        versions.dedup();
+        assert_eq!(join_versions(&versions), "0.9, 0.10, 0.10.1, main");
+
+        assert_eq!(
+            newest_known_version(&versions[..3]),
+            Some(BevyVersion::release(0, 10))
+        );
+        assert_eq!(newest_known_version(&versions), Some(BevyVersion::Main));
+        assert_eq!(newest_known_version(&[BevyVersion::release(0, 99)]), None);
+        assert_eq!(
+            BevyVersion::latest_release().to_string(),
+            *BEVY_RELEASES.last().unwrap()
+        );
+    }
+}
@@ -0,0 +1,58 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+use clap::Subcommand;
+
+use generate_assets::{config::GeneratorConfig, prepare_crates_db, CratesDumpFilter};
+
+/// Manages the cache of the crates.io database dump.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Folder the crates.io database dump is downloaded to
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Download the crates.io database dump, if the cached one is missing or outdated
+    Update {
+        /// Only load bevy related crates from the crates.io dump, using much less memory
+        #[arg(long)]
+        filter_crates_dump: bool,
+    },
+    /// Delete the cached crates.io database dump
+    Clear,
+    /// Print the folder of the cache
+    Path,
+}
+
+pub fn run(args: Args, config: GeneratorConfig) -> anyhow::Result<()> {
+    let cache_dir = args.cache_dir.unwrap_or(config.paths.cache_dir);
+    match args.command {
+        CacheCommand::Update { filter_crates_dump } => {
+            let filter = if filter_crates_dump {
+                CratesDumpFilter::BevyRelated
+            } else {
+                CratesDumpFilter::All
+            };
+            prepare_crates_db(filter, &cache_dir, false)?;
+        }
+        CacheCommand::Clear => clear(cache_dir)?,
+        CacheCommand::Path => println!("{}", cache_dir.display()),
+    }
+    Ok(())
+}
+
+fn clear(cache_dir: PathBuf) -> anyhow::Result<()> {
+    if !cache_dir.exists() {
+        tracing::info!("No cache at {}", cache_dir.display());
+        return Ok(());
+    }
+    fs::remove_dir_all(&cache_dir).with_context(|| format!("Deleting {}", cache_dir.display()))?;
+    tracing::info!("Deleted {}", cache_dir.display());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context;
+
+use generate_assets::diff::{read_index, IndexDiff};
+
+/// Prints the assets added, removed and changed between two `assets.json` files as Markdown.
+#[derive(clap::Args)]
+pub struct Args {
+    /// The `assets.json` file of the previous run
+    previous: PathBuf,
+
+    /// The `assets.json` file of the current run
+    current: PathBuf,
+
+    /// Write the differences to this file instead
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> anyhow::Result<()> {
+    let previous = read_index(&args.previous)?;
+    let current = read_index(&args.current)?;
+    let report = IndexDiff::new(&previous, &current).markdown();
+    match &args.output {
+        Some(path) => {
+            fs::write(path, report).with_context(|| format!("Writing {}", path.display()))
+        }
+        None => {
+            print!("{report}");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::bail;
+
+use generate_assets::{
+    config::GeneratorConfig,
+    crates_dump_status,
+    github_client::GithubClient,
+    gitlab_client::GitlabClient,
+    http::HttpContext,
+    walk::{is_asset_file, AssetWalker},
+};
+
+use crate::metadata::TokenArgs;
+
+/// The crates.io database dump is published daily, a week old one misses recent releases.
+const STALE_CRATES_DUMP: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Below this share of its rate limit, Github can stop answering during a full run.
+const LOW_RATE_LIMIT_PERCENT: u32 = 10;
+
+/// Checks that everything a run needs is ready: the asset folder, the tokens, the Github
+/// rate limit and the cached crates.io database dump, and prints a report.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    /// Folder the crates.io database dump is downloaded to
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    /// Don't check the tokens and the rate limit with Github and Gitlab
+    #[arg(long)]
+    offline: bool,
+
+    #[command(flatten)]
+    tokens: TokenArgs,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Status::Ok => "ok",
+            Status::Warning => "warn",
+            Status::Error => "error",
+        };
+        f.pad(status)
+    }
+}
+
+/// The result of one check of the report.
+struct Check {
+    name: &'static str,
+    status: Status,
+    message: String,
+}
+
+impl Check {
+    fn new(name: &'static str, status: Status, message: impl Into<String>) -> Self {
+        Check {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+pub fn run(args: Args, config: GeneratorConfig) -> anyhow::Result<()> {
+    let asset_dir = args
+        .asset_dir
+        .clone()
+        .unwrap_or_else(|| config.paths.asset_dir.clone());
+    let cache_dir = args
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| config.paths.cache_dir.clone());
+
+    let mut checks = vec![check_asset_dir(Path::new(&asset_dir))];
+    if config.clients.crates_io {
+        checks.push(check_crates_dump(&cache_dir));
+    } else {
+        checks.push(Check::new(
+            "crates.io dump",
+            Status::Warning,
+            "disabled in the configuration",
+        ));
+    }
+    let http = HttpContext::new(&config.http)?;
+    checks.extend(check_github(&args, &config, &http));
+    checks.push(check_gitlab(&args, &config, &http));
+
+    for check in &checks {
+        println!("{:>5}  {:<16} {}", check.status, check.name, check.message);
+    }
+    let errors = checks
+        .iter()
+        .filter(|check| check.status == Status::Error)
+        .count();
+    let warnings = checks
+        .iter()
+        .filter(|check| check.status == Status::Warning)
+        .count();
+    if errors > 0 {
+        bail!("Not ready: {errors} errors and {warnings} warnings");
+    }
+    println!("\nReady, with {warnings} warnings");
+    Ok(())
+}
+
+fn check_asset_dir(asset_dir: &Path) -> Check {
+    const NAME: &str = "asset folder";
+    if !asset_dir.is_dir() {
+        return Check::new(
+            NAME,
+            Status::Error,
+            format!(
+                "{} not found, clone https://github.com/bevyengine/bevy-assets there or use \
+                --asset-dir",
+                asset_dir.display()
+            ),
+        );
+    }
+    let files = count_asset_files(asset_dir);
+    if files == 0 {
+        return Check::new(
+            NAME,
+            Status::Error,
+            format!("no asset file in {}", asset_dir.display()),
+        );
+    }
+    if !asset_dir.join(".git").exists() {
+        return Check::new(
+            NAME,
+            Status::Warning,
+            format!(
+                "{files} asset files in {}, which isn't a git repository, so the dates and \
+                renames of the assets will be missing",
+                asset_dir.display()
+            ),
+        );
+    }
+    Check::new(
+        NAME,
+        Status::Ok,
+        format!("{files} asset files in {}", asset_dir.display()),
+    )
+}
+
+/// Counts the TOML files of the assets, without the configuration files of the sections, up
+/// to the first folder that can't be read.
+fn count_asset_files(dir: &Path) -> usize {
+    let mut count = 0;
+    let _ = AssetWalker::new(dir).and_then(|walker| {
+        walker.walk_files(dir, &mut |path| {
+            count += usize::from(is_asset_file(path));
+            Ok(())
+        })
+    });
+    count
+}
+
+fn check_crates_dump(cache_dir: &Path) -> Check {
+    const NAME: &str = "crates.io dump";
+    let status = match crates_dump_status(cache_dir) {
+        Ok(status) => status,
+        Err(err) => {
+            return Check::new(
+                NAME,
+                Status::Error,
+                format!("{err:#}, run `generate-assets cache update` to download it"),
+            )
+        }
+    };
+    let age = SystemTime::now()
+        .duration_since(status.extracted)
+        .unwrap_or_default();
+    let days = age.as_secs() / (24 * 60 * 60);
+    let message = format!(
+        "extracted {days} days ago in {}, {} databases loaded",
+        cache_dir.display(),
+        status.databases.len()
+    );
+    if age > STALE_CRATES_DUMP {
+        Check::new(
+            NAME,
+            Status::Warning,
+            format!("{message}, run `generate-assets cache update` to refresh it"),
+        )
+    } else {
+        Check::new(NAME, Status::Ok, message)
+    }
+}
+
+/// Checks the Github token, then the rate limit left for it.
+fn check_github(args: &Args, config: &GeneratorConfig, http: &HttpContext) -> Vec<Check> {
+    const NAME: &str = "github token";
+    if !config.clients.github {
+        return vec![Check::new(
+            NAME,
+            Status::Warning,
+            "disabled in the configuration",
+        )];
+    }
+    let token = match args.tokens.github_token(config) {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            return vec![Check::new(
+                NAME,
+                Status::Warning,
+                format!(
+                    "no token in {}, Github links will be skipped",
+                    config.tokens.github
+                ),
+            )]
+        }
+        Err(err) => return vec![Check::new(NAME, Status::Error, format!("{err:#}"))],
+    };
+    if args.offline {
+        return vec![Check::new(NAME, Status::Ok, "found, not checked offline")];
+    }
+
+    let rate = match GithubClient::new(token, http).get_rate_limit() {
+        Ok(rate) => rate,
+        Err(err) => {
+            return vec![Check::new(
+                NAME,
+                Status::Error,
+                format!("rejected by Github: {err:#}"),
+            )]
+        }
+    };
+    let resets_in = Duration::from_secs(rate.reset).saturating_sub(
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default(),
+    );
+    let message = format!(
+        "{}/{} requests left, resetting in {} minutes",
+        rate.remaining,
+        rate.limit,
+        resets_in.as_secs() / 60
+    );
+    let status = if rate.remaining == 0 {
+        Status::Error
+    } else if rate.remaining * 100 < rate.limit * LOW_RATE_LIMIT_PERCENT {
+        Status::Warning
+    } else {
+        Status::Ok
+    };
+    vec![
+        Check::new(NAME, Status::Ok, "accepted by Github"),
+        Check::new("github rate limit", status, message),
+    ]
+}
+
+fn check_gitlab(args: &Args, config: &GeneratorConfig, http: &HttpContext) -> Check {
+    const NAME: &str = "gitlab token";
+    if !config.clients.gitlab {
+        return Check::new(NAME, Status::Warning, "disabled in the configuration");
+    }
+    let token = match args.tokens.gitlab_token(config) {
+        Ok(Some(token)) if !token.is_empty() => token,
+        Ok(_) => {
+            return Check::new(
+                NAME,
+                Status::Ok,
+                "no token, which is optional to search public projects",
+            )
+        }
+        Err(err) => return Check::new(NAME, Status::Error, format!("{err:#}")),
+    };
+    if args.offline {
+        return Check::new(NAME, Status::Ok, "found, not checked offline");
+    }
+    match GitlabClient::new(token, http).check_token() {
+        Ok(()) => Check::new(NAME, Status::Ok, "accepted by Gitlab"),
+        Err(err) => Check::new(NAME, Status::Error, format!("rejected by Gitlab: {err:#}")),
+    }
+}
@@ -0,0 +1,35 @@
+use std::{fs, path::PathBuf};
+
+use generate_assets::{config::GeneratorConfig, snapshot::Snapshot};
+
+use crate::generate::{write_content, EmitArgs};
+
+/// Writes the website from a snapshot written by `scan`, without parsing the asset files or
+/// fetching their metadata.
+#[derive(clap::Args)]
+pub struct Args {
+    /// The snapshot written by `scan`
+    snapshot: PathBuf,
+
+    /// Path to the bevy-assets repository the images, featured assets and removed assets are
+    /// read from. Defaults to the folder the snapshot was scanned from
+    #[arg(long)]
+    asset_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    emit: EmitArgs,
+}
+
+pub fn run(args: Args, config: GeneratorConfig) -> anyhow::Result<()> {
+    let snapshot = Snapshot::read(&args.snapshot)?;
+    if !snapshot.enriched {
+        tracing::warn!(
+            "{} was scanned without metadata, the licenses and Bevy versions will be missing",
+            args.snapshot.display()
+        );
+    }
+    let asset_dir = args.asset_dir.unwrap_or(snapshot.asset_dir);
+    let content_dir = args.emit.content_dir(&config);
+    let _ = fs::create_dir(&content_dir);
+    write_content(&snapshot.root, &asset_dir, &content_dir, args.emit)
+}
@@ -0,0 +1,72 @@
+use std::{fs, path::Path, path::PathBuf};
+
+use anyhow::Context;
+
+use generate_assets::{
+    config::GeneratorConfig, failures::Failures, git::read_history, index::AssetIndex,
+    progress::Progress, write_back::write_metadata, Section,
+};
+
+use crate::metadata::MetadataArgs;
+
+/// Fetches the metadata of the assets and prints them as JSON, in the format of
+/// `assets.json`, without writing the website.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    #[command(flatten)]
+    metadata: MetadataArgs,
+
+    /// Write the assets to this file instead
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Write the fetched licenses, Bevy versions and latest versions back to the asset files
+    /// instead of printing the assets
+    #[arg(long, conflicts_with = "output")]
+    write: bool,
+}
+
+pub fn run(args: Args, config: GeneratorConfig, progress: &Progress) -> anyhow::Result<()> {
+    let asset_dir = args
+        .asset_dir
+        .unwrap_or_else(|| config.paths.asset_dir.clone());
+    let failures = Failures::new();
+    let mut asset_root_section = args
+        .metadata
+        .parse_assets(&asset_dir, config, progress, &failures)?;
+    if args.write {
+        write_back(&asset_root_section)?;
+        return args.metadata.check_failures(failures);
+    }
+    match read_history(Path::new(&asset_dir)) {
+        Ok(history) => asset_root_section.set_history(&history),
+        Err(err) => tracing::warn!(
+            "Failed to read the git history of the assets, dates will be missing: {err:#}"
+        ),
+    }
+
+    let index = serde_json::to_string_pretty(&AssetIndex::new(&asset_root_section))?;
+    match &args.output {
+        Some(path) => {
+            fs::write(path, index).with_context(|| format!("Writing {}", path.display()))?;
+        }
+        None => println!("{index}"),
+    }
+    args.metadata.check_failures(failures)
+}
+
+fn write_back(asset_root_section: &Section) -> anyhow::Result<()> {
+    let mut changed = 0;
+    for asset in asset_root_section.assets() {
+        if write_metadata(asset)? {
+            tracing::info!(asset = %asset.name, "Updated the metadata of the asset");
+            changed += 1;
+        }
+    }
+    tracing::info!("Updated {changed} asset files");
+    Ok(())
+}
@@ -0,0 +1,858 @@
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{self, prelude::*},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+};
+
+use generate_assets::{
+    api::write_api,
+    archive::Archive,
+    badges::Badge,
+    bevy_releases::BevyVersion,
+    cards::{Card, CARD_SUFFIX},
+    config::GeneratorConfig,
+    contact::Contacts,
+    demo::Demo,
+    diff::{read_index, IndexDiff},
+    failures::Failures,
+    git::read_removed_files,
+    index::AssetIndex,
+    markdown::write_markdown,
+    progress::Progress,
+    schema::parse_featured_file,
+    stats::Statistics,
+    walk::{is_asset_file, AssetWalker, Entry},
+    *,
+};
+
+use crate::{metadata::MetadataArgs, scan, validate};
+
+/// Number of assets in the recently added and recently updated listings.
+const RECENT_ASSETS: usize = 12;
+
+/// Generates the assets section of the website from the bevy-assets repository.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    #[command(flatten)]
+    metadata: MetadataArgs,
+
+    #[command(flatten)]
+    emit: EmitArgs,
+
+    /// Parse, validate and fetch the metadata of the assets without writing anything, and
+    /// print what would change. Use `--offline` to only use cached metadata
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Only fetch the metadata of the assets of this section and its sub-sections, given by
+    /// name or slug like `games`, and only rewrite their content. The listings, reports and
+    /// exports of every asset aren't written
+    #[arg(
+        long,
+        value_name = "SECTION",
+        conflicts_with_all = [
+            "dry_run",
+            "output",
+            "cards",
+            "badge_dir",
+            "api_dir",
+            "gone_report",
+            "archive",
+            "archive_page",
+            "diff_report",
+            "export",
+        ]
+    )]
+    only: Option<String>,
+}
+
+/// What is written to the content folder, and where, shared by `generate` and `emit`.
+#[derive(clap::Args)]
+pub struct EmitArgs {
+    /// Path to the content folder of the website, `../content` by default
+    #[arg(long)]
+    content_dir: Option<String>,
+
+    /// How the assets are written to the content folder
+    #[arg(long, value_enum, default_value_t = OutputFormat::Zola)]
+    output: OutputFormat,
+
+    /// Also write a detail page for each asset, with its full description, image,
+    /// supported Bevy versions and links
+    #[arg(long)]
+    asset_pages: bool,
+
+    /// Also render a social preview image for each asset, shown when its page is linked
+    #[arg(long)]
+    cards: bool,
+
+    /// Write a Bevy compatibility badge for each crates.io asset to this folder, as
+    /// `<crate name>.svg`
+    #[arg(long)]
+    badge_dir: Option<PathBuf>,
+
+    /// Write the static JSON API to this folder, with every asset, the assets supporting
+    /// each Bevy version, and a file for each asset
+    #[arg(long)]
+    api_dir: Option<PathBuf>,
+
+    /// Write the assets whose repository doesn't exist anymore as JSON to this file
+    #[arg(long)]
+    gone_report: Option<PathBuf>,
+
+    /// Keep the record of removed assets in this file, adding the assets removed since the
+    /// last run, so it isn't lost when the git history is
+    #[arg(long)]
+    archive: Option<PathBuf>,
+
+    /// Also write a page listing the removed assets
+    #[arg(long)]
+    archive_page: bool,
+
+    /// Write the assets added, removed and changed since the previous run as Markdown to
+    /// this file
+    #[arg(long)]
+    diff_report: Option<PathBuf>,
+
+    /// The `assets.json` file of the previous run, to compare with for `--diff-report`.
+    /// Defaults to the one in the content folder, before it is overwritten
+    #[arg(long)]
+    previous_index: Option<PathBuf>,
+
+    /// Also export the asset catalogue to this file, in the format given with `--format`
+    #[arg(long)]
+    export: Option<PathBuf>,
+
+    /// Format of the file written with `--export`
+    #[arg(long, value_enum, default_value_t = ExportFormat::Json, requires = "export")]
+    format: ExportFormat,
+}
+
+impl EmitArgs {
+    /// The content folder given with `--content-dir`, or set in `config`.
+    pub fn content_dir(&self, config: &GeneratorConfig) -> String {
+        self.content_dir
+            .clone()
+            .unwrap_or_else(|| config.paths.content_dir.clone())
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    /// Zola sections and pages, with the assets in their front matter
+    Zola,
+    /// A plain Markdown listing per section, for other static site generators
+    Markdown,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    /// The same content as `assets.json`
+    Json,
+    /// One row per asset, for spreadsheets
+    Csv,
+}
+
+pub fn run(args: Args, mut config: GeneratorConfig, progress: &Progress) -> anyhow::Result<()> {
+    let Args {
+        asset_dir,
+        mut metadata,
+        emit,
+        dry_run,
+        only,
+    } = args;
+    let asset_dir = asset_dir.unwrap_or_else(|| config.paths.asset_dir.clone());
+    let content_dir = emit.content_dir(&config);
+    // The previous run in the content folder is the metadata cache of `--offline`
+    config.paths.content_dir = content_dir.clone();
+
+    if dry_run {
+        return print_dry_run(&asset_dir, &content_dir, &metadata, config, progress);
+    }
+
+    let only_filter = only.map(|only| AssetFilter {
+        sections: vec![only],
+        assets: vec![],
+    });
+    if let Some(filter) = &only_filter {
+        let mut files = HashSet::new();
+        let walker =
+            AssetWalker::new(Path::new(&asset_dir))?.with_symlinks(metadata.symlink_policy());
+        if !section_files(Path::new(&asset_dir), &walker, filter, false, &mut files)? {
+            bail!("No section {} in {asset_dir}", filter.sections[0]);
+        }
+        metadata.only_files = Some(files);
+    }
+
+    let _ = fs::create_dir(content_dir.clone());
+    let failures = Failures::new();
+    let mut asset_root_section = metadata.parse_assets(&asset_dir, config, progress, &failures)?;
+    scan::set_history_and_relations(&mut asset_root_section, Path::new(&asset_dir));
+
+    if let Some(filter) = &only_filter {
+        write_sections(
+            &asset_root_section,
+            filter,
+            Path::new(&content_dir),
+            Path::new(""),
+            &OutputOptions {
+                asset_pages: emit.asset_pages,
+                cards: emit.cards,
+            },
+        )?;
+        return metadata.check_failures(failures);
+    }
+
+    write_content(
+        &asset_root_section,
+        Path::new(&asset_dir),
+        &content_dir,
+        emit,
+    )?;
+    metadata.check_failures(failures)
+}
+
+/// Writes the content of the website for the assets of `asset_root_section`, read from
+/// `asset_dir`, to `content_dir`, along with the listings, reports and exports of `args`.
+///
+/// Only the images, the featured assets and the removed assets are read from `asset_dir`.
+pub fn write_content(
+    asset_root_section: &Section,
+    asset_dir: &Path,
+    content_dir: &str,
+    args: EmitArgs,
+) -> anyhow::Result<()> {
+    let EmitArgs {
+        content_dir: _,
+        output,
+        asset_pages,
+        cards,
+        badge_dir,
+        api_dir,
+        gone_report,
+        archive,
+        archive_page,
+        diff_report,
+        previous_index,
+        export,
+        format,
+    } = args;
+
+    let assets_path = Path::new(&content_dir).join(&asset_root_section.slug);
+    match output {
+        OutputFormat::Zola => asset_root_section
+            .write(
+                Path::new(&content_dir),
+                Path::new(""),
+                0,
+                &OutputOptions { asset_pages, cards },
+            )
+            .expect("Failed to write assets section"),
+        OutputFormat::Markdown => {
+            write_markdown(asset_root_section, Path::new(&content_dir))?;
+            // The listings below are written next to the Markdown files
+            fs::create_dir_all(&assets_path)
+                .with_context(|| format!("Creating {}", assets_path.display()))?;
+        }
+    }
+
+    let index = AssetIndex::new(asset_root_section);
+    if let Some(report_path) = &diff_report {
+        let previous_path = previous_index.unwrap_or_else(|| assets_path.join("assets.json"));
+        let report = if previous_path.exists() {
+            IndexDiff::new(&read_index(&previous_path)?, &index).markdown()
+        } else {
+            format!(
+                "No previous run to compare with at {}\n",
+                previous_path.display()
+            )
+        };
+        println!("{report}");
+        fs::write(report_path, report)
+            .with_context(|| format!("Writing diff report to {}", report_path.display()))?;
+    }
+    write_json(&assets_path.join("assets.json"), &index)?;
+    fs::write(
+        assets_path.join("search_index.json"),
+        serde_json::to_string(&index.search_documents())?,
+    )
+    .context("Writing search index")?;
+    write_json(&assets_path.join("tags.json"), &index.tags())?;
+    write_json(
+        &assets_path.join("bevy_versions.json"),
+        &index.bevy_versions(),
+    )?;
+    write_json(&assets_path.join("licenses.json"), &index.licenses())?;
+    write_json(&assets_path.join("stats.json"), &Statistics::new(&index))?;
+    write_json(
+        &assets_path.join("recent.json"),
+        &index.recent(RECENT_ASSETS),
+    )?;
+    let featured_path = asset_dir.join(FEATURED_FILE);
+    let featured_config = if featured_path.exists() {
+        parse_featured_file(&featured_path)?
+    } else {
+        FeaturedConfig::default()
+    };
+    write_json(
+        &assets_path.join("featured.json"),
+        &index.featured(&featured_config),
+    )?;
+
+    if cards {
+        for asset in &index.assets {
+            let path = Path::new(&content_dir).join(format!("{}{CARD_SUFFIX}", asset.path));
+            Card::new(asset, Path::new(&content_dir))
+                .render()
+                .save(&path)
+                .with_context(|| format!("Writing card to {}", path.display()))?;
+        }
+    }
+
+    if let Some(api_dir) = api_dir {
+        write_api(&index, &api_dir)?;
+    }
+
+    if let Some(path) = export {
+        match format {
+            ExportFormat::Json => write_json(&path, &index)?,
+            ExportFormat::Csv => index
+                .write_csv(
+                    File::create(&path).with_context(|| format!("Creating {}", path.display()))?,
+                )
+                .with_context(|| format!("Writing {}", path.display()))?,
+        }
+    }
+
+    let mut asset_archive = match &archive {
+        Some(path) => Archive::read(path)?,
+        None => Archive::default(),
+    };
+    match read_removed_files(asset_dir) {
+        Ok(files) => {
+            asset_archive.add_removed_files(asset_dir, &files);
+        }
+        Err(err) => tracing::warn!(
+            "Failed to read the removed assets, the archive won't be updated: {err:#}"
+        ),
+    }
+    write_json(&assets_path.join("archive.json"), &asset_archive)?;
+    if let Some(path) = &archive {
+        write_json(path, &asset_archive)?;
+    }
+    if archive_page {
+        let path = Path::new(&content_dir).join("assets-archive.md");
+        fs::write(&path, archive_page_content(&asset_archive))
+            .with_context(|| format!("Writing {}", path.display()))?;
+    }
+    for (asset, archived) in asset_archive.resubmissions(&asset_root_section.assets()) {
+        tracing::warn!(
+            asset = %asset.name,
+            "Asset was submitted again, it was removed on {} as {}",
+            archived.removed,
+            archived.name
+        );
+    }
+
+    if let Some(badge_dir) = badge_dir {
+        fs::create_dir_all(&badge_dir)
+            .with_context(|| format!("Creating badge folder {}", badge_dir.display()))?;
+        for badge in asset_root_section
+            .assets()
+            .into_iter()
+            .filter_map(Badge::new)
+        {
+            let path = badge_dir.join(format!("{}.svg", badge.crate_name));
+            fs::write(&path, badge.svg())
+                .with_context(|| format!("Writing badge to {}", path.display()))?;
+        }
+    }
+
+    let gone_assets = asset_root_section
+        .assets()
+        .into_iter()
+        .filter_map(GoneAsset::new)
+        .collect::<Vec<_>>();
+    for asset in &gone_assets {
+        tracing::warn!(
+            asset = %asset.name,
+            file = %asset.path.display(),
+            link = %asset.link,
+            "Asset links to a repository that doesn't exist anymore"
+        );
+    }
+    if let Some(path) = gone_report {
+        write_json(&path, &gone_assets)?;
+    }
+    Ok(())
+}
+
+/// Collects the canonical paths of the asset files of the sections of `dir` matching
+/// `filter`, and of their sub-sections. Returns whether any section matched.
+fn section_files(
+    dir: &Path,
+    walker: &AssetWalker,
+    filter: &AssetFilter,
+    selected: bool,
+    files: &mut HashSet<PathBuf>,
+) -> anyhow::Result<bool> {
+    let mut matched = false;
+    for entry in walker.read_dir(dir)? {
+        match entry {
+            Entry::Dir(path) => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let selected_section = filter.matches_section(&name);
+                matched |= selected_section;
+                matched |=
+                    section_files(&path, walker, filter, selected || selected_section, files)?;
+            }
+            Entry::File(path) if selected && is_asset_file(&path) => {
+                files.insert(path.canonicalize()?);
+            }
+            Entry::File(_) => {}
+            Entry::Unreadable(path, source) => {
+                return Err(GenerateAssetsError::Io { path, source }.into())
+            }
+        }
+    }
+    Ok(matched)
+}
+
+/// Writes the content of the sections of `section` matching `filter`, with the same weights
+/// as when every section is written.
+fn write_sections(
+    section: &Section,
+    filter: &AssetFilter,
+    root_path: &Path,
+    current_path: &Path,
+    options: &OutputOptions,
+) -> io::Result<()> {
+    let section_path = current_path.join(&section.slug);
+    for (i, subsection) in section.sorted_subsections().into_iter().enumerate() {
+        if filter.matches_section(&subsection.name) {
+            fs::create_dir_all(root_path.join(&section_path))?;
+            subsection.write(root_path, &section_path, i, options)?;
+        } else {
+            write_sections(subsection, filter, root_path, &section_path, options)?;
+        }
+    }
+    Ok(())
+}
+
+/// Validates the assets and fetches their metadata like a normal run, but prints the assets
+/// that would be added, removed and changed in the content folder instead of writing it.
+fn print_dry_run(
+    asset_dir: &str,
+    content_dir: &str,
+    metadata: &MetadataArgs,
+    config: GeneratorConfig,
+    progress: &Progress,
+) -> anyhow::Result<()> {
+    let validation = validate::check_assets(asset_dir, config.clone());
+    let failures = Failures::new();
+    let asset_root_section = metadata.parse_assets(asset_dir, config, progress, &failures)?;
+    let index = AssetIndex::new(&asset_root_section);
+
+    let previous_path = Path::new(content_dir)
+        .join(&asset_root_section.slug)
+        .join("assets.json");
+    println!(
+        "\nDry run, {} assets would be written to {content_dir}\n",
+        index.assets.len()
+    );
+    if previous_path.exists() {
+        print!(
+            "{}",
+            IndexDiff::new(&read_index(&previous_path)?, &index).markdown()
+        );
+    } else {
+        println!(
+            "No previous run at {}, every asset would be added",
+            previous_path.display()
+        );
+    }
+    validation.and_then(|()| metadata.check_failures(failures))
+}
+
+/// Writes `value` as pretty-printed JSON to `path`.
+fn write_json(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("Writing {}", path.display()))
+}
+
+/// An asset whose repository doesn't exist anymore, which should probably be archived.
+#[derive(Serialize)]
+struct GoneAsset {
+    name: String,
+    link: String,
+    path: PathBuf,
+    /// The HTTP status returned for the repository, if any.
+    status: Option<u16>,
+}
+
+impl GoneAsset {
+    fn new(asset: &Asset) -> Option<Self> {
+        let gone = asset.repository_gone?;
+        Some(GoneAsset {
+            name: asset.name.clone(),
+            link: asset.link.to_string(),
+            path: asset.original_path.clone().unwrap_or_default(),
+            status: gone.status,
+        })
+    }
+}
+
+/// How the content of the website is written.
+struct OutputOptions {
+    /// Whether asset pages have content and are rendered, instead of only being listed in
+    /// their section.
+    asset_pages: bool,
+    /// Whether the front matter of assets links to their social preview image.
+    cards: bool,
+}
+
+trait FrontMatterWriter {
+    fn write(
+        &self,
+        root_path: &Path,
+        current_path: &Path,
+        weight: usize,
+        options: &OutputOptions,
+    ) -> io::Result<()>;
+}
+
+#[derive(Serialize)]
+struct FrontMatterAsset {
+    title: String,
+    description: String,
+    template: Option<String>,
+    weight: usize,
+    /// Previous URLs of the page, which Zola redirects to it.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    extra: FrontMatterAssetExtra,
+}
+
+#[derive(Serialize)]
+struct FrontMatterAssetExtra {
+    slug: String,
+    link: String,
+    image: Option<String>,
+    licenses: Option<Vec<String>>,
+    bevy_versions: Option<Vec<BevyVersion>>,
+    latest_version: Option<String>,
+    /// Set offline when the licenses or Bevy versions are missing, shown as a placeholder.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    metadata_missing: bool,
+    /// The source the asset was read from, if it isn't the asset folder.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    tags: Option<Vec<String>>,
+    deprecated: Option<Deprecation>,
+    demo: Option<Demo>,
+    icon: Option<Icon>,
+    pricing: Option<Pricing>,
+    platforms: Option<Vec<String>>,
+    /// Assets this asset works with, see [`Section::set_related`].
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    related: Vec<RelatedAsset>,
+    contacts: Option<Contacts>,
+    added: Option<String>,
+    updated: Option<String>,
+    /// Path of the social preview image of the asset on the website.
+    card: Option<String>,
+    #[serde(flatten)]
+    custom: Extra,
+}
+
+impl From<&Asset> for FrontMatterAsset {
+    fn from(asset: &Asset) -> Self {
+        FrontMatterAsset {
+            title: asset.name.clone(),
+            description: asset.description.clone(),
+            template: None,
+            weight: asset.order.unwrap_or(0),
+            aliases: asset.aliases.clone(),
+            extra: FrontMatterAssetExtra {
+                slug: asset.slug.clone(),
+                link: asset.link.to_string(),
+                image: asset.image.clone(),
+                licenses: asset.licenses.clone(),
+                bevy_versions: asset.bevy_versions.clone(),
+                latest_version: asset.latest_version.clone(),
+                metadata_missing: asset.metadata_missing,
+                source: asset.source.clone(),
+                tags: asset.tags.clone(),
+                deprecated: asset.deprecated.clone(),
+                demo: asset.demo.as_deref().and_then(|demo| Demo::new(demo).ok()),
+                icon: asset.icon.as_deref().map(Icon::new),
+                pricing: asset.pricing.clone(),
+                platforms: asset.platforms.clone(),
+                related: asset.related.clone(),
+                contacts: Some(Contacts::new(asset)).filter(|contacts| !contacts.is_empty()),
+                added: asset.added.clone(),
+                updated: asset.updated.clone(),
+                card: None,
+                custom: asset.extra.clone(),
+            },
+        }
+    }
+}
+
+impl FrontMatterWriter for Asset {
+    fn write(
+        &self,
+        root_path: &Path,
+        current_path: &Path,
+        weight: usize,
+        options: &OutputOptions,
+    ) -> io::Result<()> {
+        let path = root_path.join(current_path);
+
+        let mut frontmatter = FrontMatterAsset::from(self);
+        frontmatter.weight = weight;
+        if let Some(file) = self.image.as_ref() {
+            let image_file_path = path.join(file);
+            let image_file_link = current_path.join(file);
+            let original_image = self
+                .original_path
+                .as_ref()
+                .unwrap()
+                .clone()
+                .with_file_name(file);
+
+            frontmatter.extra.image = image_file_link.to_str().map(|link| link.to_string());
+            let _ = fs::copy(original_image, image_file_path);
+        }
+        if let Some(Icon::Image(file)) = frontmatter.extra.icon.as_mut() {
+            let original_icon = self.original_path.as_ref().unwrap().with_file_name(&*file);
+            let _ = fs::copy(original_icon, path.join(&*file));
+            *file = current_path.join(&*file).to_string_lossy().into_owned();
+        }
+
+        if options.cards {
+            frontmatter.extra.card = current_path
+                .join(format!("{}{CARD_SUFFIX}", self.slug))
+                .to_str()
+                .map(|link| link.to_string());
+        }
+
+        let content = if options.asset_pages {
+            frontmatter.template = Some("asset.html".to_string());
+            asset_page_content(self, frontmatter.extra.image.as_deref())
+        } else {
+            String::new()
+        };
+
+        let formatted_path = path.join(format!("{}.md", self.slug));
+
+        let mut file = File::create(formatted_path.clone())
+            .unwrap_or_else(|err| panic!("Failed to create file at {:?}\n{}", formatted_path, err));
+
+        file.write_all(
+            format!(
+                r#"+++
+{}
++++
+{}"#,
+                toml::to_string(&frontmatter).unwrap(),
+                content,
+            )
+            .as_bytes(),
+        )
+        .unwrap_or_else(|err| panic!("Failed to write at {:?}\n{}", formatted_path, err));
+
+        Ok(())
+    }
+}
+
+/// Markdown content of the detail page of an asset, `image` being the path of its image
+/// in the content folder.
+fn asset_page_content(asset: &Asset, image: Option<&str>) -> String {
+    let mut content = String::new();
+    if let Some(deprecation) = &asset.deprecated {
+        content += &format!("\n> **Deprecated:** {}", deprecation.reason);
+        if let Some(successor) = &deprecation.successor {
+            content += &format!(" Use {successor} instead.");
+        }
+        content += "\n";
+    }
+    content += &format!("\n{}\n", asset.description);
+    if let Some(image) = image {
+        content += &format!("\n![{}](/{})\n", asset.name, image);
+    }
+    if let Some(bevy_versions) = asset.bevy_versions.as_ref().filter(|v| !v.is_empty()) {
+        content += "\n## Bevy versions\n\n| Bevy version | Supported |\n| --- | --- |\n";
+        for version in bevy_versions {
+            content += &format!("| {version} | ✓ |\n");
+        }
+    }
+    if let Some(licenses) = asset.licenses.as_ref().filter(|l| !l.is_empty()) {
+        content += &format!("\n## Licenses\n\n{}\n", licenses.join(", "));
+    }
+    if let Some(tags) = asset.tags.as_ref().filter(|t| !t.is_empty()) {
+        content += &format!("\n## Tags\n\n{}\n", tags.join(", "));
+    }
+    if let Some(platforms) = asset.platforms.as_ref().filter(|p| !p.is_empty()) {
+        content += &format!("\n## Platforms\n\n{}\n", platforms.join(", "));
+    }
+    if let Some(pricing) = &asset.pricing {
+        content += &format!("\n## Pricing\n\n{pricing}\n");
+    }
+    content += &format!("\n## Links\n\n- <{}>\n", asset.link);
+    let contacts = Contacts::new(asset);
+    if !contacts.is_empty() {
+        content += "\n## Contact\n\n";
+        if let Some(discord) = &contacts.discord {
+            content += &format!("- [Discord]({discord})\n");
+        }
+        if let Some(mastodon) = &contacts.mastodon {
+            content += &format!("- [Mastodon]({mastodon})\n");
+        }
+        if let Some(contact) = &contacts.contact {
+            content += &format!("- <{}>\n", contact.trim_start_matches("mailto:"));
+        }
+    }
+    content
+}
+
+/// The page listing the removed assets of `archive`.
+fn archive_page_content(archive: &Archive) -> String {
+    let mut content =
+        "+++\ntitle = \"Archived Assets\"\ntemplate = \"page.html\"\n+++\n\n".to_string();
+    content += "Assets that were removed from the list of Bevy assets, with their last known \
+        information.\n\n| Asset | Section | Description | Removed |\n| --- | --- | --- | --- |\n";
+    for asset in &archive.assets {
+        content += &format!(
+            "| [{}]({}) | {} | {} | {} |\n",
+            asset.name.replace('|', "\\|"),
+            asset.link,
+            asset.sections.join(" / "),
+            asset.description.replace('|', "\\|").replace('\n', " "),
+            asset.removed,
+        );
+    }
+    content
+}
+
+impl FrontMatterWriter for AssetNode {
+    fn write(
+        &self,
+        root_path: &Path,
+        current_path: &Path,
+        weight: usize,
+        options: &OutputOptions,
+    ) -> io::Result<()> {
+        match self {
+            AssetNode::Section(content) => content.write(root_path, current_path, weight, options),
+            AssetNode::Asset(content) => content.write(root_path, current_path, weight, options),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct FrontMatterSection {
+    title: String,
+    sort_by: String,
+    paginate_by: Option<usize>,
+    template: Option<String>,
+    description: Option<String>,
+    weight: usize,
+    extra: FrontMatterSectionExtra,
+}
+
+#[derive(Serialize)]
+struct FrontMatterSectionExtra {
+    slug: String,
+    header_message: Option<String>,
+    sort_order_reversed: bool,
+    #[serde(flatten)]
+    custom: Extra,
+}
+
+impl From<&Section> for FrontMatterSectionExtra {
+    fn from(section: &Section) -> Self {
+        FrontMatterSectionExtra {
+            slug: section.slug.clone(),
+            header_message: section.header.clone(),
+            sort_order_reversed: section.sort_order_reversed,
+            custom: section.extra.clone(),
+        }
+    }
+}
+
+impl From<&Section> for FrontMatterSection {
+    fn from(section: &Section) -> Self {
+        FrontMatterSection {
+            title: section.name.clone(),
+            sort_by: "weight".to_string(),
+            paginate_by: section.paginate_by.map(NonZeroUsize::get),
+            // Paginated sections need a template rendering their paginator
+            template: section.template.clone().or_else(|| {
+                section
+                    .paginate_by
+                    .map(|_| "asset-section.html".to_string())
+            }),
+            description: section.description.clone(),
+            weight: section.order.unwrap_or(0),
+            extra: section.into(),
+        }
+    }
+}
+
+impl FrontMatterWriter for Section {
+    fn write(
+        &self,
+        root_path: &Path,
+        current_path: &Path,
+        weight: usize,
+        options: &OutputOptions,
+    ) -> io::Result<()> {
+        let section_path = current_path.join(&self.slug);
+        let path = root_path.join(&section_path);
+        if !path.exists() {
+            fs::create_dir(path.clone())
+                .unwrap_or_else(|_| panic!("Failed to create dir {:?}", path));
+        }
+
+        let mut frontmatter = FrontMatterSection::from(self);
+        if self.order.is_none() {
+            frontmatter.weight = weight;
+        }
+
+        let mut file = File::create(path.join("_index.md"))
+            .unwrap_or_else(|_| panic!("Failed to create _index.md at {:?}", path));
+        file.write_all(
+            format!(
+                r#"+++
+{}
++++
+"#,
+                toml::to_string(&frontmatter).unwrap(),
+            )
+            .as_bytes(),
+        )?;
+
+        let sorted_section = self.sorted_subsections();
+        for (i, section) in sorted_section.iter().enumerate() {
+            section.write(root_path, &section_path, i, options)?;
+        }
+        for (i, asset) in self.sorted_assets().into_iter().enumerate() {
+            let weight = match (self.sort_by, asset.order) {
+                (SortBy::Order, Some(order)) => order,
+                _ => sorted_section.len() + i,
+            };
+            asset.write(root_path, &section_path, weight, options)?;
+        }
+        Ok(())
+    }
+}
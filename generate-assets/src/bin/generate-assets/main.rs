@@ -0,0 +1,107 @@
+//! Generates and validates the assets section of the website from the bevy-assets repository.
+
+use std::path::PathBuf;
+
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use generate_assets::{config::GeneratorConfig, progress::Progress};
+use tracing::level_filters::LevelFilter;
+
+mod cache;
+mod diff;
+mod doctor;
+mod emit;
+mod enrich;
+mod generate;
+mod metadata;
+mod new_asset;
+mod prompt;
+mod scan;
+mod validate;
+
+/// Generates and validates the assets section of the website from the bevy-assets repository.
+#[derive(Parser)]
+#[command(about)]
+struct Cli {
+    /// Read the configuration from this file, instead of `generate_assets.toml` if it exists
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Log more details, twice to log everything
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Only log warnings and errors, twice to only log errors
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    quiet: u8,
+
+    /// Format of the logs, written to stderr
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    /// Human readable lines
+    Text,
+    /// One JSON object per line, with the section and asset each log is about
+    Json,
+}
+
+impl Cli {
+    fn log_level(&self) -> LevelFilter {
+        match (self.verbose, self.quiet) {
+            (0, 0) => LevelFilter::INFO,
+            (1, _) => LevelFilter::DEBUG,
+            (_, 0) => LevelFilter::TRACE,
+            (_, 1) => LevelFilter::WARN,
+            _ => LevelFilter::ERROR,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Generate(generate::Args),
+    Scan(scan::Args),
+    Emit(emit::Args),
+    Validate(validate::Args),
+    Enrich(enrich::Args),
+    Diff(diff::Args),
+    Cache(cache::Args),
+    Doctor(doctor::Args),
+    NewAsset(new_asset::Args),
+}
+
+fn main() -> anyhow::Result<()> {
+    // Don't fail if file is not present, like in CI, just ignore it
+    let _ = dotenv::dotenv();
+
+    let cli = Cli::parse();
+    // Logs are written through the progress bars to be printed above them, along with a
+    // status line for each asset unless they are JSON or only warnings are wanted
+    let progress =
+        Progress::new().status_lines(matches!(cli.log_format, LogFormat::Text) && cli.quiet == 0);
+    let logs = tracing_subscriber::fmt()
+        .with_max_level(cli.log_level())
+        .with_writer(progress.clone());
+    match cli.log_format {
+        LogFormat::Text => logs.init(),
+        LogFormat::Json => logs.json().init(),
+    }
+
+    let config = GeneratorConfig::load(cli.config.as_deref())?;
+    match cli.command {
+        Command::Generate(args) => generate::run(args, config, &progress),
+        Command::Scan(args) => scan::run(args, config, &progress),
+        Command::Emit(args) => emit::run(args, config),
+        Command::Validate(args) => validate::run(args, config),
+        Command::Enrich(args) => enrich::run(args, config, &progress),
+        Command::Diff(args) => diff::run(args),
+        Command::Cache(args) => cache::run(args, config),
+        Command::Doctor(args) => doctor::run(args, config),
+        Command::NewAsset(args) => new_asset::run(args, config),
+    }
+}
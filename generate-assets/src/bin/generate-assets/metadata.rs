@@ -0,0 +1,417 @@
+//! Options of the subcommands fetching the metadata of assets from crates.io, Github and
+//! Gitlab.
+
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use generate_assets::{
+    config::{GeneratorConfig, CONFIG_FILE},
+    diff::read_index,
+    failures::{summary, ErrorMode, FailureReport, Failures},
+    github_client::GithubClient,
+    gitlab_client::GitlabClient,
+    http::HttpContext,
+    index::{AssetIndex, INDEX_FORMAT_VERSION},
+    progress::Progress,
+    sources::fetch_source,
+    walk::SymlinkPolicy,
+    *,
+};
+
+/// Tokens used to fetch metadata from Github and Gitlab.
+#[derive(clap::Args)]
+pub struct TokenArgs {
+    /// Token used to fetch metadata from Github, Github links are skipped without it.
+    /// Read from `GITHUB_TOKEN` by default, or from the source set in the configuration
+    #[arg(long)]
+    pub github_token: Option<String>,
+
+    /// Token used to fetch metadata from Gitlab. Read from `GITLAB_TOKEN` by default, or from
+    /// the source set in the configuration
+    #[arg(long)]
+    pub gitlab_token: Option<String>,
+}
+
+impl TokenArgs {
+    /// The Github token given with `--github-token`, or read from its source in `config`.
+    pub fn github_token(&self, config: &GeneratorConfig) -> anyhow::Result<Option<String>> {
+        match &self.github_token {
+            Some(token) => Ok(Some(token.clone())),
+            None => {
+                config.tokens.github.read().with_context(|| {
+                    format!("Reading the Github token from {}", config.tokens.github)
+                })
+            }
+        }
+    }
+
+    /// The Gitlab token given with `--gitlab-token`, or read from its source in `config`.
+    pub fn gitlab_token(&self, config: &GeneratorConfig) -> anyhow::Result<Option<String>> {
+        match &self.gitlab_token {
+            Some(token) => Ok(Some(token.clone())),
+            None => {
+                config.tokens.gitlab.read().with_context(|| {
+                    format!("Reading the Gitlab token from {}", config.tokens.gitlab)
+                })
+            }
+        }
+    }
+
+    /// The Github client, if it is enabled and has a token.
+    pub fn github_client(
+        &self,
+        config: &GeneratorConfig,
+        http: &HttpContext,
+    ) -> anyhow::Result<Option<GithubClient>> {
+        if !config.clients.github {
+            return Ok(None);
+        }
+        let token = self.github_token(config)?;
+        Ok(token.map(|token| GithubClient::new(token, http)))
+    }
+
+    /// The Gitlab client if it is enabled, which can read public projects without a token.
+    pub fn gitlab_client(
+        &self,
+        config: &GeneratorConfig,
+        http: &HttpContext,
+    ) -> anyhow::Result<Option<GitlabClient>> {
+        if !config.clients.gitlab {
+            return Ok(None);
+        }
+        let token = self.gitlab_token(config)?;
+        Ok(Some(GitlabClient::new(token.unwrap_or_default(), http)))
+    }
+}
+
+/// Which assets are parsed and enriched.
+#[derive(clap::Args)]
+pub struct FilterArgs {
+    /// Only process the assets of this section and its sub-sections, given by name or slug,
+    /// like `2d`. Can be repeated
+    #[arg(long = "section", value_name = "SECTION")]
+    pub sections: Vec<String>,
+
+    /// Only process this asset, given by name or slug, like `bevy_ecs_tilemap`. Can be
+    /// repeated
+    #[arg(long = "asset", value_name = "ASSET")]
+    pub assets: Vec<String>,
+}
+
+impl FilterArgs {
+    pub fn filter(&self) -> AssetFilter {
+        AssetFilter {
+            sections: self.sections.clone(),
+            assets: self.assets.clone(),
+        }
+    }
+}
+
+/// Where and how fast the metadata of assets is fetched.
+#[derive(clap::Args)]
+pub struct MetadataArgs {
+    /// Maximum number of assets fetched in parallel
+    #[arg(short, long)]
+    pub jobs: Option<usize>,
+
+    /// Maximum number of assets fetched in parallel from Github
+    #[arg(long)]
+    pub github_jobs: Option<usize>,
+
+    /// Maximum number of assets fetched in parallel from Gitlab
+    #[arg(long)]
+    pub gitlab_jobs: Option<usize>,
+
+    /// Only load bevy related crates from the crates.io dump, using much less memory
+    #[arg(long)]
+    pub filter_crates_dump: bool,
+
+    /// Folder the crates.io database dump is downloaded to
+    #[arg(long)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Don't use the network: only use the crates.io database dump downloaded by a previous
+    /// run and the metadata of the previous `assets.json`, without any request to crates.io,
+    /// Github or Gitlab
+    #[arg(long)]
+    pub offline: bool,
+
+    /// The `assets.json` file of a previous run, giving the metadata of the assets with
+    /// `--offline`. Defaults to the one in the content folder
+    #[arg(long, requires = "offline")]
+    pub metadata_cache: Option<PathBuf>,
+
+    /// Stop at the first asset failing to parse or whose metadata can't be fetched
+    #[arg(long, conflicts_with = "keep_going")]
+    pub fail_fast: bool,
+
+    /// Leave out the assets failing to parse or whose metadata can't be fetched, then list
+    /// every failure at the end and exit with an error
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Write every asset failing to parse or whose metadata can't be fetched to this JSON
+    /// file at the end of the run, with the kind of error and the URL of the failed request
+    #[arg(long)]
+    pub failure_report: Option<PathBuf>,
+
+    /// Skip the links found in the asset folder, instead of following them
+    #[arg(long)]
+    pub skip_symlinks: bool,
+
+    #[command(flatten)]
+    pub tokens: TokenArgs,
+
+    #[command(flatten)]
+    pub filter: FilterArgs,
+
+    /// Only fetch the metadata of the assets read from these files, given as canonical paths
+    #[arg(skip)]
+    pub only_files: Option<HashSet<PathBuf>>,
+}
+
+impl MetadataArgs {
+    /// Overrides the configuration of the generator with these options.
+    pub fn apply(&self, config: &mut GeneratorConfig) {
+        if let Some(jobs) = self.jobs {
+            config.concurrency.jobs = jobs;
+        }
+        if let Some(github_jobs) = self.github_jobs {
+            config.concurrency.github_jobs = github_jobs;
+        }
+        if let Some(gitlab_jobs) = self.gitlab_jobs {
+            config.concurrency.gitlab_jobs = gitlab_jobs;
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            config.paths.cache_dir = cache_dir.clone();
+        }
+        if self.offline {
+            config.clients.github = false;
+            config.clients.gitlab = false;
+        }
+    }
+
+    pub fn crates_dump_filter(&self) -> CratesDumpFilter {
+        if self.filter_crates_dump {
+            CratesDumpFilter::BevyRelated
+        } else {
+            CratesDumpFilter::All
+        }
+    }
+
+    pub fn error_mode(&self) -> ErrorMode {
+        if self.fail_fast {
+            ErrorMode::FailFast
+        } else if self.keep_going {
+            ErrorMode::KeepGoing
+        } else {
+            ErrorMode::Lenient
+        }
+    }
+
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        if self.skip_symlinks {
+            SymlinkPolicy::Skip
+        } else {
+            SymlinkPolicy::Follow
+        }
+    }
+
+    /// Parses the assets of `asset_dir` matching `--section` and `--asset`, with their metadata
+    /// from the crates.io database dump, Github and Gitlab, unless they are disabled in
+    /// `config` or `--offline` is set.
+    ///
+    /// With `--keep-going`, the failures are collected in `failures`, to be reported with
+    /// [`MetadataArgs::check_failures`] once the output is written.
+    pub fn parse_assets(
+        &self,
+        asset_dir: &str,
+        config: GeneratorConfig,
+        progress: &Progress,
+        failures: &Failures,
+    ) -> anyhow::Result<Section> {
+        let mut section = self.scan_assets(asset_dir, &config, failures)?;
+        self.enrich_assets(&mut section, config, progress, failures)?;
+        Ok(section)
+    }
+
+    /// Parses the assets of `asset_dir` matching `--section` and `--asset`, without their
+    /// metadata, along with the ones of the sources of `config`. With `--offline`, the
+    /// sources fetched by a previous run are read instead.
+    pub fn scan_assets(
+        &self,
+        asset_dir: &str,
+        config: &GeneratorConfig,
+        failures: &Failures,
+    ) -> anyhow::Result<Section> {
+        let scan = |dir: &Path| {
+            scan_assets(
+                dir,
+                &self.filter.filter(),
+                self.symlink_policy(),
+                self.error_mode(),
+                Some(failures),
+            )
+        };
+        let mut section = scan(Path::new(asset_dir))?;
+        if config.sources.is_empty() {
+            return Ok(section);
+        }
+
+        let http = if self.offline {
+            None
+        } else {
+            Some(HttpContext::new(&config.http)?)
+        };
+        let cache_dir = self.cache_dir.as_ref().unwrap_or(&config.paths.cache_dir);
+        for source in &config.sources {
+            let dir = fetch_source(source, cache_dir, http.as_ref())?;
+            section.merge_source(&source.name, scan(&dir)?);
+        }
+        Ok(section)
+    }
+
+    /// Gathers the metadata of the assets of `section` like [`MetadataArgs::parse_assets`].
+    pub fn enrich_assets(
+        &self,
+        section: &mut Section,
+        mut config: GeneratorConfig,
+        progress: &Progress,
+        failures: &Failures,
+    ) -> anyhow::Result<()> {
+        self.apply(&mut config);
+        if self.offline {
+            return self.enrich_assets_offline(section, &config, progress, failures);
+        }
+
+        let http = HttpContext::new(&config.http)?;
+        let db = if config.clients.crates_io {
+            Some(prepare_crates_db(
+                self.crates_dump_filter(),
+                &config.paths.cache_dir,
+                false,
+            )?)
+        } else {
+            None
+        };
+        let github_client = self.tokens.github_client(&config, &http)?;
+        if config.clients.github && github_client.is_none() {
+            // This should be configured in CI, but it's not mandatory if running locally
+            tracing::warn!(
+                "No Github token in {}, github links will be skipped. It can also be read \
+                from a file, the keyring or `gh auth token`, see `[tokens]` in {CONFIG_FILE}",
+                config.tokens.github
+            );
+        }
+        let gitlab_client = self.tokens.gitlab_client(&config, &http)?;
+
+        let result = enrich_assets(
+            section,
+            MetadataSource {
+                crates_io_db: db.as_ref(),
+                github_client: github_client.as_ref().map(|client| client as _),
+                gitlab_client: gitlab_client.as_ref().map(|client| client as _),
+                progress: Some(progress),
+                concurrency: config.concurrency,
+                only_files: self.only_files.as_ref(),
+                error_mode: self.error_mode(),
+                failures: Some(failures),
+                ..Default::default()
+            },
+        );
+        progress.print_summary();
+        result
+    }
+
+    /// Gathers the metadata of the assets without any network client, from the cached
+    /// crates.io database dump, then from the `assets.json` file of a previous run.
+    fn enrich_assets_offline(
+        &self,
+        section: &mut Section,
+        config: &GeneratorConfig,
+        progress: &Progress,
+        failures: &Failures,
+    ) -> anyhow::Result<()> {
+        let db = if config.clients.crates_io {
+            match prepare_crates_db(self.crates_dump_filter(), &config.paths.cache_dir, true) {
+                Ok(db) => Some(db),
+                Err(err) => {
+                    tracing::warn!(
+                        "No cached crates.io data dump, crates.io metadata will be skipped: \
+                        {err:#}"
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let result = enrich_assets(
+            section,
+            MetadataSource {
+                crates_io_db: db.as_ref(),
+                progress: Some(progress),
+                concurrency: config.concurrency,
+                only_files: self.only_files.as_ref(),
+                error_mode: self.error_mode(),
+                failures: Some(failures),
+                ..Default::default()
+            },
+        );
+        progress.print_summary();
+        result?;
+
+        let cache_path = self.metadata_cache.clone().unwrap_or_else(|| {
+            Path::new(&config.paths.content_dir)
+                .join(&section.slug)
+                .join("assets.json")
+        });
+        let cached = match read_index(&cache_path) {
+            Ok(index) => index,
+            Err(err) => {
+                tracing::warn!(
+                    "No metadata cache, only the cached crates.io metadata is used: {err:#}"
+                );
+                AssetIndex {
+                    version: INDEX_FORMAT_VERSION,
+                    assets: vec![],
+                }
+            }
+        };
+        section.set_cached_metadata(&cached);
+        let missing = section
+            .assets()
+            .iter()
+            .filter(|asset| asset.metadata_missing)
+            .count();
+        if missing > 0 {
+            tracing::warn!("{missing} assets miss their licenses or Bevy versions offline");
+        }
+        Ok(())
+    }
+
+    /// Writes the `--failure-report`, then fails with the list of `failures` collected with
+    /// `--keep-going`, if there are any.
+    pub fn check_failures(&self, failures: Failures) -> anyhow::Result<()> {
+        let failures = failures.into_vec();
+        if let Some(path) = &self.failure_report {
+            let report = serde_json::to_string_pretty(&FailureReport::new(&failures))?;
+            fs::write(path, report + "\n")
+                .with_context(|| format!("Writing {}", path.display()))?;
+        }
+        let failures: Vec<_> = failures
+            .into_iter()
+            .filter(|failure| !failure.tolerated)
+            .collect();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        bail!("{}", summary(&failures))
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use serde::Serialize;
+
+use generate_assets::{
+    config::GeneratorConfig,
+    get_metadata,
+    http::HttpContext,
+    prepare_crates_db,
+    schema::parse_asset_file,
+    slugify,
+    spdx::{is_valid_license, suggest_license},
+    walk::{AssetWalker, Entry},
+    AssetLink, CratesDumpFilter, ExtraMetadata, MetadataSource,
+};
+
+use crate::{
+    metadata::TokenArgs,
+    prompt::{ask, ask_required},
+};
+
+/// Writes the file of a new asset, asking for anything not given with the flags, with its
+/// licenses and Bevy version filled from crates.io, Github or Gitlab.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    /// Name of the asset
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Link to the crate, repository or website of the asset
+    #[arg(long)]
+    link: Option<String>,
+
+    /// Short description of the asset
+    #[arg(long)]
+    description: Option<String>,
+
+    /// Folder of the section, relative to the asset folder, like `Assets/2D`
+    #[arg(long)]
+    section: Option<PathBuf>,
+
+    /// Keep the licenses and Bevy version found for the asset without asking
+    #[arg(short, long)]
+    yes: bool,
+
+    /// Folder the crates.io database dump was downloaded to. The dump isn't downloaded, the
+    /// licenses of crates.io links are only filled if a previous run cached it
+    #[arg(long)]
+    cache_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    tokens: TokenArgs,
+}
+
+/// The content of a new asset file, in the order of the files of bevy-assets.
+#[derive(Serialize)]
+struct NewAsset {
+    name: String,
+    description: String,
+    link: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    licenses: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bevy_versions: Option<Vec<String>>,
+}
+
+pub fn run(args: Args, config: GeneratorConfig) -> anyhow::Result<()> {
+    let asset_dir = PathBuf::from(
+        args.asset_dir
+            .clone()
+            .unwrap_or_else(|| config.paths.asset_dir.clone()),
+    );
+    if !asset_dir.is_dir() {
+        bail!(
+            "{} is not a folder, set it with --asset-dir",
+            asset_dir.display()
+        );
+    }
+
+    let name = match &args.name {
+        Some(name) => name.clone(),
+        None => ask_required("Name")?,
+    };
+    let link = match &args.link {
+        Some(link) => link.clone(),
+        None => ask_required("Link")?,
+    };
+    let link = link
+        .parse::<AssetLink>()
+        .with_context(|| format!("Invalid link: {link}"))?
+        .to_string();
+    let description = match &args.description {
+        Some(description) => description.clone(),
+        None => ask_required("Description")?,
+    };
+    let section = match &args.section {
+        Some(section) => section.clone(),
+        None => choose_section(&asset_dir)?,
+    };
+    let section_dir = asset_dir.join(&section);
+    if !section_dir.is_dir() {
+        bail!("{} is not a section folder", section_dir.display());
+    }
+    let path = section_dir.join(format!("{}.toml", slugify(&name)));
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+
+    let metadata = fetch_metadata(&args, &config, &link);
+    let license = metadata
+        .as_ref()
+        .and_then(|metadata| metadata.license.clone());
+    let license = if args.yes {
+        license
+    } else {
+        ask("Licenses, separated with OR", license.as_deref())?
+    };
+    let bevy_version = metadata
+        .and_then(|metadata| metadata.bevy_version)
+        // The release of the version requirement, like `0.13` for `^0.13.1`
+        .map(|version| version.minor().to_string());
+    let bevy_version = if args.yes {
+        bevy_version
+    } else {
+        ask("Bevy version", bevy_version.as_deref())?
+    };
+
+    let asset = NewAsset {
+        name,
+        description,
+        link,
+        licenses: license.map(|license| split_licenses(&license)),
+        bevy_versions: bevy_version.map(|version| vec![version]),
+    };
+    fs::write(&path, toml::to_string(&asset)?)
+        .with_context(|| format!("Writing {}", path.display()))?;
+    // Catches anything the prompts let through, like an empty description
+    if let Err(err) = parse_asset_file(&path) {
+        let _ = fs::remove_file(&path);
+        return Err(err.into());
+    }
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Gets the metadata of the asset at `link`, from the cached crates.io database dump or the
+/// clients with a token, logging why it couldn't.
+fn fetch_metadata(args: &Args, config: &GeneratorConfig, link: &str) -> Option<ExtraMetadata> {
+    let cache_dir = args
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| config.paths.cache_dir.clone());
+    let db = config
+        .clients
+        .crates_io
+        .then(|| prepare_crates_db(CratesDumpFilter::BevyRelated, &cache_dir, true))
+        .and_then(|db| {
+            db.map_err(|err| tracing::debug!("No cached crates.io data dump: {err:#}"))
+                .ok()
+        });
+    let clients = HttpContext::new(&config.http).and_then(|http| {
+        Ok((
+            args.tokens.github_client(config, &http)?,
+            args.tokens.gitlab_client(config, &http)?,
+        ))
+    });
+    let (github_client, gitlab_client) = match clients {
+        Ok(clients) => clients,
+        Err(err) => {
+            tracing::warn!("Failed to create the clients, the metadata won't be filled: {err:#}");
+            (None, None)
+        }
+    };
+
+    let metadata = get_metadata(
+        link,
+        MetadataSource {
+            crates_io_db: db.as_ref(),
+            github_client: github_client.as_ref().map(|client| client as _),
+            gitlab_client: gitlab_client.as_ref().map(|client| client as _),
+            ..Default::default()
+        },
+    );
+    match metadata {
+        Ok(Some(metadata)) => Some(metadata),
+        Ok(None) => {
+            tracing::info!("No source to fill the licenses and Bevy version from");
+            None
+        }
+        Err(err) => {
+            tracing::warn!("Failed to get the metadata of the asset: {err:#}");
+            None
+        }
+    }
+}
+
+/// Splits licenses separated with `OR`, replacing invalid ones with the closest SPDX
+/// identifier.
+fn split_licenses(license: &str) -> Vec<String> {
+    license
+        .split(" OR ")
+        .map(str::trim)
+        .filter(|license| !license.is_empty())
+        .map(|license| {
+            if is_valid_license(license) {
+                license.to_string()
+            } else {
+                suggest_license(license).unwrap_or(license).to_string()
+            }
+        })
+        .collect()
+}
+
+/// Lists the section folders of `asset_dir` and asks which one the asset goes in.
+fn choose_section(asset_dir: &Path) -> anyhow::Result<PathBuf> {
+    let mut sections = vec![];
+    let walker = AssetWalker::new(asset_dir)?;
+    section_folders(&walker, asset_dir, Path::new(""), &mut sections)?;
+    sections.sort();
+    if sections.is_empty() {
+        bail!("No section folder in {}", asset_dir.display());
+    }
+    for (index, section) in sections.iter().enumerate() {
+        println!("{:>3}. {}", index + 1, section.display());
+    }
+    loop {
+        let choice = ask_required("Section number")?;
+        match choice.parse::<usize>() {
+            Ok(index) if (1..=sections.len()).contains(&index) => {
+                return Ok(sections.swap_remove(index - 1));
+            }
+            _ => println!("Enter a number between 1 and {}", sections.len()),
+        }
+    }
+}
+
+fn section_folders(
+    walker: &AssetWalker,
+    dir: &Path,
+    relative: &Path,
+    sections: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    for entry in walker.read_dir(dir)? {
+        if let Entry::Dir(path) = entry {
+            let relative = relative.join(path.file_name().unwrap_or_default());
+            section_folders(walker, &path, &relative, sections)?;
+            sections.push(relative);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+//! Questions asked on the terminal by the interactive subcommands.
+
+use std::io::{self, Write};
+
+use anyhow::bail;
+
+/// Asks a question, returning `default` when the answer is empty and `None` when there is
+/// no default either.
+pub fn ask(question: &str, default: Option<&str>) -> anyhow::Result<Option<String>> {
+    match default {
+        Some(default) => print!("{question} [{default}]: "),
+        None => print!("{question}: "),
+    }
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    if io::stdin().read_line(&mut answer)? == 0 {
+        bail!("No answer to {question}");
+    }
+    let answer = answer.trim();
+    Ok(if answer.is_empty() {
+        default.map(str::to_string)
+    } else {
+        Some(answer.to_string())
+    })
+}
+
+/// Asks a question until it is answered.
+pub fn ask_required(question: &str) -> anyhow::Result<String> {
+    loop {
+        if let Some(answer) = ask(question, None)? {
+            return Ok(answer);
+        }
+    }
+}
+
+/// Asks a yes or no question, no being the default.
+pub fn confirm(question: &str) -> anyhow::Result<bool> {
+    loop {
+        let answer = ask(&format!("{question} [y/N]"), None)?;
+        match answer.as_deref().map(str::to_ascii_lowercase).as_deref() {
+            None | Some("n" | "no") => return Ok(false),
+            Some("y" | "yes") => return Ok(true),
+            Some(_) => println!("Answer y or n"),
+        }
+    }
+}
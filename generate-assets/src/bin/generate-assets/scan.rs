@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+
+use generate_assets::{
+    config::GeneratorConfig,
+    failures::Failures,
+    git::{read_history, read_renames},
+    progress::Progress,
+    snapshot::Snapshot,
+    Section,
+};
+
+use crate::metadata::MetadataArgs;
+
+/// Parses the asset files and fetches their metadata into a snapshot, printed as JSON, which
+/// `emit` writes the website from.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    #[command(flatten)]
+    metadata: MetadataArgs,
+
+    /// Only parse the asset files and read their git history, without fetching their metadata
+    #[arg(long)]
+    no_metadata: bool,
+
+    /// Fetch the metadata of the assets of this snapshot, written by a previous `scan`,
+    /// instead of parsing the asset files
+    #[arg(long, conflicts_with_all = ["asset_dir", "no_metadata", "sections", "assets"])]
+    snapshot: Option<PathBuf>,
+
+    /// Write the snapshot to this file instead
+    #[arg(long)]
+    output: Option<PathBuf>,
+}
+
+pub fn run(args: Args, config: GeneratorConfig, progress: &Progress) -> anyhow::Result<()> {
+    let failures = Failures::new();
+    let mut snapshot = match &args.snapshot {
+        Some(path) => Snapshot::read(path)?,
+        None => {
+            let asset_dir = args
+                .asset_dir
+                .unwrap_or_else(|| config.paths.asset_dir.clone());
+            let mut section = args.metadata.scan_assets(&asset_dir, &config, &failures)?;
+            set_history_and_relations(&mut section, Path::new(&asset_dir));
+            Snapshot::new(asset_dir, section)
+        }
+    };
+    if !args.no_metadata {
+        args.metadata
+            .enrich_assets(&mut snapshot.root, config, progress, &failures)?;
+        snapshot.enriched = true;
+    }
+
+    match &args.output {
+        Some(path) => snapshot.write(path)?,
+        None => print!("{}", snapshot.to_json()?),
+    }
+    args.metadata.check_failures(failures)
+}
+
+/// Sets the dates and previous URLs of the assets of `section` from the git history of
+/// `asset_dir`, and the assets they are related to.
+pub fn set_history_and_relations(section: &mut Section, asset_dir: &Path) {
+    match read_history(asset_dir) {
+        Ok(history) => section.set_history(&history),
+        Err(err) => tracing::warn!(
+            "Failed to read the git history of the assets, dates will be missing: {err:#}"
+        ),
+    }
+    match read_renames(asset_dir) {
+        Ok(renames) => section.set_aliases(&renames, asset_dir),
+        Err(err) => tracing::warn!(
+            "Failed to read the renamed assets, their old pages won't redirect: {err:#}"
+        ),
+    }
+    section.set_related();
+}
@@ -0,0 +1,619 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
+
+use generate_assets::{
+    concurrency::for_each_parallel,
+    config::GeneratorConfig,
+    http::HttpContext,
+    links::{check_link, LinkStatus},
+    schema::{
+        parse_duplicates_file, parse_featured_file, read_asset_files, validate_asset_files,
+        AssetFormat,
+    },
+    validation::{
+        check_featured, find_duplicates, AssetValidator, Rules, Severity, DEFAULT_MAX_IMAGE_BYTES,
+    },
+    walk::AssetWalker,
+    write_back::{apply_fix, Fix},
+    *,
+};
+
+use crate::{metadata::TokenArgs, prompt::confirm};
+
+/// Extensions of files considered images when looking for unused images, allowed or not.
+const IMAGE_EXTENSIONS: &[&str] = &["gif", "jpg", "jpeg", "png", "webp", "svg", "bmp", "avif"];
+
+/// Validates the assets of the bevy-assets repository.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the bevy-assets repository, `assets` by default
+    #[arg(long)]
+    asset_dir: Option<String>,
+
+    /// Maximum size of asset images, in bytes or with a unit like `500KB` or `2MiB`
+    #[arg(long, default_value_t = DEFAULT_MAX_IMAGE_BYTES, value_parser = parse_size)]
+    max_image_size: u64,
+
+    /// Only validate asset files changed since this git ref, like `origin/main`.
+    /// Their metadata is fetched, and warnings are denied.
+    #[arg(long)]
+    changed_since: Option<String>,
+
+    /// Fail the validation on problems of this severity or higher
+    #[arg(long, value_enum, default_value_t = DenyLevel::Errors)]
+    deny: DenyLevel,
+
+    /// Also write the results as JSON to this file
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Offer to fix the common problems, like invalid licenses, missing images and, with
+    /// `validate links`, redirected links, and apply the fixes confirmed to the asset files
+    #[arg(short, long)]
+    interactive: bool,
+
+    #[command(flatten)]
+    tokens: TokenArgs,
+
+    /// Only run a single check, instead of the default checks
+    #[command(subcommand)]
+    check: Option<Check>,
+}
+
+#[derive(Subcommand)]
+enum Check {
+    /// Check that every asset file matches the expected schema
+    Schema,
+    /// Check that every asset link can be reached, this is not part of the default checks
+    Links,
+    /// List image files that no asset references
+    OrphanImages {
+        /// Delete the orphan images instead of listing them
+        #[arg(long)]
+        delete: bool,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DenyLevel {
+    Errors,
+    Warnings,
+    Info,
+}
+
+impl DenyLevel {
+    /// Lowest severity failing the validation.
+    fn threshold(self) -> Severity {
+        match self {
+            DenyLevel::Errors => Severity::Error,
+            DenyLevel::Warnings => Severity::Warning,
+            DenyLevel::Info => Severity::Info,
+        }
+    }
+}
+
+pub fn run(args: Args, config: GeneratorConfig) -> Result<()> {
+    let report_path = args.report.clone();
+    let interactive = args.interactive;
+    let mut threshold = args.deny.threshold();
+    if args.changed_since.is_some() {
+        // Contributors should fix every warning of the files they change
+        threshold = threshold.min(Severity::Warning);
+    }
+
+    let mut report = Report::default();
+    let result = run_checks(args, config, &mut report);
+    // Fixes are offered even if a check failed, like unreachable links next to redirected ones
+    let fixed = if interactive {
+        report.fix_interactively()
+    } else {
+        Ok(())
+    };
+    let result = result.and(fixed).and_then(|()| report.check(threshold));
+
+    if let Some(path) = report_path {
+        let written = serde_json::to_string_pretty(&report)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(fs::write(&path, json)?))
+            .with_context(|| format!("Writing report to {}", path.display()));
+        if let Err(err) = written {
+            if result.is_ok() {
+                return Err(err);
+            }
+            // The result of the checks is returned, the failure to write the report logged
+            tracing::error!("{err:#}");
+        }
+    }
+    result
+}
+
+/// Runs the default checks on `asset_dir`, failing on errors, like `validate` without flags.
+pub fn check_assets(asset_dir: &str, config: GeneratorConfig) -> Result<()> {
+    let args = Args {
+        asset_dir: Some(asset_dir.to_string()),
+        max_image_size: DEFAULT_MAX_IMAGE_BYTES,
+        changed_since: None,
+        deny: DenyLevel::Errors,
+        report: None,
+        interactive: false,
+        tokens: TokenArgs {
+            github_token: None,
+            gitlab_token: None,
+        },
+        check: None,
+    };
+    run(args, config)
+}
+
+fn run_checks(args: Args, config: GeneratorConfig, report: &mut Report) -> Result<()> {
+    let Args {
+        asset_dir,
+        max_image_size,
+        changed_since,
+        tokens,
+        check,
+        ..
+    } = args;
+    let asset_dir = asset_dir.unwrap_or_else(|| config.paths.asset_dir.clone());
+
+    validate_schema(&asset_dir, report)?;
+    match check {
+        Some(Check::Schema) => return Ok(()),
+        Some(Check::Links) => return validate_links(&asset_dir, &config, report),
+        Some(Check::OrphanImages { delete }) => return clean_orphan_images(&asset_dir, delete),
+        None => {}
+    }
+
+    let changed_files = changed_since
+        .map(|git_ref| changed_asset_files(Path::new(&asset_dir), &git_ref))
+        .transpose()?;
+    let is_changed = |path: &Path| {
+        changed_files
+            .as_ref()
+            .is_none_or(|files| path.canonicalize().is_ok_and(|path| files.contains(&path)))
+    };
+
+    // Assets are validated as written in their files, without metadata from other sources
+    let asset_root_section = parse_assets(
+        &asset_dir,
+        &AssetFilter::default(),
+        MetadataSource::default(),
+    )
+    .with_context(|| "Parsing assets")?;
+    if let Some(changed_files) = &changed_files {
+        fetch_changed_metadata(&asset_dir, changed_files, &config, &tokens)
+            .with_context(|| "Fetching metadata")?;
+    }
+
+    let asset_names = asset_root_section
+        .assets()
+        .into_iter()
+        .map(|asset| asset.name.clone())
+        .collect();
+    let rules = Rules {
+        max_image_bytes: max_image_size,
+        required_fields: vec![],
+        asset_names: &asset_names,
+    };
+    let mut reports = asset_root_section.validate(&rules);
+    reports.retain(|report| is_changed(&report.path));
+    let assets_by_path: HashMap<&Path, &Asset> = asset_root_section
+        .assets()
+        .into_iter()
+        .filter_map(|asset| Some((asset.original_path.as_deref()?, asset)))
+        .collect();
+
+    let duplicates_path = Path::new(&asset_dir).join(DUPLICATES_FILE);
+    let duplicates_config = if duplicates_path.exists() {
+        parse_duplicates_file(&duplicates_path)?
+    } else {
+        DuplicatesConfig::default()
+    };
+    let mut duplicates = find_duplicates(
+        &asset_root_section,
+        Path::new(&asset_dir),
+        &duplicates_config,
+    );
+    duplicates.retain(|duplicate| {
+        duplicate
+            .paths()
+            .any(|path| is_changed(&Path::new(&asset_dir).join(path)))
+    });
+
+    for asset_report in &reports {
+        let _span = tracing::info_span!("asset", asset = %asset_report.asset_name).entered();
+        let asset = assets_by_path.get(asset_report.path.as_path());
+        for error in &asset_report.errors {
+            report.push_fixable(
+                &asset_report.path,
+                Severity::Error,
+                error.rule(),
+                error,
+                asset.and_then(|asset| error.fix(asset)),
+            );
+        }
+        for warning in &asset_report.warnings {
+            report.push(
+                &asset_report.path,
+                warning.severity(),
+                warning.rule(),
+                warning,
+            );
+        }
+    }
+    for duplicate in &duplicates {
+        for path in duplicate.paths() {
+            report.push(
+                &Path::new(&asset_dir).join(path),
+                Severity::Error,
+                duplicate.rule(),
+                duplicate,
+            );
+        }
+    }
+
+    // Unused images can't be attributed to a changed file
+    if changed_files.is_none() {
+        let orphan_images =
+            find_orphan_images(Path::new(&asset_dir), &asset_root_section.assets())?;
+        for image in &orphan_images {
+            let message = format!("Image {} is not used by any asset.", image.display());
+            report.push(image, Severity::Warning, "orphan-image", message);
+        }
+    }
+
+    // Checked even if the file didn't change, since removing or renaming an asset can break it
+    let featured_path = Path::new(&asset_dir).join(FEATURED_FILE);
+    if featured_path.exists() {
+        let featured = parse_featured_file(&featured_path)?;
+        for message in check_featured(&featured, &asset_names) {
+            report.push(&featured_path, Severity::Error, "featured", message);
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the asset files changed since `git_ref`, including uncommitted and untracked
+/// files, as canonical paths.
+fn changed_asset_files(asset_dir: &Path, git_ref: &str) -> Result<HashSet<PathBuf>> {
+    let git = |args: &[&str]| git::git(asset_dir, args);
+
+    let root = git::repository_root(asset_dir)?;
+    let changed = git(&[
+        "diff",
+        "--name-only",
+        "--diff-filter=ACMR",
+        "--merge-base",
+        git_ref,
+    ])?;
+    let untracked = git(&["ls-files", "--others", "--exclude-standard", "--full-name"])?;
+
+    Ok(changed
+        .lines()
+        .chain(untracked.lines())
+        .filter(|path| AssetFormat::from_path(Path::new(path)).is_some())
+        .filter_map(|path| root.join(path).canonicalize().ok())
+        .collect())
+}
+
+/// Fetches the metadata of the changed assets, printing the assets it can't be found for.
+fn fetch_changed_metadata(
+    asset_dir: &str,
+    changed_files: &HashSet<PathBuf>,
+    config: &GeneratorConfig,
+    tokens: &TokenArgs,
+) -> Result<()> {
+    let http = HttpContext::new(&config.http)?;
+    let github_client = tokens.github_client(config, &http)?;
+    let gitlab_client = tokens.gitlab_client(config, &http)?;
+
+    parse_assets(
+        asset_dir,
+        &AssetFilter::default(),
+        MetadataSource {
+            github_client: github_client.as_ref().map(|client| client as _),
+            gitlab_client: gitlab_client.as_ref().map(|client| client as _),
+            concurrency: config.concurrency,
+            only_files: Some(changed_files),
+            ..Default::default()
+        },
+    )?;
+
+    Ok(())
+}
+
+fn validate_schema(asset_dir: &str, report: &mut Report) -> Result<()> {
+    let errors = validate_asset_files(Path::new(asset_dir)).with_context(|| "Reading assets")?;
+
+    for error in &errors {
+        let message = match &error.hint {
+            Some(hint) => format!("{} ({})", error.message, hint),
+            None => error.message.clone(),
+        };
+        log_problem(&error.path, Severity::Error, "schema", error);
+        report.diagnostics.push(Diagnostic {
+            file: error.path.clone(),
+            severity: Severity::Error,
+            rule: "schema",
+            message,
+            span: error
+                .line
+                .zip(error.column)
+                .map(|(line, column)| Span { line, column }),
+            fix: None,
+        });
+    }
+
+    if errors.is_empty() {
+        return Ok(());
+    }
+
+    Err(anyhow!(
+        "{} asset file(s) don't match the schema.",
+        errors.len()
+    ))
+}
+
+fn validate_links(asset_dir: &str, config: &GeneratorConfig, report: &mut Report) -> Result<()> {
+    let assets = read_asset_files(Path::new(asset_dir)).with_context(|| "Reading assets")?;
+    let http = HttpContext::new(&config.http)?;
+
+    let diagnostics = Mutex::new(Report::default());
+    for_each_parallel(assets, config.concurrency.jobs, |asset| {
+        let _span = tracing::info_span!("asset", asset = %asset.name, link = %asset.link).entered();
+        let path = asset.original_path.clone().unwrap_or_default();
+        match check_link(http.agent(), &asset.link) {
+            Ok(LinkStatus::Ok) => {}
+            Ok(LinkStatus::Moved(new_link)) => {
+                let message = format!("Link redirects to {}, use it instead.", new_link);
+                diagnostics.lock().unwrap().push_fixable(
+                    &path,
+                    Severity::Warning,
+                    "link-redirect",
+                    message,
+                    Some(Fix::Set {
+                        key: "link",
+                        value: new_link,
+                    }),
+                );
+            }
+            Err(err) => {
+                diagnostics
+                    .lock()
+                    .unwrap()
+                    .push(&path, Severity::Error, "link-unreachable", err);
+            }
+        }
+    });
+    let diagnostics = diagnostics.into_inner().unwrap().diagnostics;
+    let unreachable = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity == Severity::Error)
+        .count();
+    report.diagnostics.extend(diagnostics);
+
+    if unreachable == 0 {
+        return Ok(());
+    }
+    Err(anyhow!("{} link(s) are unreachable.", unreachable))
+}
+
+fn clean_orphan_images(asset_dir: &str, delete: bool) -> Result<()> {
+    let assets = read_asset_files(Path::new(asset_dir)).with_context(|| "Reading assets")?;
+    let orphan_images =
+        find_orphan_images(Path::new(asset_dir), &assets.iter().collect::<Vec<_>>())?;
+
+    for image in &orphan_images {
+        if delete {
+            fs::remove_file(image).with_context(|| format!("Deleting {}", image.display()))?;
+            tracing::info!("Deleted {}", image.display());
+        } else {
+            println!("{}", image.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds image files in `asset_dir` that aren't the image or the icon of any asset.
+fn find_orphan_images(asset_dir: &Path, assets: &[&Asset]) -> Result<Vec<PathBuf>> {
+    let used: HashSet<PathBuf> = assets
+        .iter()
+        .flat_map(|&asset| {
+            let icon = match asset.icon.as_deref().map(Icon::new) {
+                Some(Icon::Image(icon)) => Some(icon),
+                _ => None,
+            };
+            asset
+                .image
+                .clone()
+                .into_iter()
+                .chain(icon)
+                .filter_map(move |image| {
+                    let path = asset.original_path.as_ref()?.with_file_name(image);
+                    path.canonicalize().ok()
+                })
+        })
+        .collect();
+
+    let mut orphans = vec![];
+    visit_images(asset_dir, &mut |path| {
+        if !used.contains(&path.canonicalize()?) {
+            orphans.push(path.to_path_buf());
+        }
+        Ok(())
+    })?;
+    orphans.sort();
+
+    Ok(orphans)
+}
+
+fn visit_images(dir: &Path, f: &mut impl FnMut(&Path) -> Result<()>) -> Result<()> {
+    AssetWalker::new(dir)?.walk_files(dir, &mut |path| {
+        let is_image = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+        if is_image {
+            f(path)?;
+        }
+        Ok(())
+    })
+}
+
+/// Results of the validation, written as JSON with `--report`.
+#[derive(Serialize, Default)]
+struct Report {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    /// Fails if any problem has at least the `threshold` severity.
+    fn check(&self, threshold: Severity) -> Result<()> {
+        let count = |severity| {
+            self.diagnostics
+                .iter()
+                .filter(|diagnostic| diagnostic.severity == severity)
+                .count()
+        };
+        if self
+            .diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.severity < threshold)
+        {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Validation failed with {} error(s), {} warning(s) and {} info(s).",
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info)
+        ))
+    }
+
+    /// Adds a problem to the report, and logs it.
+    fn push(&mut self, file: &Path, severity: Severity, rule: &'static str, message: impl Display) {
+        self.push_fixable(file, severity, rule, message, None);
+    }
+
+    /// Adds a problem to the report with the fix offered by `--interactive`, and logs it.
+    fn push_fixable(
+        &mut self,
+        file: &Path,
+        severity: Severity,
+        rule: &'static str,
+        message: impl Display,
+        fix: Option<Fix>,
+    ) {
+        log_problem(file, severity, rule, &message);
+        // Only TOML files can be edited keeping their formatting
+        let fix = fix.filter(|_| AssetFormat::from_path(file) == Some(AssetFormat::Toml));
+        self.diagnostics.push(Diagnostic {
+            file: file.to_path_buf(),
+            severity,
+            rule,
+            message: message.to_string(),
+            span: None,
+            fix,
+        });
+    }
+
+    /// Offers to apply the fix of each problem having one, and removes the problems fixed
+    /// from the report.
+    fn fix_interactively(&mut self) -> Result<()> {
+        let fixable = self
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.fix.is_some())
+            .count();
+        if fixable == 0 {
+            return Ok(());
+        }
+        println!("\n{fixable} problem(s) can be fixed.");
+
+        let mut fixed = vec![false; self.diagnostics.len()];
+        for (diagnostic, fixed) in self.diagnostics.iter().zip(&mut fixed) {
+            let Some(fix) = &diagnostic.fix else {
+                continue;
+            };
+            println!("\n{}: {}", diagnostic.file.display(), diagnostic.message);
+            if confirm(&format!("Fix it, {fix}?"))? {
+                *fixed = apply_fix(&diagnostic.file, fix)?;
+            }
+        }
+
+        let count = fixed.iter().filter(|&&fixed| fixed).count();
+        let mut fixed = fixed.into_iter();
+        self.diagnostics
+            .retain(|_| !fixed.next().unwrap_or_default());
+        println!("\nFixed {count} of {fixable} problem(s), validate again to check the files.");
+        Ok(())
+    }
+}
+
+/// Logs a problem with the file and the rule it is about, in the span of the asset if any.
+fn log_problem(file: &Path, severity: Severity, rule: &'static str, message: &impl Display) {
+    let file = file.display();
+    match severity {
+        Severity::Error => tracing::error!(%file, rule, "{message}"),
+        Severity::Warning => tracing::warn!(%file, rule, "{message}"),
+        Severity::Info => tracing::info!(%file, rule, "{message}"),
+    }
+}
+
+#[derive(Serialize)]
+struct Diagnostic {
+    file: PathBuf,
+    severity: Severity,
+    /// Identifier of the check that failed, like `description-too-long`.
+    rule: &'static str,
+    message: String,
+    /// Location of the problem in the file, when known.
+    span: Option<Span>,
+    /// Change to the file fixing the problem, offered by `--interactive`.
+    #[serde(skip)]
+    fix: Option<Fix>,
+}
+
+/// 1-based position in a file.
+#[derive(Serialize)]
+struct Span {
+    line: usize,
+    column: usize,
+}
+
+/// Parses a size like `500KB`, `2MiB` or `1024` into a number of bytes.
+fn parse_size(size: &str) -> Result<u64> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "kib" => 1_024,
+        "m" | "mb" => 1_000_000,
+        "mib" => 1_048_576,
+        unit => return Err(anyhow!("Unknown size unit {unit:?}")),
+    };
+    let number: u64 = number
+        .parse()
+        .with_context(|| format!("Invalid size {size:?}"))?;
+
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| anyhow!("Size {size:?} is too large"))
+}
@@ -0,0 +1,256 @@
+//! Social preview images of assets, shown when their page is linked on Discord or Twitter.
+
+use std::path::Path;
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::{imageops, DynamicImage, Rgba, RgbaImage};
+
+use crate::{
+    bevy_releases::{newest_known_version, BevyVersion},
+    index::IndexedAsset,
+};
+
+/// Suffix of the file name of cards, written next to the page of their asset.
+pub const CARD_SUFFIX: &str = ".card.png";
+
+/// Size of cards, as recommended for Open Graph images.
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 630;
+const MARGIN: u32 = 60;
+/// Size of the box the screenshot of the asset is fitted in, on the right of the card.
+const THUMBNAIL_SIZE: u32 = 500;
+
+const BACKGROUND: Rgba<u8> = Rgba([35, 35, 38, 255]);
+const TEXT: Rgba<u8> = Rgba([236, 236, 236, 255]);
+const MUTED_TEXT: Rgba<u8> = Rgba([160, 160, 166, 255]);
+const BADGE_LABEL: Rgba<u8> = Rgba([85, 85, 85, 255]);
+const BADGE_MESSAGE: Rgba<u8> = Rgba([68, 204, 17, 255]);
+
+const FONT: &[u8] = include_bytes!("../../static/assets/fonts/fira-sans-v10-latin-500.ttf");
+
+/// The social preview of an asset: its name, its sections, the newest Bevy version it
+/// supports, and its screenshot.
+pub struct Card<'a> {
+    pub name: &'a str,
+    /// Names of the sections containing the asset, from the top-level one.
+    pub sections: &'a [String],
+    pub bevy_version: Option<BevyVersion>,
+    pub thumbnail: Option<DynamicImage>,
+}
+
+impl<'a> Card<'a> {
+    /// The card of `asset`, reading its image from the content folder `content_dir`.
+    ///
+    /// Assets whose image can't be read get a card without screenshot.
+    pub fn new(asset: &'a IndexedAsset, content_dir: &Path) -> Self {
+        Card {
+            name: &asset.name,
+            sections: &asset.sections,
+            bevy_version: newest_known_version(&asset.bevy_versions),
+            thumbnail: asset
+                .image
+                .as_ref()
+                .and_then(|image| image::open(content_dir.join(image)).ok()),
+        }
+    }
+
+    /// Renders the card as an image of 1200×630 pixels.
+    pub fn render(&self) -> RgbaImage {
+        let font = FontRef::try_from_slice(FONT).expect("The card font is a valid font");
+        let mut card = RgbaImage::from_pixel(WIDTH, HEIGHT, BACKGROUND);
+
+        let mut text_width = WIDTH - 2 * MARGIN;
+        if let Some(thumbnail) = &self.thumbnail {
+            let thumbnail = thumbnail.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+            let x = WIDTH - MARGIN - THUMBNAIL_SIZE + (THUMBNAIL_SIZE - thumbnail.width()) / 2;
+            let y = (HEIGHT - thumbnail.height()) / 2;
+            imageops::overlay(&mut card, &thumbnail.to_rgba8(), x.into(), y.into());
+            text_width -= THUMBNAIL_SIZE + MARGIN;
+        }
+        let text_width = text_width as f32;
+
+        let mut y = MARGIN as f32;
+        if !self.sections.is_empty() {
+            let sections = self.sections.join(" / ");
+            let line = truncate(&font, 32.0, &sections, text_width);
+            draw_text(&mut card, &font, 32.0, &line, MARGIN as f32, y, MUTED_TEXT);
+            y += 64.0;
+        }
+        for line in wrap(&font, 72.0, self.name, text_width, 4) {
+            draw_text(&mut card, &font, 72.0, &line, MARGIN as f32, y, TEXT);
+            y += 84.0;
+        }
+
+        if let Some(version) = self.bevy_version {
+            draw_badge(
+                &mut card,
+                &font,
+                "bevy",
+                &format!("{version} compatible"),
+                MARGIN,
+                HEIGHT - MARGIN - 40,
+            );
+        }
+        card
+    }
+}
+
+/// Draws a badge like the ones of [`crate::badges`], 40 pixels high, at `x` and `y`.
+fn draw_badge(card: &mut RgbaImage, font: &FontRef, label: &str, message: &str, x: u32, y: u32) {
+    const SIZE: f32 = 24.0;
+    const PADDING: u32 = 16;
+    let label_width = text_width(font, SIZE, label) as u32 + 2 * PADDING;
+    let message_width = text_width(font, SIZE, message) as u32 + 2 * PADDING;
+
+    fill_rect(card, x, y, label_width, 40, BADGE_LABEL);
+    fill_rect(card, x + label_width, y, message_width, 40, BADGE_MESSAGE);
+    let text_y = y as f32 + 6.0;
+    draw_text(card, font, SIZE, label, (x + PADDING) as f32, text_y, TEXT);
+    draw_text(
+        card,
+        font,
+        SIZE,
+        message,
+        (x + label_width + PADDING) as f32,
+        text_y,
+        TEXT,
+    );
+}
+
+fn fill_rect(card: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for py in y..(y + height).min(card.height()) {
+        for px in x..(x + width).min(card.width()) {
+            card.put_pixel(px, py, color);
+        }
+    }
+}
+
+/// Width of `text` at the font size `size`, in pixels.
+fn text_width(font: &FontRef, size: f32, text: &str) -> f32 {
+    let font = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let glyph = font.glyph_id(c);
+        if let Some(previous) = previous {
+            width += font.kern(previous, glyph);
+        }
+        width += font.h_advance(glyph);
+        previous = Some(glyph);
+    }
+    width
+}
+
+/// Draws `text` with its top left corner at `x` and `y`.
+fn draw_text(
+    card: &mut RgbaImage,
+    font: &FontRef,
+    size: f32,
+    text: &str,
+    x: f32,
+    y: f32,
+    color: Rgba<u8>,
+) {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut caret = x;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        let glyph = id.with_scale_and_position(size, point(caret, y + scaled.ascent()));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+
+        let Some(outline) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outline.px_bounds();
+        outline.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i64 + i64::from(gx);
+            let py = bounds.min.y as i64 + i64::from(gy);
+            if px < 0 || py < 0 || px >= card.width().into() || py >= card.height().into() {
+                return;
+            }
+            let pixel = card.get_pixel_mut(px as u32, py as u32);
+            for channel in 0..3 {
+                let blended = f32::from(pixel[channel]) * (1.0 - coverage)
+                    + f32::from(color[channel]) * coverage;
+                pixel[channel] = blended.round() as u8;
+            }
+        });
+    }
+}
+
+/// Splits `text` in lines fitting in `max_width`, breaking between words. Text that doesn't
+/// fit in `max_lines` lines is cut with an ellipsis.
+fn wrap(font: &FontRef, size: f32, text: &str, max_width: f32, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut words = text.split_whitespace().peekable();
+    while let Some(word) = words.next() {
+        let mut line = word.to_string();
+        while let Some(next) = words.peek() {
+            let candidate = format!("{line} {next}");
+            if text_width(font, size, &candidate) > max_width {
+                break;
+            }
+            line = candidate;
+            words.next();
+        }
+
+        if lines.len() + 1 == max_lines && words.peek().is_some() {
+            let rest: Vec<_> = words.collect();
+            line = format!("{line} {}", rest.join(" "));
+            lines.push(truncate(font, size, &line, max_width));
+            break;
+        }
+        lines.push(truncate(font, size, &line, max_width));
+    }
+    lines
+}
+
+/// Cuts `text` with an ellipsis so it fits in `max_width`.
+fn truncate(font: &FontRef, size: f32, text: &str, max_width: f32) -> String {
+    if text_width(font, size, text) <= max_width {
+        return text.to_string();
+    }
+    let mut truncated = text.to_string();
+    while !truncated.is_empty() && text_width(font, size, &format!("{truncated}...")) > max_width {
+        truncated.pop();
+    }
+    format!("{}...", truncated.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_long_names() {
+        let font = FontRef::try_from_slice(FONT).unwrap();
+        assert_eq!(wrap(&font, 72.0, "bevy_foo", 1080.0, 4), ["bevy_foo"]);
+
+        let lines = wrap(&font, 72.0, &"word ".repeat(40), 520.0, 4);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[3].ends_with("..."));
+        assert!(lines
+            .iter()
+            .all(|line| text_width(&font, 72.0, line) <= 520.0));
+    }
+
+    #[test]
+    fn renders_cards_of_the_expected_size() {
+        let sections = vec!["Assets".to_string(), "2D".to_string()];
+        let card = Card {
+            name: "bevy_ecs_tilemap",
+            sections: &sections,
+            bevy_version: Some(BevyVersion::release(0, 13)),
+            thumbnail: Some(DynamicImage::new_rgba8(800, 400)),
+        }
+        .render();
+        assert_eq!(card.dimensions(), (WIDTH, HEIGHT));
+        // The text was drawn over the background
+        assert!(card.pixels().any(|pixel| *pixel == TEXT));
+    }
+}
@@ -0,0 +1,236 @@
+//! Records the responses to the HTTP requests of a run in a file, and replays them instead of
+//! making the requests, so the fetching of metadata can be tested offline and
+//! deterministically with real responses.
+//!
+//! A [`Cassette`] is a [`ureq::Middleware`] added to the agent of the
+//! [`HttpContext`](crate::http::HttpContext) when [`HttpConfig::cassette`] is set. Requests are
+//! matched by method and URL, so they can be replayed in any order, like when fetching in
+//! parallel. Only the responses are written, never the headers of the requests, which hold the
+//! tokens.
+//!
+//! [`HttpConfig::cassette`]: crate::config::HttpConfig::cassette
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+/// Whether a [`Cassette`] records responses or replays them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Makes the requests, and writes their responses to the file.
+    Record,
+    /// Answers with the responses of the file, without making any request.
+    Replay,
+}
+
+/// The file of a [`Cassette`] and what to do with it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CassetteConfig {
+    pub mode: CassetteMode,
+    pub path: PathBuf,
+}
+
+/// A request and the response it got.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Interaction {
+    pub method: String,
+    pub url: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+// Middlewares must fail with a `ureq::Error`
+#[allow(clippy::result_large_err)]
+impl Interaction {
+    /// Builds the response of the interaction.
+    fn response(&self) -> Result<ureq::Response, ureq::Error> {
+        let mut response = format!("HTTP/1.1 {} Recorded\r\n", self.status);
+        for (name, value) in &self.headers {
+            response += &format!("{name}: {value}\r\n");
+        }
+        response += &format!("Content-Length: {}\r\n\r\n{}", self.body.len(), self.body);
+        response.parse()
+    }
+}
+
+/// Records or replays the interactions with the HTTP APIs, see the [module](self) docs.
+#[derive(Debug)]
+pub struct Cassette {
+    config: CassetteConfig,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+#[allow(clippy::result_large_err)]
+impl Cassette {
+    /// Reads the interactions of the file to replay them, or starts an empty cassette to
+    /// record them.
+    pub fn new(config: CassetteConfig) -> anyhow::Result<Self> {
+        let interactions = match config.mode {
+            CassetteMode::Record => vec![],
+            CassetteMode::Replay => read_interactions(&config.path)?,
+        };
+        Ok(Self {
+            config,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    /// The interactions recorded or replayed.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions.lock().unwrap().clone()
+    }
+
+    /// Answers with the response recorded for `request`, or with a `501` error response
+    /// naming the request if there is none.
+    fn replay(&self, request: &ureq::Request) -> Result<ureq::Response, ureq::Error> {
+        let interactions = self.interactions.lock().unwrap();
+        let recorded = interactions.iter().find(|interaction| {
+            interaction.method == request.method() && interaction.url == request.url()
+        });
+        match recorded {
+            Some(interaction) => interaction.response(),
+            None => {
+                tracing::warn!(
+                    "No response recorded in {} for {} {}",
+                    self.config.path.display(),
+                    request.method(),
+                    request.url()
+                );
+                Interaction {
+                    method: request.method().to_string(),
+                    url: request.url().to_string(),
+                    status: 501,
+                    headers: vec![],
+                    body: format!("No response recorded for {}", request.url()),
+                }
+                .response()
+            }
+        }
+    }
+
+    /// Makes `request`, and writes its response to the file before returning it.
+    fn record(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        let method = request.method().to_string();
+        let url = request.url().to_string();
+        let response = match next.handle(request) {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(err) => return Err(err),
+        };
+        let status = response.status();
+        let headers = response
+            .headers_names()
+            .into_iter()
+            // The length is set again when replaying, and the body is already decoded
+            .filter(|name| !matches!(name.as_str(), "content-length" | "transfer-encoding"))
+            .filter_map(|name| {
+                let value = response.header(&name)?.to_string();
+                Some((name, value))
+            })
+            .collect();
+        let body = response.into_string()?;
+
+        let interaction = Interaction {
+            method,
+            url,
+            status,
+            headers,
+            body,
+        };
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.retain(|recorded| {
+            recorded.method != interaction.method || recorded.url != interaction.url
+        });
+        interactions.push(interaction.clone());
+        if let Err(err) = write_interactions(&self.config.path, &interactions) {
+            tracing::warn!(
+                "Failed to record the response to {}: {err:#}",
+                interaction.url
+            );
+        }
+        interaction.response()
+    }
+}
+
+impl ureq::Middleware for Cassette {
+    fn handle(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        match self.config.mode {
+            CassetteMode::Record => self.record(request, next),
+            CassetteMode::Replay => self.replay(&request),
+        }
+    }
+}
+
+fn read_interactions(path: &Path) -> anyhow::Result<Vec<Interaction>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Reading the cassette {}", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Parsing the cassette {}", path.display()))
+}
+
+/// Writes the interactions sorted by URL, so recording again gives a small diff.
+fn write_interactions(path: &Path, interactions: &[Interaction]) -> anyhow::Result<()> {
+    let mut interactions = interactions.to_vec();
+    interactions.sort_by(|a, b| (&a.url, &a.method).cmp(&(&b.url, &b.method)));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&interactions)? + "\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cassette(interactions: Vec<Interaction>) -> Cassette {
+        Cassette {
+            config: CassetteConfig {
+                mode: CassetteMode::Replay,
+                path: PathBuf::from("cassette.json"),
+            },
+            interactions: Mutex::new(interactions),
+        }
+    }
+
+    #[test]
+    fn replays_recorded_responses() {
+        let cassette = cassette(vec![Interaction {
+            method: "GET".to_string(),
+            url: "https://api.github.com/rate_limit".to_string(),
+            status: 403,
+            headers: vec![("x-ratelimit-remaining".to_string(), "0".to_string())],
+            body: "{}".to_string(),
+        }]);
+        let agent = ureq::AgentBuilder::new().middleware(cassette).build();
+
+        match agent.get("https://api.github.com/rate_limit").call() {
+            Err(ureq::Error::Status(403, response)) => {
+                assert_eq!(response.header("x-ratelimit-remaining"), Some("0"));
+                assert_eq!(response.into_string().unwrap(), "{}");
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+        match agent.get("https://api.github.com/other").call() {
+            Err(ureq::Error::Status(501, response)) => assert_eq!(
+                response.into_string().unwrap(),
+                "No response recorded for https://api.github.com/other"
+            ),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! The interfaces of the hosts the metadata of assets is fetched from, so the fetching can
+//! be tested without a network, and other hosts can be added.
+//!
+//! A [`MetadataClient`] gets the metadata of a whole repository, and is what
+//! [`MetadataSource`](crate::MetadataSource) uses for Github and Gitlab links. A
+//! [`RepositoryClient`] only reads the files of repositories, which is enough to find the
+//! metadata in their `Cargo.toml` files like [`GithubClient`] does.
+
+use crate::{
+    error::Result, get_metadata_from_github, get_metadata_from_gitlab, github_client::GithubClient,
+    gitlab_client::GitlabClient, ExtraMetadata,
+};
+
+/// Gets the metadata of the assets of a host, like Github or Gitlab.
+pub trait MetadataClient: Sync {
+    /// Gets the license, Bevy version and platforms of the repository `repository_name` of
+    /// `owner`, the user, organization or group in its link.
+    ///
+    /// Fails with [`GenerateAssetsError::NotFound`](crate::GenerateAssetsError::NotFound)
+    /// for the repository if it was deleted or made private.
+    fn get_metadata(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        bevy_crates: &Option<Vec<String>>,
+    ) -> Result<ExtraMetadata>;
+}
+
+/// Reads the files of repositories, like the API of Github.
+pub trait RepositoryClient: Sync {
+    /// Checks that a repository still exists.
+    ///
+    /// Fails with [`GenerateAssetsError::NotFound`](crate::GenerateAssetsError::NotFound)
+    /// for the repository if it was deleted or made private.
+    fn get_repository(&self, owner: &str, repository_name: &str) -> Result<()>;
+
+    /// Gets the content of the file at `path` in a repository.
+    fn get_content(&self, owner: &str, repository_name: &str, path: &str) -> Result<String>;
+
+    /// Gets the SPDX identifier of the license of a repository, found by the host.
+    fn get_license(&self, owner: &str, repository_name: &str) -> Result<String>;
+
+    /// Lists the paths of the files of a repository, at its default branch.
+    fn list_files(&self, owner: &str, repository_name: &str) -> Result<Vec<String>>;
+
+    /// Lists the paths of the files named `file_name` in a repository.
+    fn search_file(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        file_name: &str,
+    ) -> Result<Vec<String>>;
+}
+
+impl RepositoryClient for GithubClient {
+    fn get_repository(&self, owner: &str, repository_name: &str) -> Result<()> {
+        GithubClient::get_repository(self, owner, repository_name)
+    }
+
+    fn get_content(&self, owner: &str, repository_name: &str, path: &str) -> Result<String> {
+        GithubClient::get_content(self, owner, repository_name, path)
+    }
+
+    fn get_license(&self, owner: &str, repository_name: &str) -> Result<String> {
+        GithubClient::get_license(self, owner, repository_name)
+    }
+
+    fn list_files(&self, owner: &str, repository_name: &str) -> Result<Vec<String>> {
+        GithubClient::list_files(self, owner, repository_name)
+    }
+
+    fn search_file(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        file_name: &str,
+    ) -> Result<Vec<String>> {
+        GithubClient::search_file(self, owner, repository_name, file_name)
+    }
+}
+
+impl MetadataClient for GithubClient {
+    fn get_metadata(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        bevy_crates: &Option<Vec<String>>,
+    ) -> Result<ExtraMetadata> {
+        get_metadata_from_github(self, owner, repository_name, bevy_crates)
+    }
+}
+
+impl MetadataClient for GitlabClient {
+    /// `owner` is the group of the project, with its subgroups like `group/subgroup`.
+    fn get_metadata(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        bevy_crates: &Option<Vec<String>>,
+    ) -> Result<ExtraMetadata> {
+        get_metadata_from_gitlab(self, owner, repository_name, bevy_crates)
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::{Condvar, Mutex};
+
+/// A counting semaphore, used to cap the number of concurrent requests to a host.
+pub struct Semaphore {
+    permits: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Self {
+            // A semaphore without permits would block forever
+            permits: Mutex::new(permits.max(1)),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Waits until a permit is available, and holds it until the guard is dropped.
+    pub fn acquire(&self) -> SemaphoreGuard<'_> {
+        let mut permits = self.permits.lock().unwrap();
+        while *permits == 0 {
+            permits = self.released.wait(permits).unwrap();
+        }
+        *permits -= 1;
+
+        SemaphoreGuard { semaphore: self }
+    }
+}
+
+/// A permit of a [`Semaphore`], released on drop.
+pub struct SemaphoreGuard<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+    fn drop(&mut self) {
+        *self.semaphore.permits.lock().unwrap() += 1;
+        self.semaphore.released.notify_one();
+    }
+}
+
+/// Calls `f` on every item, using up to `jobs` threads.
+pub fn for_each_parallel<T: Send>(items: Vec<T>, jobs: usize, f: impl Fn(T) + Sync) {
+    let queue = Mutex::new(items.into_iter());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                // The lock must be released before calling `f`
+                let item = queue.lock().unwrap().next();
+                match item {
+                    Some(item) => f(item),
+                    None => break,
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn for_each_parallel_visits_every_item() {
+        let sum = AtomicUsize::new(0);
+        for_each_parallel((1..=100).collect(), 8, |i| {
+            sum.fetch_add(i, Ordering::Relaxed);
+        });
+        assert_eq!(sum.into_inner(), 5050);
+    }
+
+    #[test]
+    fn semaphore_caps_concurrency() {
+        let semaphore = Semaphore::new(2);
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        for_each_parallel((0..32).collect(), 8, |_: usize| {
+            let _permit = semaphore.acquire();
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        });
+
+        assert!(max_in_flight.into_inner() <= 2);
+    }
+}
@@ -0,0 +1,559 @@
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use std::{
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use crate::cassette::{CassetteConfig, CassetteMode};
+use crate::encoding::read_text_file;
+
+/// Configuration file read from the current directory, if no other file is given.
+pub const CONFIG_FILE: &str = "generate_assets.toml";
+
+const DEFAULT_USER_AGENT: &str = "bevy-website-generate-assets";
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_IDLE_CONNECTIONS_PER_HOST: usize = 8;
+const DEFAULT_JOBS: usize = 8;
+const DEFAULT_GITHUB_JOBS: usize = 4;
+const DEFAULT_GITLAB_JOBS: usize = 4;
+const DEFAULT_ASSET_DIR: &str = "assets";
+const DEFAULT_CONTENT_DIR: &str = "../content";
+const DEFAULT_CACHE_DIR: &str = "data";
+
+/// Configuration of the generator, shared by every part of the run.
+#[derive(Debug, Clone, Default)]
+pub struct GeneratorConfig {
+    /// Configuration of the HTTP clients used to fetch metadata.
+    pub http: HttpConfig,
+    /// How many requests can be in flight at the same time.
+    pub concurrency: ConcurrencyConfig,
+    /// Folders read and written by the generator.
+    pub paths: PathsConfig,
+    /// Which sources metadata is fetched from.
+    pub clients: ClientsConfig,
+    /// Where the tokens of the Github and Gitlab clients are read from.
+    pub tokens: TokensConfig,
+    /// Lists of assets merged into the assets of the asset folder, see
+    /// [`sources`](crate::sources).
+    pub sources: Vec<SourceConfig>,
+}
+
+/// The content of a configuration file, each table overriding the defaults.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    http: HttpFile,
+    concurrency: ConcurrencyConfig,
+    paths: PathsConfig,
+    clients: ClientsConfig,
+    tokens: TokensConfig,
+    sources: Vec<SourceConfig>,
+}
+
+/// The `[http]` table of a configuration file, with timeouts in seconds.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct HttpFile {
+    connect_timeout: Option<u64>,
+    read_timeout: Option<u64>,
+    user_agent: Option<String>,
+    proxy: Option<String>,
+    max_idle_connections_per_host: Option<usize>,
+    max_rate_limit_wait: Option<u64>,
+}
+
+impl GeneratorConfig {
+    /// Reads the configuration file at `path`, or [`CONFIG_FILE`] if it exists, overridden by
+    /// environment variables like [`GeneratorConfig::from_env`].
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(CONFIG_FILE).exists() => Self::from_file(Path::new(CONFIG_FILE))?,
+            None => Self::default(),
+        };
+        config.override_from_env()?;
+        Ok(config)
+    }
+
+    /// Reads the configuration file at `path`, without looking at environment variables.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = read_text_file(path)?;
+        Self::from_toml(&content).with_context(|| format!("Parsing {}", path.display()))
+    }
+
+    /// Parses the content of a configuration file, using the defaults for anything it doesn't
+    /// set.
+    pub fn from_toml(content: &str) -> anyhow::Result<Self> {
+        let file: ConfigFile = toml::from_str(content)?;
+        let mut http = HttpConfig::default();
+        if let Some(timeout) = file.http.connect_timeout {
+            http.connect_timeout = Duration::from_secs(timeout);
+        }
+        if let Some(timeout) = file.http.read_timeout {
+            http.read_timeout = Duration::from_secs(timeout);
+        }
+        if let Some(user_agent) = file.http.user_agent {
+            http.user_agent = user_agent;
+        }
+        http.proxy = file.http.proxy;
+        if let Some(connections) = file.http.max_idle_connections_per_host {
+            http.max_idle_connections_per_host = connections;
+        }
+        http.max_rate_limit_wait = file.http.max_rate_limit_wait.map(Duration::from_secs);
+        for (index, source) in file.sources.iter().enumerate() {
+            source.check()?;
+            if file.sources[..index]
+                .iter()
+                .any(|other| other.name == source.name)
+            {
+                bail!("Two sources are named {:?}", source.name);
+            }
+        }
+
+        Ok(Self {
+            http,
+            concurrency: file.concurrency,
+            paths: file.paths,
+            clients: file.clients,
+            tokens: file.tokens,
+            sources: file.sources,
+        })
+    }
+
+    /// Builds the configuration from the defaults, overridden by environment variables:
+    /// - `GENERATE_ASSETS_CONNECT_TIMEOUT`: connection timeout, in seconds,
+    /// - `GENERATE_ASSETS_READ_TIMEOUT`: read timeout, in seconds,
+    /// - `GENERATE_ASSETS_USER_AGENT`: user agent sent with every request,
+    /// - `GENERATE_ASSETS_PROXY`: proxy used for every request,
+    /// - `GENERATE_ASSETS_MAX_RATE_LIMIT_WAIT`: maximum time to wait for a rate limit to
+    ///   reset, in seconds,
+    /// - `GENERATE_ASSETS_RECORD` and `GENERATE_ASSETS_REPLAY`: file the responses are
+    ///   recorded to or replayed from, see [`cassette`](crate::cassette).
+    pub fn from_env() -> anyhow::Result<Self> {
+        let mut config = Self::default();
+        config.override_from_env()?;
+        Ok(config)
+    }
+
+    fn override_from_env(&mut self) -> anyhow::Result<()> {
+        if let Some(timeout) = duration_from_env("GENERATE_ASSETS_CONNECT_TIMEOUT")? {
+            self.http.connect_timeout = timeout;
+        }
+        if let Some(timeout) = duration_from_env("GENERATE_ASSETS_READ_TIMEOUT")? {
+            self.http.read_timeout = timeout;
+        }
+        if let Ok(user_agent) = std::env::var("GENERATE_ASSETS_USER_AGENT") {
+            self.http.user_agent = user_agent;
+        }
+        if let Ok(proxy) = std::env::var("GENERATE_ASSETS_PROXY") {
+            self.http.proxy = Some(proxy);
+        }
+        if let Some(wait) = duration_from_env("GENERATE_ASSETS_MAX_RATE_LIMIT_WAIT")? {
+            self.http.max_rate_limit_wait = Some(wait);
+        }
+        let cassettes = [
+            ("GENERATE_ASSETS_RECORD", CassetteMode::Record),
+            ("GENERATE_ASSETS_REPLAY", CassetteMode::Replay),
+        ];
+        for (key, mode) in cassettes {
+            if let Some(path) = std::env::var_os(key) {
+                if self.http.cassette.is_some() {
+                    bail!("GENERATE_ASSETS_RECORD and GENERATE_ASSETS_REPLAY can't both be set");
+                }
+                self.http.cassette = Some(CassetteConfig {
+                    mode,
+                    path: path.into(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of the HTTP clients used to fetch metadata.
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Maximum time to establish a connection.
+    pub connect_timeout: Duration,
+    /// Maximum time to wait for data once connected.
+    pub read_timeout: Duration,
+    /// User agent sent with every request.
+    pub user_agent: String,
+    /// Proxy used for every request, like `http://localhost:8080`.
+    pub proxy: Option<String>,
+    /// Number of keep-alive connections kept open for each host.
+    pub max_idle_connections_per_host: usize,
+    /// Records the responses to a file, or replays them from it, see
+    /// [`cassette`](crate::cassette).
+    pub cassette: Option<CassetteConfig>,
+    /// Maximum time to wait for the rate limit of Github or Gitlab to reset before retrying
+    /// a request, in total for that request. Rate limited requests fail right away if `None`.
+    pub max_rate_limit_wait: Option<Duration>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            proxy: None,
+            max_idle_connections_per_host: DEFAULT_MAX_IDLE_CONNECTIONS_PER_HOST,
+            cassette: None,
+            max_rate_limit_wait: None,
+        }
+    }
+}
+
+/// Reads a duration, in seconds, from an environment variable.
+fn duration_from_env(key: &str) -> anyhow::Result<Option<Duration>> {
+    match std::env::var(key) {
+        Ok(value) => {
+            let seconds = value
+                .parse::<u64>()
+                .with_context(|| format!("{key} must be a number of seconds, got {value:?}"))?;
+            Ok(Some(Duration::from_secs(seconds)))
+        }
+        Err(_) => Ok(None),
+    }
+}
+
+/// Limits on the number of assets whose metadata is fetched at the same time.
+///
+/// Lookups in the crates.io database dump are local and always done one at a time.
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConcurrencyConfig {
+    /// Total number of assets fetched in parallel.
+    pub jobs: usize,
+    /// Maximum number of assets fetched in parallel from Github.
+    pub github_jobs: usize,
+    /// Maximum number of assets fetched in parallel from Gitlab.
+    pub gitlab_jobs: usize,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            jobs: DEFAULT_JOBS,
+            github_jobs: DEFAULT_GITHUB_JOBS,
+            gitlab_jobs: DEFAULT_GITLAB_JOBS,
+        }
+    }
+}
+
+/// Folders read and written by the generator, relative to the current directory.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PathsConfig {
+    /// The bevy-assets repository.
+    pub asset_dir: String,
+    /// The content folder of the website.
+    pub content_dir: String,
+    /// Where the crates.io database dump is downloaded to.
+    pub cache_dir: PathBuf,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            asset_dir: DEFAULT_ASSET_DIR.to_string(),
+            content_dir: DEFAULT_CONTENT_DIR.to_string(),
+            cache_dir: PathBuf::from(DEFAULT_CACHE_DIR),
+        }
+    }
+}
+
+/// Which sources metadata is fetched from, all of them by default.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientsConfig {
+    pub crates_io: bool,
+    pub github: bool,
+    pub gitlab: bool,
+}
+
+impl Default for ClientsConfig {
+    fn default() -> Self {
+        Self {
+            crates_io: true,
+            github: true,
+            gitlab: true,
+        }
+    }
+}
+
+/// A list of assets from another place than the asset folder, from a `[[sources]]` table,
+/// like `{ name = "jam", git = "https://github.com/user/jam-assets" }`. See
+/// [`sources`](crate::sources).
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SourceConfig {
+    /// Name of the source, kept by its assets, and of the folder it is fetched to.
+    pub name: String,
+    /// A git repository laid out like the asset folder.
+    pub git: Option<String>,
+    /// The branch, tag or commit of the `git` repository, its default branch if not set.
+    pub rev: Option<String>,
+    /// A file listing assets by section, in TOML, JSON or YAML.
+    pub url: Option<String>,
+}
+
+impl SourceConfig {
+    /// Fails if the source doesn't have either a `git` repository or a `url`, or if its name
+    /// can't be the name of a folder.
+    fn check(&self) -> anyhow::Result<()> {
+        let is_valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !is_valid_name {
+            bail!(
+                "The name of a source can only have letters, digits, `-` and `_`, got {:?}",
+                self.name
+            );
+        }
+        match (&self.git, &self.url) {
+            (Some(_), None) => Ok(()),
+            (None, Some(_)) if self.rev.is_none() => Ok(()),
+            (None, Some(_)) => bail!("The source {} sets `rev` without `git`", self.name),
+            _ => bail!("The source {} must set either `git` or `url`", self.name),
+        }
+    }
+}
+
+/// Where the tokens of the Github and Gitlab clients are read from, `GITHUB_TOKEN` and
+/// `GITLAB_TOKEN` by default.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokensConfig {
+    pub github: TokenSource,
+    pub gitlab: TokenSource,
+}
+
+impl Default for TokensConfig {
+    fn default() -> Self {
+        Self {
+            github: TokenSource::Env("GITHUB_TOKEN".to_string()),
+            gitlab: TokenSource::Env("GITLAB_TOKEN".to_string()),
+        }
+    }
+}
+
+/// Where a token is read from, like `{ env = "GITHUB_TOKEN" }`,
+/// `{ file = "/run/secrets/github_token" }`, `{ keyring = { service = "bevy-assets", user =
+/// "github" } }` or `"gh"`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum TokenSource {
+    /// An environment variable, the token is missing if it isn't set.
+    Env(String),
+    /// A file containing the token, which must exist.
+    File(PathBuf),
+    /// An entry of the system keyring, read with `secret-tool` on Linux and `security` on
+    /// macOS.
+    Keyring { service: String, user: String },
+    /// The token of the Github CLI, printed by `gh auth token`.
+    Gh,
+    /// A command printing the token, like `["pass", "show", "github"]`.
+    Command(Vec<String>),
+}
+
+impl TokenSource {
+    /// Reads the token, which is only allowed to be missing from an environment variable.
+    pub fn read(&self) -> anyhow::Result<Option<String>> {
+        let token = match self {
+            TokenSource::Env(key) => return Ok(std::env::var(key).ok()),
+            TokenSource::File(path) => fs::read_to_string(path)
+                .with_context(|| format!("Reading token from {}", path.display()))?,
+            TokenSource::Keyring { service, user } => read_keyring(service, user)?,
+            TokenSource::Gh => run_token_command("gh", &["auth", "token"])
+                .context("Run `gh auth login` to log in to the Github CLI")?,
+            TokenSource::Command(command) => {
+                let (program, args) = command
+                    .split_first()
+                    .context("The token command is empty")?;
+                run_token_command(program, args)?
+            }
+        };
+        let token = token.trim();
+        if token.is_empty() {
+            bail!("The token read from {self} is empty");
+        }
+        Ok(Some(token.to_string()))
+    }
+}
+
+impl std::fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenSource::Env(key) => write!(f, "the environment variable {key}"),
+            TokenSource::File(path) => write!(f, "{}", path.display()),
+            TokenSource::Keyring { service, user } => {
+                write!(f, "the keyring entry of {user} for {service}")
+            }
+            TokenSource::Gh => write!(f, "`gh auth token`"),
+            TokenSource::Command(command) => write!(f, "`{}`", command.join(" ")),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn read_keyring(service: &str, user: &str) -> anyhow::Result<String> {
+    run_token_command(
+        "security",
+        &["find-generic-password", "-s", service, "-a", user, "-w"],
+    )
+    .with_context(|| format!("Reading the keychain entry of {user} for {service}"))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn read_keyring(service: &str, user: &str) -> anyhow::Result<String> {
+    // The attributes used by the `keyring` crate, so tokens stored by other tools are found
+    run_token_command(
+        "secret-tool",
+        &["lookup", "service", service, "username", user],
+    )
+    .with_context(|| format!("Reading the keyring entry of {user} for {service}"))
+}
+
+/// Runs a command printing a token, failing with its error output if it fails.
+fn run_token_command(program: &str, args: &[impl AsRef<OsStr>]) -> anyhow::Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .with_context(|| format!("Running `{program}`, is it installed?"))?;
+    if !output.status.success() {
+        bail!(
+            "`{program}` failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    String::from_utf8(output.stdout).with_context(|| format!("Reading the output of `{program}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_the_defaults_with_the_config_file() {
+        let config = GeneratorConfig::from_toml(
+            r#"
+            [http]
+            read_timeout = 60
+            max_rate_limit_wait = 900
+
+            [concurrency]
+            github_jobs = 1
+
+            [paths]
+            asset_dir = "bevy-assets"
+
+            [clients]
+            gitlab = false
+
+            [tokens]
+            github = { file = "secrets/github_token" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.http.read_timeout, Duration::from_secs(60));
+        assert_eq!(
+            config.http.max_rate_limit_wait,
+            Some(Duration::from_secs(900))
+        );
+        assert_eq!(config.http.connect_timeout, DEFAULT_CONNECT_TIMEOUT);
+        assert_eq!(config.concurrency.github_jobs, 1);
+        assert_eq!(config.concurrency.jobs, DEFAULT_JOBS);
+        assert_eq!(config.paths.asset_dir, "bevy-assets");
+        assert_eq!(config.paths.content_dir, DEFAULT_CONTENT_DIR);
+        assert!(config.clients.github && !config.clients.gitlab);
+        assert_eq!(
+            config.tokens.github,
+            TokenSource::File(PathBuf::from("secrets/github_token"))
+        );
+        assert_eq!(
+            config.tokens.gitlab,
+            TokenSource::Env("GITLAB_TOKEN".to_string())
+        );
+    }
+
+    #[test]
+    fn reads_tokens_from_every_source() {
+        let config = GeneratorConfig::from_toml(
+            r#"
+            [tokens]
+            github = "gh"
+            gitlab = { keyring = { service = "bevy-assets", user = "gitlab" } }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.tokens.github, TokenSource::Gh);
+        assert_eq!(
+            config.tokens.gitlab,
+            TokenSource::Keyring {
+                service: "bevy-assets".to_string(),
+                user: "gitlab".to_string()
+            }
+        );
+
+        let command =
+            |args: &[&str]| TokenSource::Command(args.iter().map(|a| a.to_string()).collect());
+        assert_eq!(
+            command(&["echo", " token "]).read().unwrap(),
+            Some("token".to_string())
+        );
+        let err = command(&["echo"]).read().unwrap_err();
+        assert_eq!(err.to_string(), "The token read from `echo` is empty");
+        assert!(command(&["false"]).read().is_err());
+        assert!(command(&[]).read().is_err());
+    }
+
+    #[test]
+    fn reads_sources() {
+        let config = GeneratorConfig::from_toml(
+            r#"
+            [[sources]]
+            name = "jam"
+            git = "https://github.com/user/jam-assets"
+            rev = "v1"
+
+            [[sources]]
+            name = "mirror"
+            url = "https://example.com/assets.json"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.sources.len(), 2);
+        assert_eq!(config.sources[0].rev.as_deref(), Some("v1"));
+        assert_eq!(
+            config.sources[1].url.as_deref(),
+            Some("https://example.com/assets.json")
+        );
+
+        for sources in [
+            "[[sources]]\nname = \"a\"\n",
+            "[[sources]]\nname = \"a\"\ngit = \"g\"\nurl = \"u\"\n",
+            "[[sources]]\nname = \"a\"\nurl = \"u\"\nrev = \"v1\"\n",
+            "[[sources]]\nname = \"../a\"\ngit = \"g\"\n",
+            "[[sources]]\nname = \"a\"\ngit = \"g\"\n[[sources]]\nname = \"a\"\nurl = \"u\"\n",
+        ] {
+            assert!(GeneratorConfig::from_toml(sources).is_err(), "{}", sources);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(GeneratorConfig::from_toml("[paths]\noutput_dir = \"out\"").is_err());
+        assert!(GeneratorConfig::from_toml("jobs = 4").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Ways to reach the maintainers of an asset, set with its `discord`, `mastodon` and
+//! `contact` fields.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::Asset;
+
+/// The contacts of an asset, as links the website can show.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Contacts {
+    /// Invite link to the Discord server of the asset.
+    pub discord: Option<String>,
+    /// Profile of the maintainer on Mastodon, like `https://mastodon.social/@user`.
+    pub mastodon: Option<String>,
+    /// Any other link, or a `mailto:` link for an email address.
+    pub contact: Option<String>,
+}
+
+impl Contacts {
+    /// The contacts of `asset`, without the invalid ones, which are reported by the
+    /// validation.
+    pub fn new(asset: &Asset) -> Self {
+        Contacts {
+            discord: asset
+                .discord
+                .as_deref()
+                .and_then(|d| discord_invite(d).ok()),
+            mastodon: asset
+                .mastodon
+                .as_deref()
+                .and_then(|m| mastodon_profile(m).ok()),
+            contact: asset.contact.as_deref().and_then(|c| contact_link(c).ok()),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.discord.is_none() && self.mastodon.is_none() && self.contact.is_none()
+    }
+}
+
+/// Why a contact of an asset is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactError {
+    DiscordInvite,
+    MastodonHandle,
+    Contact,
+}
+
+impl Display for ContactError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContactError::DiscordInvite => write!(
+                f,
+                "Discord must be an invite link, like https://discord.gg/bevy."
+            ),
+            ContactError::MastodonHandle => write!(
+                f,
+                "Mastodon must be a handle like @user@mastodon.social, or a link to a profile."
+            ),
+            ContactError::Contact => {
+                write!(f, "Contact must be an email address or an https link.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ContactError {}
+
+/// Checks that `invite` is a Discord invite link, like `https://discord.gg/<code>` or
+/// `https://discord.com/invite/<code>`, and returns it.
+pub fn discord_invite(invite: &str) -> Result<String, ContactError> {
+    let url = https_url(invite).ok_or(ContactError::DiscordInvite)?;
+    let segments: Vec<_> = url.path_segments().into_iter().flatten().collect();
+    let ((Some("discord.gg"), [code])
+    | (Some("discord.com" | "www.discord.com" | "discordapp.com"), ["invite", code])) =
+        (url.host_str(), &segments[..])
+    else {
+        return Err(ContactError::DiscordInvite);
+    };
+    let is_code = (2..=32).contains(&code.len())
+        && code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    if !is_code || url.query().is_some() {
+        return Err(ContactError::DiscordInvite);
+    }
+    Ok(invite.to_string())
+}
+
+/// Reads a Mastodon handle, like `@user@mastodon.social`, or a link to a profile, like
+/// `https://mastodon.social/@user`, and returns the link to the profile.
+pub fn mastodon_profile(profile: &str) -> Result<String, ContactError> {
+    let (user, server) = if let Some(handle) = profile.strip_prefix('@') {
+        handle.split_once('@').ok_or(ContactError::MastodonHandle)?
+    } else {
+        let url = https_url(profile).ok_or(ContactError::MastodonHandle)?;
+        let segments: Vec<_> = url.path_segments().into_iter().flatten().collect();
+        match (url.host_str(), &segments[..]) {
+            (Some(server), [user]) if user.starts_with('@') => {
+                return is_username(&user[1..])
+                    .then(|| format!("https://{server}/{user}"))
+                    .ok_or(ContactError::MastodonHandle);
+            }
+            _ => return Err(ContactError::MastodonHandle),
+        }
+    };
+    let is_server = server.contains('.')
+        && Url::parse(&format!("https://{server}"))
+            .is_ok_and(|url| url.host_str() == Some(server) && url.path() == "/");
+    if !is_username(user) || !is_server {
+        return Err(ContactError::MastodonHandle);
+    }
+    Ok(format!("https://{server}/@{user}"))
+}
+
+/// Reads an email address or an https link, and returns a link to it.
+pub fn contact_link(contact: &str) -> Result<String, ContactError> {
+    if let Some((user, domain)) = contact.split_once('@') {
+        let is_email = !contact.contains(['/', ':', ' '])
+            && !user.is_empty()
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.');
+        if is_email {
+            return Ok(format!("mailto:{contact}"));
+        }
+    }
+    https_url(contact)
+        .map(|_| contact.to_string())
+        .ok_or(ContactError::Contact)
+}
+
+fn https_url(link: &str) -> Option<Url> {
+    Url::parse(link)
+        .ok()
+        .filter(|url| url.scheme() == "https" && url.has_host())
+}
+
+fn is_username(user: &str) -> bool {
+    !user.is_empty()
+        && user
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_discord_invites() {
+        assert!(discord_invite("https://discord.gg/bevy").is_ok());
+        assert!(discord_invite("https://discord.com/invite/Ab-12cd").is_ok());
+        assert_eq!(
+            discord_invite("https://discord.com/channels/691052431525675048"),
+            Err(ContactError::DiscordInvite)
+        );
+        assert_eq!(
+            discord_invite("discord.gg/bevy"),
+            Err(ContactError::DiscordInvite)
+        );
+    }
+
+    #[test]
+    fn links_mastodon_profiles() {
+        assert_eq!(
+            mastodon_profile("@bevy@mastodon.social").as_deref(),
+            Ok("https://mastodon.social/@bevy")
+        );
+        assert_eq!(
+            mastodon_profile("https://mastodon.social/@bevy").as_deref(),
+            Ok("https://mastodon.social/@bevy")
+        );
+        assert_eq!(
+            mastodon_profile("bevy@mastodon.social"),
+            Err(ContactError::MastodonHandle)
+        );
+        assert_eq!(
+            mastodon_profile("@bevy@localhost"),
+            Err(ContactError::MastodonHandle)
+        );
+    }
+
+    #[test]
+    fn links_emails_and_pages() {
+        assert_eq!(
+            contact_link("me@example.com").as_deref(),
+            Ok("mailto:me@example.com")
+        );
+        assert_eq!(
+            contact_link("https://example.com/contact").as_deref(),
+            Ok("https://example.com/contact")
+        );
+        assert_eq!(contact_link("me@localhost"), Err(ContactError::Contact));
+        assert_eq!(contact_link("example.com"), Err(ContactError::Contact));
+    }
+}
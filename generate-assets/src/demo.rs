@@ -0,0 +1,149 @@
+//! Playable or watchable previews of assets, set with their `demo` field.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Where a demo is hosted, which decides how the website shows it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DemoKind {
+    /// A Youtube video, embedded with its player.
+    Youtube,
+    /// A game on itch.io, linked to since embedding it needs its numeric id.
+    Itch,
+    /// A page hosting a WASM build, embedded as is.
+    Web,
+}
+
+/// The demo of an asset, with what the website needs to embed it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Demo {
+    pub kind: DemoKind,
+    /// The link set in the asset file.
+    pub url: String,
+    /// The page to show in an iframe, if the demo can be embedded.
+    pub embed_url: Option<String>,
+}
+
+/// Why the `demo` of an asset can't be shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DemoError {
+    NotALink,
+    /// Embedding pages served over HTTP is blocked by browsers on the website.
+    NotHttps,
+    /// A Youtube link to a channel or a playlist, instead of a video.
+    NotAVideo,
+}
+
+impl Display for DemoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DemoError::NotALink => write!(f, "Demo must be a link."),
+            DemoError::NotHttps => write!(f, "Demo must be an https link."),
+            DemoError::NotAVideo => write!(f, "Demo must link to a YouTube video."),
+        }
+    }
+}
+
+impl std::error::Error for DemoError {}
+
+impl Demo {
+    /// The demo at `link`: a Youtube video, an itch.io game, or any other page, like a
+    /// hosted WASM build.
+    pub fn new(link: &str) -> Result<Self, DemoError> {
+        let url = Url::parse(link).map_err(|_| DemoError::NotALink)?;
+        if url.scheme() != "https" {
+            return Err(DemoError::NotHttps);
+        }
+        let Some(host) = url.host_str() else {
+            return Err(DemoError::NotALink);
+        };
+
+        let (kind, embed_url) = match host.trim_start_matches("www.") {
+            "youtube.com" | "m.youtube.com" | "youtu.be" => {
+                let video = youtube_video(&url).ok_or(DemoError::NotAVideo)?;
+                (
+                    DemoKind::Youtube,
+                    Some(format!("https://www.youtube-nocookie.com/embed/{video}")),
+                )
+            }
+            host if host.ends_with(".itch.io") => (DemoKind::Itch, None),
+            _ => (DemoKind::Web, Some(link.to_string())),
+        };
+        Ok(Demo {
+            kind,
+            url: link.to_string(),
+            embed_url,
+        })
+    }
+}
+
+/// The id of the video a Youtube link points to, like `https://youtu.be/<id>` or
+/// `https://www.youtube.com/watch?v=<id>`.
+fn youtube_video(url: &Url) -> Option<String> {
+    let mut segments = url.path_segments()?.filter(|segment| !segment.is_empty());
+    let video = if url.host_str() == Some("youtu.be") {
+        segments.next()?.to_string()
+    } else {
+        match segments.next()? {
+            "watch" => url
+                .query_pairs()
+                .find(|(key, _)| key == "v")
+                .map(|(_, video)| video.into_owned())?,
+            "embed" | "shorts" | "live" => segments.next()?.to_string(),
+            _ => return None,
+        }
+    };
+    let is_id = !video.is_empty()
+        && video
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    is_id.then_some(video)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embeds_youtube_videos_and_web_builds() {
+        for link in [
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42",
+            "https://youtu.be/dQw4w9WgXcQ",
+            "https://youtube.com/shorts/dQw4w9WgXcQ",
+        ] {
+            let demo = Demo::new(link).unwrap();
+            assert_eq!(demo.kind, DemoKind::Youtube);
+            assert_eq!(
+                demo.embed_url.as_deref(),
+                Some("https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ")
+            );
+        }
+
+        let demo = Demo::new("https://user.itch.io/game").unwrap();
+        assert_eq!(demo.kind, DemoKind::Itch);
+        assert_eq!(demo.embed_url, None);
+
+        let demo = Demo::new("https://user.github.io/game/").unwrap();
+        assert_eq!(demo.kind, DemoKind::Web);
+        assert_eq!(
+            demo.embed_url.as_deref(),
+            Some("https://user.github.io/game/")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_demos() {
+        assert_eq!(Demo::new("game.wasm"), Err(DemoError::NotALink));
+        assert_eq!(
+            Demo::new("http://user.github.io/game/"),
+            Err(DemoError::NotHttps)
+        );
+        assert_eq!(
+            Demo::new("https://www.youtube.com/@bevyengine"),
+            Err(DemoError::NotAVideo)
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Differences between the assets of two runs, to describe what a metadata refresh changed.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    bevy_releases::BevyVersion,
+    index::{AssetIndex, IndexedAsset, INDEX_FORMAT_VERSION},
+};
+
+/// The assets added, removed and changed between two [`AssetIndex`], matched by path.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexDiff<'a> {
+    pub added: Vec<&'a IndexedAsset>,
+    pub removed: Vec<&'a IndexedAsset>,
+    pub changed: Vec<AssetChanges<'a>>,
+}
+
+/// How the metadata of an asset changed between two runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetChanges<'a> {
+    pub asset: &'a IndexedAsset,
+    /// The previous and new supported Bevy versions, if they changed.
+    pub bevy_versions: Option<(&'a [BevyVersion], &'a [BevyVersion])>,
+    /// The previous and new licenses, if they changed.
+    pub licenses: Option<(&'a [String], &'a [String])>,
+}
+
+/// Reads an `assets.json` snapshot written by a previous run.
+pub fn read_index(path: &Path) -> anyhow::Result<AssetIndex> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+    let index: AssetIndex =
+        serde_json::from_str(&content).with_context(|| format!("Parsing {}", path.display()))?;
+    if index.version != INDEX_FORMAT_VERSION {
+        bail!(
+            "{} has version {} of the format, expected {INDEX_FORMAT_VERSION}",
+            path.display(),
+            index.version
+        );
+    }
+    Ok(index)
+}
+
+impl<'a> IndexDiff<'a> {
+    /// The differences from the `previous` run to the `current` one.
+    pub fn new(previous: &'a AssetIndex, current: &'a AssetIndex) -> Self {
+        let previous_assets: BTreeMap<_, _> = previous
+            .assets
+            .iter()
+            .map(|asset| (&asset.path, asset))
+            .collect();
+        let current_assets: BTreeMap<_, _> = current
+            .assets
+            .iter()
+            .map(|asset| (&asset.path, asset))
+            .collect();
+
+        let mut diff = IndexDiff::default();
+        for (path, asset) in &current_assets {
+            let Some(previous) = previous_assets.get(path) else {
+                diff.added.push(asset);
+                continue;
+            };
+            let changes = AssetChanges {
+                asset,
+                bevy_versions: (previous.bevy_versions != asset.bevy_versions)
+                    .then_some((&previous.bevy_versions[..], &asset.bevy_versions[..])),
+                licenses: (previous.licenses != asset.licenses)
+                    .then_some((&previous.licenses[..], &asset.licenses[..])),
+            };
+            if changes.bevy_versions.is_some() || changes.licenses.is_some() {
+                diff.changed.push(changes);
+            }
+        }
+        diff.removed = previous_assets
+            .iter()
+            .filter(|(path, _)| !current_assets.contains_key(*path))
+            .map(|(_, asset)| *asset)
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Renders the differences as Markdown, for the description of a pull request.
+    pub fn markdown(&self) -> String {
+        if self.is_empty() {
+            return "No asset changed.\n".to_string();
+        }
+
+        let mut markdown = String::new();
+        if !self.added.is_empty() {
+            markdown += &format!("## Added assets ({})\n\n", self.added.len());
+            for asset in &self.added {
+                markdown += &format!(
+                    "- [{}]({}) in {}\n",
+                    asset.name,
+                    asset.link,
+                    asset.sections.join(" / ")
+                );
+            }
+            markdown += "\n";
+        }
+        if !self.removed.is_empty() {
+            markdown += &format!("## Removed assets ({})\n\n", self.removed.len());
+            for asset in &self.removed {
+                markdown += &format!(
+                    "- [{}]({}) in {}\n",
+                    asset.name,
+                    asset.link,
+                    asset.sections.join(" / ")
+                );
+            }
+            markdown += "\n";
+        }
+        if !self.changed.is_empty() {
+            markdown += &format!("## Changed assets ({})\n\n", self.changed.len());
+            for changes in &self.changed {
+                markdown += &format!("- [{}]({})\n", changes.asset.name, changes.asset.link);
+                if let Some((previous, current)) = changes.bevy_versions {
+                    markdown += &format!(
+                        "  - Bevy versions: {} → {}\n",
+                        list(previous),
+                        list(current)
+                    );
+                }
+                if let Some((previous, current)) = changes.licenses {
+                    markdown += &format!("  - Licenses: {} → {}\n", list(previous), list(current));
+                }
+            }
+            markdown += "\n";
+        }
+        markdown
+    }
+}
+
+fn list(values: &[impl ToString]) -> String {
+    if values.is_empty() {
+        "none".to_string()
+    } else {
+        values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(path: &str, versions: &[&str], licenses: &[&str]) -> IndexedAsset {
+        IndexedAsset {
+            name: path.to_string(),
+            path: path.to_string(),
+            sections: vec!["Assets".to_string()],
+            link: format!("https://github.com/user/{path}"),
+            licenses: licenses.iter().map(|l| l.to_string()).collect(),
+            bevy_versions: versions.iter().map(|v| v.parse().unwrap()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn index(assets: Vec<IndexedAsset>) -> AssetIndex {
+        AssetIndex {
+            version: INDEX_FORMAT_VERSION,
+            assets,
+        }
+    }
+
+    #[test]
+    fn lists_added_removed_and_changed_assets() {
+        let previous = index(vec![
+            asset("kept", &["0.13"], &["MIT"]),
+            asset("gone", &["0.12"], &["MIT"]),
+            asset("updated", &["0.13"], &["MIT"]),
+        ]);
+        let current = index(vec![
+            asset("kept", &["0.13"], &["MIT"]),
+            asset("new", &["0.14"], &[]),
+            asset("updated", &["0.13", "0.14"], &["MIT", "Apache-2.0"]),
+        ]);
+        let diff = IndexDiff::new(&previous, &current);
+
+        assert_eq!(diff.added, [&current.assets[1]]);
+        assert_eq!(diff.removed, [&previous.assets[1]]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].asset.path, "updated");
+
+        let markdown = diff.markdown();
+        assert!(markdown
+            .contains("## Added assets (1)\n\n- [new](https://github.com/user/new) in Assets\n"));
+        assert!(markdown.contains("  - Bevy versions: 0.13 → 0.13, 0.14\n"));
+        assert!(markdown.contains("  - Licenses: MIT → MIT, Apache-2.0\n"));
+
+        assert_eq!(
+            IndexDiff::new(&current, &current).markdown(),
+            "No asset changed.\n"
+        );
+    }
+}
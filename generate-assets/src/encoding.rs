@@ -0,0 +1,146 @@
+//! Reads the text files written by contributors, like the asset files, which may be saved
+//! with a byte order mark, or in UTF-16 by some editors on Windows.
+//!
+//! UTF-8 files have their byte order mark removed, and UTF-16 files with a byte order mark
+//! are decoded. Other files that aren't valid UTF-8 fail with an [`EncodingError`] telling
+//! where, instead of a generic IO error.
+
+use std::{fmt::Display, fs, path::Path};
+
+use crate::error::{GenerateAssetsError, Result};
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+const UTF32_LE_BOM: &[u8] = &[0xFF, 0xFE, 0x00, 0x00];
+const UTF32_BE_BOM: &[u8] = &[0x00, 0x00, 0xFE, 0xFF];
+
+/// Why the content of a file couldn't be read as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    /// The file isn't valid UTF-8 from this 1-based line and column.
+    InvalidUtf8 { line: usize, column: usize },
+    /// The file starts with the byte order mark of UTF-16, but isn't valid UTF-16.
+    InvalidUtf16,
+    /// The file looks like UTF-16, without a byte order mark to tell its byte order.
+    Utf16WithoutBom,
+    /// The file is in an encoding that isn't supported, like UTF-32.
+    Unsupported(&'static str),
+}
+
+impl Display for EncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncodingError::InvalidUtf8 { line, column } => {
+                write!(f, "invalid UTF-8 at line {line}, column {column}")
+            }
+            EncodingError::InvalidUtf16 => write!(f, "invalid UTF-16"),
+            EncodingError::Utf16WithoutBom => {
+                write!(f, "UTF-16 without a byte order mark, save it as UTF-8")
+            }
+            EncodingError::Unsupported(encoding) => {
+                write!(f, "{encoding} isn't supported, save it as UTF-8")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// Reads the text file at `path`, see the [module](self) docs.
+pub fn read_text_file(path: &Path) -> Result<String> {
+    let bytes = fs::read(path).map_err(|source| GenerateAssetsError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    decode(&bytes).map_err(|source| GenerateAssetsError::Encoding {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Decodes `bytes` as UTF-8, or as UTF-16 if they start with its byte order mark.
+pub fn decode(bytes: &[u8]) -> Result<String, EncodingError> {
+    if bytes.starts_with(UTF32_LE_BOM) || bytes.starts_with(UTF32_BE_BOM) {
+        return Err(EncodingError::Unsupported("UTF-32"));
+    }
+    if let Some(bytes) = bytes.strip_prefix(UTF16_LE_BOM) {
+        return decode_utf16(bytes, u16::from_le_bytes);
+    }
+    if let Some(bytes) = bytes.strip_prefix(UTF16_BE_BOM) {
+        return decode_utf16(bytes, u16::from_be_bytes);
+    }
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    // Text in UTF-16 has a NUL byte in every ASCII character, which is never in a text file
+    if bytes.len() >= 2 && bytes[..2].contains(&0) {
+        return Err(EncodingError::Utf16WithoutBom);
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Ok(text.to_string()),
+        Err(err) => {
+            // Valid up to the error, so the line and column can be counted in characters
+            let valid = std::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default();
+            let line = valid.matches('\n').count() + 1;
+            let column = valid.chars().rev().take_while(|c| *c != '\n').count() + 1;
+            Err(EncodingError::InvalidUtf8 { line, column })
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String, EncodingError> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(EncodingError::InvalidUtf16);
+    }
+    let units = bytes
+        .chunks_exact(2)
+        .map(|unit| from_bytes([unit[0], unit[1]]));
+    char::decode_utf16(units)
+        .collect::<Result<String, _>>()
+        .map_err(|_| EncodingError::InvalidUtf16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2], bom: &[u8]) -> Vec<u8> {
+        let mut bytes = bom.to_vec();
+        bytes.extend(text.encode_utf16().flat_map(to_bytes));
+        bytes
+    }
+
+    #[test]
+    fn decodes_boms_and_utf16() {
+        let text = "name = \"Bévy\"\n";
+        assert_eq!(decode(text.as_bytes()).unwrap(), text);
+        assert_eq!(decode(&[UTF8_BOM, text.as_bytes()].concat()).unwrap(), text);
+        assert_eq!(
+            decode(&utf16(text, u16::to_le_bytes, UTF16_LE_BOM)).unwrap(),
+            text
+        );
+        assert_eq!(
+            decode(&utf16(text, u16::to_be_bytes, UTF16_BE_BOM)).unwrap(),
+            text
+        );
+    }
+
+    #[test]
+    fn reports_where_the_encoding_is_invalid() {
+        assert_eq!(
+            decode(b"name = \"a\"\nlink = \"\xE9\"\n"),
+            Err(EncodingError::InvalidUtf8 { line: 2, column: 9 })
+        );
+        assert_eq!(
+            decode(&utf16("name", u16::to_le_bytes, &[])),
+            Err(EncodingError::Utf16WithoutBom)
+        );
+        assert_eq!(
+            decode(&[UTF16_LE_BOM, &[0x00, 0xD8, 0x61]].concat()),
+            Err(EncodingError::InvalidUtf16)
+        );
+        assert_eq!(
+            decode(&[UTF32_LE_BOM, &[0x61, 0, 0, 0]].concat()),
+            Err(EncodingError::Unsupported("UTF-32"))
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! The kinds of failures of the library, so callers can handle them differently, like
+//! waiting for a rate limit to reset, instead of matching on error messages.
+//!
+//! The clients, the parsing of files and the fetching of metadata fail with a
+//! [`GenerateAssetsError`]. The functions running a whole step, like
+//! [`parse_assets`](crate::parse_assets), fail with an [`anyhow::Error`] adding context to
+//! it, which can be downcast back to a [`GenerateAssetsError`].
+
+use std::{
+    io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{encoding::EncodingError, schema::SchemaError, RepositoryGone};
+
+pub type Result<T, E = GenerateAssetsError> = std::result::Result<T, E>;
+
+/// The underlying error of a [`GenerateAssetsError`].
+pub type Source = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GenerateAssetsError {
+    /// A file or a response couldn't be parsed, like a `Cargo.toml` file.
+    #[error("Failed to parse {what}")]
+    ParseError {
+        what: String,
+        #[source]
+        source: Source,
+    },
+    /// A request failed because of the network or the server, and may work if retried.
+    #[error("Request to {url} failed")]
+    NetworkError {
+        url: String,
+        #[source]
+        source: Source,
+    },
+    /// The API refuses requests until its rate limit resets.
+    #[error("Rate limited by {url}")]
+    RateLimited {
+        url: String,
+        /// When the limit resets, in seconds since the Unix epoch, if the API tells.
+        reset: Option<u64>,
+    },
+    /// A resource doesn't exist, or isn't visible with the token.
+    #[error("{what} not found")]
+    NotFound {
+        what: String,
+        /// The HTTP status returned, `None` if it couldn't be found by a search.
+        status: Option<u16>,
+        /// Whether it is the repository an asset links to, which was deleted or made
+        /// private, as opposed to a file missing from it.
+        repository: bool,
+    },
+    /// An asset or configuration file doesn't match its schema.
+    #[error(transparent)]
+    SchemaError(Box<SchemaError>),
+    /// A file couldn't be read.
+    #[error("Failed to read {}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    /// A file isn't text in a supported encoding.
+    #[error("Failed to decode {}", path.display())]
+    Encoding {
+        path: PathBuf,
+        #[source]
+        source: EncodingError,
+    },
+}
+
+impl GenerateAssetsError {
+    /// Classifies a failed request to `url`: 404 and 410 responses are [`NotFound`], 429
+    /// responses and 403 responses without any request left or telling when to retry are
+    /// [`RateLimited`], and other errors are [`NetworkError`]s.
+    ///
+    /// Both the rate limit headers of Github, like `x-ratelimit-remaining`, and of Gitlab,
+    /// like `ratelimit-remaining`, are read, and `retry-after` for the secondary rate
+    /// limits of Github.
+    ///
+    /// [`NotFound`]: GenerateAssetsError::NotFound
+    /// [`RateLimited`]: GenerateAssetsError::RateLimited
+    /// [`NetworkError`]: GenerateAssetsError::NetworkError
+    pub(crate) fn from_ureq(url: &str, err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(status @ (404 | 410), _) => GenerateAssetsError::NotFound {
+                what: url.to_string(),
+                status: Some(status),
+                repository: false,
+            },
+            ureq::Error::Status(status, response)
+                if status == 429
+                    || (status == 403
+                        && (rate_limit_header(&response, "remaining") == Some("0")
+                            || response.header("retry-after").is_some())) =>
+            {
+                GenerateAssetsError::RateLimited {
+                    url: url.to_string(),
+                    reset: rate_limit_reset(&response),
+                }
+            }
+            err => GenerateAssetsError::NetworkError {
+                url: url.to_string(),
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// A failure to parse `what`.
+    pub(crate) fn parse(what: impl Into<String>, source: impl Into<Source>) -> Self {
+        GenerateAssetsError::ParseError {
+            what: what.into(),
+            source: source.into(),
+        }
+    }
+
+    /// The URL of the request that failed, if that's what this error is about.
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            GenerateAssetsError::NetworkError { url, .. }
+            | GenerateAssetsError::RateLimited { url, .. } => Some(url),
+            _ => None,
+        }
+    }
+
+    /// The repository the asset links to is gone, if that's what this error is about.
+    pub fn repository_gone(&self) -> Option<RepositoryGone> {
+        match self {
+            GenerateAssetsError::NotFound {
+                status,
+                repository: true,
+                ..
+            } => Some(RepositoryGone { status: *status }),
+            _ => None,
+        }
+    }
+}
+
+/// The `ratelimit-{name}` header of `response`, with the `x-` prefix of Github or without it
+/// like Gitlab.
+fn rate_limit_header<'a>(response: &'a ureq::Response, name: &str) -> Option<&'a str> {
+    response
+        .header(&format!("x-ratelimit-{name}"))
+        .or_else(|| response.header(&format!("ratelimit-{name}")))
+}
+
+/// When the rate limit of a response resets, in seconds since the Unix epoch, from its reset
+/// header or from the number of seconds to wait of `retry-after`.
+fn rate_limit_reset(response: &ureq::Response) -> Option<u64> {
+    if let Some(reset) = rate_limit_header(response, "reset").and_then(|reset| reset.parse().ok()) {
+        return Some(reset);
+    }
+    let seconds: u64 = response.header("retry-after")?.parse().ok()?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(now + seconds)
+}
+
+impl From<SchemaError> for GenerateAssetsError {
+    fn from(err: SchemaError) -> Self {
+        GenerateAssetsError::SchemaError(Box::new(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(code: u16, headers: &str) -> ureq::Error {
+        let response = format!("HTTP/1.1 {code} Status\r\n{headers}\r\n");
+        ureq::Error::Status(code, response.parse().unwrap())
+    }
+
+    #[test]
+    fn classifies_failed_requests() {
+        for code in [404, 410] {
+            assert!(matches!(
+                GenerateAssetsError::from_ureq("url", response(code, "")),
+                GenerateAssetsError::NotFound { status: Some(status), repository: false, .. }
+                    if status == code
+            ));
+        }
+        assert!(matches!(
+            GenerateAssetsError::from_ureq("url", response(429, "")),
+            GenerateAssetsError::RateLimited { reset: None, .. }
+        ));
+        assert!(matches!(
+            GenerateAssetsError::from_ureq(
+                "url",
+                response(
+                    403,
+                    "x-ratelimit-remaining: 0\r\nx-ratelimit-reset: 1700000000\r\n"
+                )
+            ),
+            GenerateAssetsError::RateLimited {
+                reset: Some(1_700_000_000),
+                ..
+            }
+        ));
+        assert!(matches!(
+            GenerateAssetsError::from_ureq(
+                "url",
+                response(
+                    403,
+                    "ratelimit-remaining: 0\r\nratelimit-reset: 1700000000\r\n"
+                )
+            ),
+            GenerateAssetsError::RateLimited {
+                reset: Some(1_700_000_000),
+                ..
+            }
+        ));
+        match GenerateAssetsError::from_ureq("url", response(403, "retry-after: 60\r\n")) {
+            GenerateAssetsError::RateLimited {
+                reset: Some(reset), ..
+            } => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+                assert!(reset.abs_diff(now.as_secs() + 60) <= 1);
+            }
+            err => panic!("{:?}", err),
+        }
+        for code in [403, 500, 503] {
+            assert!(matches!(
+                GenerateAssetsError::from_ureq("url", response(code, "")),
+                GenerateAssetsError::NetworkError { .. }
+            ));
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! How errors met while parsing the assets and fetching their metadata are handled, the
+//! summary of the ones collected with [`ErrorMode::KeepGoing`], and the [`FailureReport`] of
+//! a run.
+
+use std::{collections::BTreeMap, fmt::Display, path::PathBuf, sync::Mutex};
+
+use serde::Serialize;
+
+use crate::error::GenerateAssetsError;
+
+/// How errors of [`parse_assets`](crate::parse_assets) are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// Files failing to parse stop the run, metadata that can't be fetched is logged and
+    /// left out.
+    #[default]
+    Lenient,
+    /// Any error stops the run, including metadata that can't be fetched.
+    FailFast,
+    /// Every error is collected in [`Failures`] and the run goes on, leaving out the files
+    /// failing to parse.
+    KeepGoing,
+}
+
+/// What went wrong, to tell the assets whose repository is gone from the ones that failed
+/// because of the network or a rate limit.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    Parse,
+    Schema,
+    Io,
+    Encoding,
+    Network,
+    RateLimited,
+    NotFound,
+    /// The repository of the asset was deleted or made private.
+    RepositoryGone,
+    /// Any error that isn't a [`GenerateAssetsError`].
+    Other,
+}
+
+impl FailureKind {
+    /// The kind of the first [`GenerateAssetsError`] of the chain of `error`.
+    pub fn of(error: &anyhow::Error) -> Self {
+        let Some(error) = error
+            .chain()
+            .find_map(|error| error.downcast_ref::<GenerateAssetsError>())
+        else {
+            return FailureKind::Other;
+        };
+        match error {
+            GenerateAssetsError::ParseError { .. } => FailureKind::Parse,
+            GenerateAssetsError::SchemaError(_) => FailureKind::Schema,
+            GenerateAssetsError::Io { .. } => FailureKind::Io,
+            GenerateAssetsError::Encoding { .. } => FailureKind::Encoding,
+            GenerateAssetsError::NetworkError { .. } => FailureKind::Network,
+            GenerateAssetsError::RateLimited { .. } => FailureKind::RateLimited,
+            GenerateAssetsError::NotFound {
+                repository: true, ..
+            } => FailureKind::RepositoryGone,
+            GenerateAssetsError::NotFound { .. } => FailureKind::NotFound,
+        }
+    }
+}
+
+/// An asset file that failed to parse, or an asset whose metadata couldn't be fetched.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub file: Option<PathBuf>,
+    /// Name of the asset, when its file could be parsed.
+    pub asset: Option<String>,
+    /// Link of the asset, when its file could be parsed.
+    pub link: Option<String>,
+    pub kind: FailureKind,
+    /// The URL of the request that failed, if any.
+    pub url: Option<String>,
+    pub error: String,
+    /// Whether the run goes on and succeeds anyway, like metadata that can't be fetched
+    /// with [`ErrorMode::Lenient`].
+    pub tolerated: bool,
+}
+
+impl Failure {
+    pub fn new(file: Option<PathBuf>, asset: Option<String>, error: &anyhow::Error) -> Self {
+        let url = error
+            .chain()
+            .find_map(|error| error.downcast_ref::<GenerateAssetsError>())
+            .and_then(GenerateAssetsError::url);
+        Failure {
+            file,
+            asset,
+            link: None,
+            kind: FailureKind::of(error),
+            url: url.map(str::to_string),
+            error: format!("{error:#}"),
+            tolerated: false,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.asset, &self.file) {
+            (Some(asset), Some(file)) => write!(f, "{asset} ({}): ", file.display())?,
+            (Some(asset), None) => write!(f, "{asset}: ")?,
+            (None, Some(file)) => write!(f, "{}: ", file.display())?,
+            (None, None) => {}
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+/// The failures collected during a run, shared by the threads fetching metadata.
+#[derive(Debug, Default)]
+pub struct Failures {
+    failures: Mutex<Vec<Failure>>,
+}
+
+impl Failures {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, failure: Failure) {
+        self.failures.lock().unwrap().push(failure);
+    }
+
+    /// The failures, in the order they were collected.
+    pub fn into_vec(self) -> Vec<Failure> {
+        self.failures.into_inner().unwrap()
+    }
+}
+
+/// Every failure of a run, written as JSON at the end of the run for the maintainers to
+/// see which assets fail, and why.
+#[derive(Serialize, Debug)]
+pub struct FailureReport<'a> {
+    pub total: usize,
+    /// How many failures there are of each kind.
+    pub kinds: BTreeMap<FailureKind, usize>,
+    /// The failures sorted by asset and file, so the reports of two runs can be compared.
+    pub failures: Vec<&'a Failure>,
+}
+
+impl<'a> FailureReport<'a> {
+    pub fn new(failures: &'a [Failure]) -> Self {
+        let mut kinds = BTreeMap::new();
+        for failure in failures {
+            *kinds.entry(failure.kind).or_default() += 1;
+        }
+        let mut failures: Vec<_> = failures.iter().collect();
+        failures.sort_by(|failure1, failure2| {
+            (&failure1.asset, &failure1.file).cmp(&(&failure2.asset, &failure2.file))
+        });
+        FailureReport {
+            total: failures.len(),
+            kinds,
+            failures,
+        }
+    }
+}
+
+/// Lists `failures`, one per line after a line counting them.
+pub fn summary(failures: &[Failure]) -> String {
+    let mut summary = format!("{} failures:\n", failures.len());
+    for failure in failures {
+        summary.push_str(&format!("- {failure}\n"));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_every_failure() {
+        let failures = Failures::new();
+        failures.push(Failure::new(
+            Some(PathBuf::from("Assets/2D/broken.toml")),
+            None,
+            &anyhow::anyhow!("missing field `link`"),
+        ));
+        failures.push(Failure::new(
+            Some(PathBuf::from("Assets/2D/tilemap.toml")),
+            Some("Tilemap".to_string()),
+            &anyhow::anyhow!("Not found").context("Getting the Cargo.toml file"),
+        ));
+        assert_eq!(
+            summary(&failures.into_vec()),
+            "2 failures:\n\
+            - Assets/2D/broken.toml: missing field `link`\n\
+            - Tilemap (Assets/2D/tilemap.toml): Getting the Cargo.toml file: Not found\n"
+        );
+    }
+
+    #[test]
+    fn reports_the_kind_and_url_of_failures() {
+        let failures = [
+            Failure::new(
+                None,
+                Some("Tilemap".to_string()),
+                &anyhow::Error::new(GenerateAssetsError::RateLimited {
+                    url: "https://api.github.com/repos/user/tilemap".to_string(),
+                    reset: None,
+                })
+                .context("Getting the Cargo.toml file"),
+            ),
+            Failure::new(
+                None,
+                Some("Gone".to_string()),
+                &GenerateAssetsError::NotFound {
+                    what: "Github repository user/gone".to_string(),
+                    status: Some(404),
+                    repository: true,
+                }
+                .into(),
+            ),
+            Failure::new(None, Some("Other".to_string()), &anyhow::anyhow!("Failed")),
+        ];
+        assert_eq!(failures[0].kind, FailureKind::RateLimited);
+        assert_eq!(
+            failures[0].url.as_deref(),
+            Some("https://api.github.com/repos/user/tilemap")
+        );
+        assert_eq!(failures[1].kind, FailureKind::RepositoryGone);
+        assert_eq!(failures[1].url, None);
+        assert_eq!(failures[2].kind, FailureKind::Other);
+
+        let report = FailureReport::new(&failures);
+        let assets: Vec<_> = report
+            .failures
+            .iter()
+            .map(|failure| failure.asset.as_deref().unwrap())
+            .collect();
+        assert_eq!(assets, ["Gone", "Other", "Tilemap"]);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["kinds"],
+            serde_json::json!({"rate_limited": 1, "repository_gone": 1, "other": 1})
+        );
+    }
+}
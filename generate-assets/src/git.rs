@@ -0,0 +1,327 @@
+//! Information about asset files read from the git history of the bevy-assets repository.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use crate::{encoding::decode, schema::AssetFormat};
+
+/// Runs git in `dir`, returning its output.
+pub fn git(dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    Ok(String::from_utf8(git_bytes(dir, args)?)?)
+}
+
+/// The content of a file at a revision, like `<commit>^:<path>`, decoded like the files of
+/// the asset folder, see [`crate::encoding`].
+fn file_at(dir: &Path, revision: &str) -> Option<String> {
+    decode(&git_bytes(dir, &["show", revision]).ok()?).ok()
+}
+
+fn git_bytes(dir: &Path, args: &[&str]) -> anyhow::Result<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Running git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+/// Root of the git repository containing `dir`.
+pub fn repository_root(dir: &Path) -> anyhow::Result<PathBuf> {
+    Ok(PathBuf::from(
+        git(dir, &["rev-parse", "--show-toplevel"])?.trim(),
+    ))
+}
+
+/// When a file was added to the repository and last changed, as `YYYY-MM-DD` dates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHistory {
+    pub added: String,
+    pub updated: String,
+}
+
+/// Reads the history of every file of the repository containing `dir`, by canonical path.
+///
+/// The history of a renamed file starts at its rename. In a shallow clone, every file
+/// seems to have been added by the oldest fetched commit.
+pub fn read_history(dir: &Path) -> anyhow::Result<HashMap<PathBuf, FileHistory>> {
+    let root = repository_root(dir)?;
+    let log = git(
+        &root,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--format=commit %cs",
+            "--name-only",
+        ],
+    )?;
+
+    Ok(parse_log(&log)
+        .into_iter()
+        .filter_map(|(path, history)| Some((root.join(path).canonicalize().ok()?, history)))
+        .collect())
+}
+
+/// Parses the output of `git log --format="commit %cs" --name-only`, from newest to oldest.
+fn parse_log(log: &str) -> HashMap<String, FileHistory> {
+    let mut history: HashMap<String, FileHistory> = HashMap::new();
+    let mut date = "";
+    for line in log.lines() {
+        if let Some(commit_date) = line.strip_prefix("commit ") {
+            date = commit_date;
+        } else if !line.is_empty() {
+            history
+                .entry(line.to_string())
+                .and_modify(|file| file.added = date.to_string())
+                .or_insert_with(|| FileHistory {
+                    added: date.to_string(),
+                    updated: date.to_string(),
+                });
+        }
+    }
+    history
+}
+
+/// A previous location of a file, before it was renamed or moved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviousLocation {
+    /// Path of the file before the rename.
+    pub path: PathBuf,
+    /// Name of the asset in the file before the rename, when it could be read.
+    pub name: Option<String>,
+}
+
+/// Reads the previous locations of every renamed or moved file of the repository containing
+/// `dir`, by canonical current path, from newest to oldest.
+pub fn read_renames(dir: &Path) -> anyhow::Result<HashMap<PathBuf, Vec<PreviousLocation>>> {
+    let root = repository_root(dir)?;
+    let log = git(
+        &root,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "-M",
+            "--diff-filter=R",
+            "--name-status",
+            "--format=commit %H",
+        ],
+    )?;
+
+    Ok(parse_renames(&log)
+        .into_iter()
+        .filter_map(|(path, renames)| {
+            let previous = renames
+                .into_iter()
+                .map(|rename| PreviousLocation {
+                    name: previous_name(&root, &rename),
+                    path: root.join(rename.from),
+                })
+                .collect();
+            Some((root.join(path).canonicalize().ok()?, previous))
+        })
+        .collect())
+}
+
+/// A rename read from the log, in the commit `commit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rename {
+    commit: String,
+    from: String,
+}
+
+/// The name of an asset, ignoring its other fields.
+#[derive(Deserialize)]
+struct Named {
+    name: String,
+}
+
+/// Name of the asset in the renamed file, read from the parent of the renaming commit.
+fn previous_name(root: &Path, rename: &Rename) -> Option<String> {
+    let content = file_at(root, &format!("{}^:{}", rename.commit, rename.from))?;
+    let path = Path::new(&rename.from);
+    let asset: Named = AssetFormat::from_path(path)?.parse(path, &content).ok()?;
+    Some(asset.name)
+}
+
+/// Parses the output of `git log --format="commit %H" --name-status --diff-filter=R`, from
+/// newest to oldest, following files renamed several times to their current path.
+fn parse_renames(log: &str) -> HashMap<String, Vec<Rename>> {
+    let mut renames: HashMap<String, Vec<Rename>> = HashMap::new();
+    // Path a file had at some point, to its current path
+    let mut current_paths: HashMap<String, String> = HashMap::new();
+    let mut commit = "";
+    for line in log.lines() {
+        if let Some(hash) = line.strip_prefix("commit ") {
+            commit = hash;
+            continue;
+        }
+        let mut fields = line.split('\t');
+        let (Some(status), Some(from), Some(to)) = (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if !status.starts_with('R') {
+            continue;
+        }
+
+        let current = current_paths
+            .get(to)
+            .cloned()
+            .unwrap_or_else(|| to.to_string());
+        current_paths.insert(from.to_string(), current.clone());
+        renames.entry(current).or_default().push(Rename {
+            commit: commit.to_string(),
+            from: from.to_string(),
+        });
+    }
+    renames
+}
+
+/// A file removed from the repository, with its content before the removal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemovedFile {
+    pub path: PathBuf,
+    /// When the file was removed, as a `YYYY-MM-DD` date.
+    pub removed: String,
+    pub content: String,
+}
+
+/// Reads every file removed from the repository containing `dir` that doesn't exist anymore,
+/// from the most recently removed.
+///
+/// Files whose content can't be read, like in a shallow clone, are skipped.
+pub fn read_removed_files(dir: &Path) -> anyhow::Result<Vec<RemovedFile>> {
+    let root = repository_root(dir)?;
+    let log = git(
+        &root,
+        &[
+            "-c",
+            "core.quotePath=false",
+            "log",
+            "--diff-filter=D",
+            "--name-only",
+            "--format=commit %H %cs",
+        ],
+    )?;
+
+    Ok(parse_removals(&log)
+        .into_iter()
+        .filter(|removal| !root.join(&removal.path).exists())
+        .filter_map(|removal| {
+            let content = file_at(&root, &format!("{}^:{}", removal.commit, removal.path))?;
+            Some(RemovedFile {
+                path: root.join(removal.path),
+                removed: removal.date,
+                content,
+            })
+        })
+        .collect())
+}
+
+/// A removal read from the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Removal {
+    commit: String,
+    date: String,
+    path: String,
+}
+
+/// Parses the output of `git log --format="commit %H %cs" --name-only --diff-filter=D`,
+/// from newest to oldest, keeping the last removal of files removed several times.
+fn parse_removals(log: &str) -> Vec<Removal> {
+    let mut removals = Vec::new();
+    let mut seen = HashSet::new();
+    let (mut commit, mut date) = ("", "");
+    for line in log.lines() {
+        if let Some(header) = line.strip_prefix("commit ") {
+            (commit, date) = header.split_once(' ').unwrap_or((header, ""));
+        } else if !line.is_empty() && seen.insert(line) {
+            removals.push(Removal {
+                commit: commit.to_string(),
+                date: date.to_string(),
+                path: line.to_string(),
+            });
+        }
+    }
+    removals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_added_and_updated_dates() {
+        let log = "commit 2024-03-01\n\nGames/a.toml\n\ncommit 2023-05-02\n\nGames/a.toml\nGames/b.toml\n";
+        let history = parse_log(log);
+
+        assert_eq!(
+            history["Games/a.toml"],
+            FileHistory {
+                added: "2023-05-02".to_string(),
+                updated: "2024-03-01".to_string(),
+            }
+        );
+        assert_eq!(history["Games/b.toml"].added, "2023-05-02");
+        assert_eq!(history["Games/b.toml"].updated, "2023-05-02");
+    }
+
+    #[test]
+    fn follows_renames_to_current_path() {
+        let log = "commit bbb\n\nR100\tGames/b.toml\tGames/3D/c.toml\n\ncommit aaa\n\nR090\tGames/a.toml\tGames/b.toml\nR100\tTools/x.toml\tTools/y.toml\n";
+        let renames = parse_renames(log);
+
+        assert_eq!(
+            renames["Games/3D/c.toml"],
+            [
+                Rename {
+                    commit: "bbb".to_string(),
+                    from: "Games/b.toml".to_string(),
+                },
+                Rename {
+                    commit: "aaa".to_string(),
+                    from: "Games/a.toml".to_string(),
+                },
+            ]
+        );
+        assert_eq!(renames["Tools/y.toml"].len(), 1);
+        assert_eq!(renames.len(), 2);
+    }
+
+    #[test]
+    fn keeps_last_removal() {
+        let log = "commit bbb 2024-03-01\n\nGames/a.toml\n\ncommit aaa 2023-05-02\n\nGames/a.toml\nGames/b.toml\n";
+        let removals = parse_removals(log);
+
+        assert_eq!(
+            removals,
+            [
+                Removal {
+                    commit: "bbb".to_string(),
+                    date: "2024-03-01".to_string(),
+                    path: "Games/a.toml".to_string(),
+                },
+                Removal {
+                    commit: "aaa".to_string(),
+                    date: "2023-05-02".to_string(),
+                    path: "Games/b.toml".to_string(),
+                },
+            ]
+        );
+    }
+}
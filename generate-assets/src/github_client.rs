@@ -1,5 +1,11 @@
-use anyhow::bail;
-use serde::Deserialize;
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    error::{GenerateAssetsError, Result},
+    http::{decode_content, retry_rate_limited, HttpContext},
+};
 
 const BASE_URL: &str = "https://api.github.com";
 
@@ -31,18 +37,94 @@ struct GithubSearchFileItem {
     path: std::path::PathBuf,
 }
 
+#[derive(Deserialize)]
+struct GithubTreeResponse {
+    tree: Vec<GithubTreeItem>,
+    truncated: bool,
+}
+
+#[derive(Deserialize)]
+struct GithubTreeItem {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRateLimitResponse {
+    rate: RateLimit,
+}
+
+/// The rate limit of the core API of Github, for the token of a [`GithubClient`].
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests allowed per hour.
+    pub limit: u32,
+    pub remaining: u32,
+    /// When the limit resets, in seconds since the Unix epoch.
+    pub reset: u64,
+}
+
 pub struct GithubClient {
     agent: ureq::Agent,
     token: String,
+    max_rate_limit_wait: Option<Duration>,
 }
 
 impl GithubClient {
-    pub fn new(token: String) -> Self {
-        let agent: ureq::Agent = ureq::AgentBuilder::new()
-            .user_agent("bevy-website-generate-assets")
-            .build();
+    pub fn new(token: String, http: &HttpContext) -> Self {
+        let agent = http.agent().clone();
+
+        Self {
+            agent,
+            token,
+            max_rate_limit_wait: http.max_rate_limit_wait(),
+        }
+    }
 
-        Self { agent, token }
+    /// Gets `path` from the API and deserializes the JSON response, waiting for the rate
+    /// limit to reset if [`HttpConfig::max_rate_limit_wait`] allows it.
+    ///
+    /// [`HttpConfig::max_rate_limit_wait`]: crate::config::HttpConfig::max_rate_limit_wait
+    fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{BASE_URL}{path}");
+        let response = retry_rate_limited(self.max_rate_limit_wait, || {
+            self.agent
+                .get(&url)
+                .set("Accept", "application/json")
+                .set("Authorization", &format!("Bearer {}", self.token))
+                .call()
+                .map_err(|err| GenerateAssetsError::from_ureq(&url, err))
+        })?;
+        response
+            .into_json()
+            .map_err(|err| GenerateAssetsError::parse(format!("response of {url}"), err))
+    }
+
+    /// Checks that a github repo still exists
+    ///
+    /// Fails with [`GenerateAssetsError::NotFound`] for the repository if it was deleted or
+    /// made private.
+    pub fn get_repository(&self, username: &str, repository_name: &str) -> Result<()> {
+        match self.get::<serde_json::Value>(&format!("/repos/{username}/{repository_name}")) {
+            Err(GenerateAssetsError::NotFound { status, .. }) => {
+                Err(GenerateAssetsError::NotFound {
+                    what: format!("Github repository {username}/{repository_name}"),
+                    status,
+                    repository: true,
+                })
+            }
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// Gets the rate limit of the token, which fails if Github rejects it.
+    ///
+    /// Checking the rate limit doesn't count against it.
+    pub fn get_rate_limit(&self) -> Result<RateLimit> {
+        let response: GithubRateLimitResponse = self.get("/rate_limit")?;
+
+        Ok(response.rate)
     }
 
     /// Gets the content of a file from a github repo
@@ -51,46 +133,53 @@ impl GithubClient {
         username: &str,
         repository_name: &str,
         content_path: &str,
-    ) -> anyhow::Result<String> {
-        let response: GithubContentResponse = self
-            .agent
-            .get(&format!(
-                "{BASE_URL}/repos/{username}/{repository_name}/contents/{content_path}"
-            ))
-            .set("Accept", "application/json")
-            .set("Authorization", &format!("Bearer {}", self.token))
-            .call()?
-            .into_json()?;
-
-        if response.encoding == "base64" {
-            let data = base64::decode(response.content.replace('\n', "").trim())?;
-            Ok(String::from_utf8(data)?)
-        } else {
-            bail!("Content is not in base64");
-        }
+    ) -> Result<String> {
+        let response: GithubContentResponse = self.get(&format!(
+            "/repos/{username}/{repository_name}/contents/{content_path}"
+        ))?;
+
+        decode_content(&response.encoding, &response.content)
+            .map_err(|err| GenerateAssetsError::parse(content_path, err))
     }
 
     /// Gets the license from a github repo
     /// Technically, github supports multiple licenses, but the API only returns one
-    #[allow(unused)]
-    pub fn get_license(&self, username: &str, repository_name: &str) -> anyhow::Result<String> {
-        let response: GithubLicenseResponse = self
-            .agent
-            .get(&format!(
-                "{BASE_URL}/repos/{username}/{repository_name}/license"
-            ))
-            .set("Accept", "application/json")
-            .set("Authorization", &format!("Bearer {}", self.token))
-            .call()?
-            .into_json()?;
+    pub fn get_license(&self, username: &str, repository_name: &str) -> Result<String> {
+        let response: GithubLicenseResponse =
+            self.get(&format!("/repos/{username}/{repository_name}/license"))?;
 
         let license = response.license.spdx_id;
 
         if license != "NOASSERTION" {
             Ok(license)
         } else {
-            bail!("No spdx license assertion")
+            Err(GenerateAssetsError::NotFound {
+                what: format!("SPDX license of {username}/{repository_name}"),
+                status: None,
+                repository: false,
+            })
+        }
+    }
+
+    /// Lists the paths of the files of a github repo, at its default branch.
+    pub fn list_files(&self, username: &str, repository_name: &str) -> Result<Vec<String>> {
+        let response: GithubTreeResponse = self.get(&format!(
+            "/repos/{username}/{repository_name}/git/trees/HEAD?recursive=1"
+        ))?;
+
+        if response.truncated {
+            tracing::warn!(
+                "Too many files in {username}/{repository_name}, checking only the first {} ones.",
+                response.tree.len(),
+            );
         }
+
+        Ok(response
+            .tree
+            .into_iter()
+            .filter(|item| item.kind == "blob")
+            .map(|item| item.path)
+            .collect())
     }
 
     /// Search file by name
@@ -99,21 +188,16 @@ impl GithubClient {
         username: &str,
         repository_name: &str,
         file_name: &str,
-    ) -> anyhow::Result<Vec<String>> {
-        let response: GithubSearchFile = self
-            .agent
-            .get(&format!(
-                "{BASE_URL}/search/code?q=repo:{username}/{repository_name}+filename:{file_name}"
-            ))
-            .set("Accept", "application/json")
-            .set("Authorization", &format!("Bearer {}", self.token))
-            .call()?
-            .into_json()?;
+    ) -> Result<Vec<String>> {
+        let response: GithubSearchFile = self.get(&format!(
+            "/search/code?q=repo:{username}/{repository_name}+filename:{file_name}"
+        ))?;
 
         if response.incomplete_results {
-            println!(
+            tracing::warn!(
                 "Too many {} files in repository, checking only the first {} ones.",
-                file_name, response.total_count,
+                file_name,
+                response.total_count,
             );
         }
 
@@ -124,7 +208,7 @@ impl GithubClient {
                 if let Some(path_string) = i.path.to_str() {
                     Some(path_string.to_string())
                 } else {
-                    println!("Path.to_str failed for {}", i.path.to_string_lossy());
+                    tracing::warn!("Path.to_str failed for {}", i.path.to_string_lossy());
                     None
                 }
             })
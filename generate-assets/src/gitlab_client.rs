@@ -1,12 +1,38 @@
-use anyhow::bail;
-use serde::Deserialize;
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize};
+
+use crate::{
+    error::{GenerateAssetsError, Result},
+    http::{decode_content, retry_rate_limited, HttpContext},
+    spdx,
+};
 
 const BASE_URL: &str = "https://gitlab.com/api/v4/projects";
+const USER_URL: &str = "https://gitlab.com/api/v4/user";
 
 #[derive(Deserialize)]
-pub struct GitlabProjectSearchResponse {
+pub struct GitlabProject {
     pub id: usize,
     pub default_branch: String,
+    /// The license Gitlab found in the project, `None` if it found none.
+    #[serde(default)]
+    pub license: Option<GitlabLicense>,
+}
+
+/// A license found by Gitlab, identified by a lowercase `key` like `apache-2.0`.
+#[derive(Deserialize)]
+pub struct GitlabLicense {
+    pub key: String,
+    pub name: String,
+}
+
+impl GitlabLicense {
+    /// The SPDX identifier of the license, `None` for licenses Gitlab doesn't know, whose key
+    /// is `other`.
+    pub fn spdx_id(&self) -> Option<&'static str> {
+        spdx::spdx_identifier(&self.key)
+    }
 }
 
 #[derive(Deserialize)]
@@ -17,36 +43,70 @@ struct GitlabContentResponse {
 
 pub struct GitlabClient {
     agent: ureq::Agent,
-    // This is not currently used because we have so few assets using gitlab that we don't need it.
-    _token: String,
+    /// Sent with every request when set, as Gitlab allows more requests with a token.
+    token: String,
+    max_rate_limit_wait: Option<Duration>,
 }
 
 impl GitlabClient {
-    pub fn new(token: String) -> Self {
-        let agent: ureq::Agent = ureq::AgentBuilder::new()
-            .user_agent("bevy-website-generate-assets")
-            .build();
+    pub fn new(token: String, http: &HttpContext) -> Self {
+        let agent = http.agent().clone();
 
         Self {
             agent,
-            _token: token,
+            token,
+            max_rate_limit_wait: http.max_rate_limit_wait(),
         }
     }
 
-    /// Finds a list of repo based on their name
-    /// Useful to get the repo `id` and `default_branch`
-    pub fn search_project_by_name(
-        &self,
-        repository_name: &str,
-    ) -> anyhow::Result<Vec<GitlabProjectSearchResponse>> {
-        let response: Vec<GitlabProjectSearchResponse> = self
-            .agent
-            .get(&format!("{BASE_URL}?search={repository_name}"))
+    /// Gets `url` and deserializes the JSON response, waiting for the rate limit to reset if
+    /// [`HttpConfig::max_rate_limit_wait`] allows it.
+    ///
+    /// [`HttpConfig::max_rate_limit_wait`]: crate::config::HttpConfig::max_rate_limit_wait
+    fn get<T: DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let response = retry_rate_limited(self.max_rate_limit_wait, || {
+            let mut request = self.agent.get(url).set("Accept", "application/json");
+            if !self.token.is_empty() {
+                request = request.set("PRIVATE-TOKEN", &self.token);
+            }
+            request
+                .call()
+                .map_err(|err| GenerateAssetsError::from_ureq(url, err))
+        })?;
+        response
+            .into_json()
+            .map_err(|err| GenerateAssetsError::parse(format!("response of {url}"), err))
+    }
+
+    /// Checks that Gitlab accepts the token.
+    pub fn check_token(&self) -> Result<()> {
+        self.agent
+            .get(USER_URL)
             .set("Accept", "application/json")
-            // .set("Authorization", &format!("Bearer {}", self.token))
-            .call()?
-            .into_json()?;
-        Ok(response)
+            .set("PRIVATE-TOKEN", &self.token)
+            .call()
+            .map_err(|err| GenerateAssetsError::from_ureq(USER_URL, err))?;
+        Ok(())
+    }
+
+    /// Gets the project at `path`, like `group/subgroup/project`, to get its `id`,
+    /// `default_branch` and `license`.
+    ///
+    /// Fails with [`GenerateAssetsError::NotFound`] for the repository if it was deleted or
+    /// made private.
+    pub fn get_project(&self, path: &str) -> Result<GitlabProject> {
+        // The path is a single segment of the URL, with its slashes encoded
+        let id: String = url::form_urlencoded::byte_serialize(path.as_bytes()).collect();
+        match self.get(&format!("{BASE_URL}/{id}?license=true")) {
+            Err(GenerateAssetsError::NotFound { status, .. }) => {
+                Err(GenerateAssetsError::NotFound {
+                    what: format!("Gitlab project {path}"),
+                    status,
+                    repository: true,
+                })
+            }
+            result => result,
+        }
     }
 
     /// Gets the content of a file from a gitlab repo
@@ -55,22 +115,12 @@ impl GitlabClient {
         id: usize,
         default_branch: &str,
         content_path: &str,
-    ) -> anyhow::Result<String> {
-        let response: GitlabContentResponse = self
-            .agent
-            .get(&format!(
-                "{BASE_URL}/{id}/repository/files/{content_path}?ref={default_branch}"
-            ))
-            .set("Accept", "application/json")
-            // .set("Authorization", &format!("Bearer {}", self.token))
-            .call()?
-            .into_json()?;
+    ) -> Result<String> {
+        let response: GitlabContentResponse = self.get(&format!(
+            "{BASE_URL}/{id}/repository/files/{content_path}?ref={default_branch}"
+        ))?;
 
-        if response.encoding == "base64" {
-            let data = base64::decode(response.content.replace('\n', "").trim())?;
-            Ok(String::from_utf8(data)?)
-        } else {
-            bail!("Content is not in base64");
-        }
+        decode_content(&response.encoding, &response.content)
+            .map_err(|err| GenerateAssetsError::parse(content_path, err))
     }
 }
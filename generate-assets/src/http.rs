@@ -0,0 +1,179 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Context;
+
+use crate::{
+    cassette::Cassette,
+    config::HttpConfig,
+    error::{GenerateAssetsError, Result, Source},
+};
+
+/// HTTP state shared by every client.
+///
+/// All clients use the same [`ureq::Agent`], so its pool of keep-alive connections
+/// is reused across Github, Gitlab and any other request made during a run.
+#[derive(Clone)]
+pub struct HttpContext {
+    agent: ureq::Agent,
+    max_rate_limit_wait: Option<Duration>,
+}
+
+impl HttpContext {
+    /// Builds the shared agent following `config`.
+    ///
+    /// Unless a proxy is configured, proxy settings are read from the usual
+    /// environment variables (`HTTPS_PROXY`, `ALL_PROXY`, ...). With a cassette, responses
+    /// are recorded or replayed, see [`cassette`](crate::cassette).
+    pub fn new(config: &HttpConfig) -> anyhow::Result<Self> {
+        let mut builder = ureq::AgentBuilder::new()
+            .user_agent(&config.user_agent)
+            .timeout_connect(config.connect_timeout)
+            .timeout_read(config.read_timeout)
+            .max_idle_connections_per_host(config.max_idle_connections_per_host)
+            .try_proxy_from_env(true);
+
+        if let Some(proxy) = &config.proxy {
+            let proxy = ureq::Proxy::new(proxy)
+                .with_context(|| format!("Invalid proxy configuration: {proxy}"))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(cassette) = &config.cassette {
+            builder = builder.middleware(Cassette::new(cassette.clone())?);
+        }
+
+        Ok(Self {
+            agent: builder.build(),
+            max_rate_limit_wait: config.max_rate_limit_wait,
+        })
+    }
+
+    /// The shared agent, cloning it keeps sharing its connection pool.
+    pub fn agent(&self) -> &ureq::Agent {
+        &self.agent
+    }
+
+    /// See [`HttpConfig::max_rate_limit_wait`].
+    pub fn max_rate_limit_wait(&self) -> Option<Duration> {
+        self.max_rate_limit_wait
+    }
+}
+
+impl Default for HttpContext {
+    fn default() -> Self {
+        Self::new(&HttpConfig::default()).expect("The default HTTP configuration is valid")
+    }
+}
+
+/// Makes `request`, and while it is [rate limited](GenerateAssetsError::RateLimited), waits
+/// for the limit to reset and makes it again, waiting for `max_wait` at most in total.
+///
+/// Requests fail right away without `max_wait`, or if the API doesn't tell when its limit
+/// resets.
+pub(crate) fn retry_rate_limited<T>(
+    max_wait: Option<Duration>,
+    mut request: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let mut waited = Duration::ZERO;
+    loop {
+        match request() {
+            Err(GenerateAssetsError::RateLimited {
+                url,
+                reset: Some(reset),
+            }) if max_wait.is_some() => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                // A second after the reset, as it is rounded to seconds. Waiting at least a
+                // second, so a reset in the past still counts towards `max_wait`
+                let wait = Duration::from_secs((reset + 1).saturating_sub(now).max(1));
+                if max_wait.is_some_and(|max_wait| waited + wait > max_wait) {
+                    return Err(GenerateAssetsError::RateLimited {
+                        url,
+                        reset: Some(reset),
+                    });
+                }
+                tracing::warn!(
+                    "Rate limited by {url}, retrying in {} seconds",
+                    wait.as_secs()
+                );
+                std::thread::sleep(wait);
+                waited += wait;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Decodes the content of a file returned by the Github or Gitlab API.
+pub(crate) fn decode_content(encoding: &str, content: &str) -> Result<String, Source> {
+    if encoding != "base64" {
+        return Err(format!("Content is encoded in {encoding}, not base64").into());
+    }
+    let data = base64::decode(content.replace('\n', "").trim())?;
+    Ok(String::from_utf8(data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limited(reset: u64) -> GenerateAssetsError {
+        GenerateAssetsError::RateLimited {
+            url: "url".to_string(),
+            reset: Some(reset),
+        }
+    }
+
+    #[test]
+    fn retries_rate_limited_requests_once_the_limit_resets() {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let max_wait = Some(Duration::from_secs(60));
+
+        // The limit already reset, so the request is made again after a second
+        let mut requests = 0;
+        let result = retry_rate_limited(max_wait, || {
+            requests += 1;
+            if requests == 1 {
+                Err(rate_limited(now - 10))
+            } else {
+                Ok(requests)
+            }
+        });
+        assert_eq!(result.unwrap(), 2);
+
+        let mut requests = 0;
+        let result = retry_rate_limited(None, || -> Result<()> {
+            requests += 1;
+            Err(rate_limited(now - 10))
+        });
+        assert!(matches!(
+            result,
+            Err(GenerateAssetsError::RateLimited { .. })
+        ));
+        assert_eq!(requests, 1);
+
+        // The limit keeps being hit after a reset in the past, each retry waiting a second
+        let mut requests = 0;
+        let result = retry_rate_limited(Some(Duration::from_secs(1)), || -> Result<()> {
+            requests += 1;
+            Err(rate_limited(now - 10))
+        });
+        assert!(matches!(
+            result,
+            Err(GenerateAssetsError::RateLimited { .. })
+        ));
+        assert_eq!(requests, 2);
+
+        // Resetting later than the maximum wait
+        let result =
+            retry_rate_limited(max_wait, || -> Result<()> { Err(rate_limited(now + 3600)) });
+        assert!(matches!(
+            result,
+            Err(GenerateAssetsError::RateLimited { .. })
+        ));
+    }
+}
@@ -0,0 +1,571 @@
+use std::{collections::BTreeMap, io, num::NonZeroUsize};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bevy_releases::{deserialize_valid_versions, join_versions, BevyVersion},
+    contact::Contacts,
+    demo::Demo,
+    spdx::normalize_licenses,
+    Asset, AssetNode, Deprecation, FeaturedConfig, Icon, Pricing, Section,
+};
+
+/// Version of the format of [`AssetIndex`], increased on every breaking change.
+pub const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Every asset with its metadata, written as `assets.json` for third-party tools like
+/// plugin managers or editor extensions.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AssetIndex {
+    pub version: u32,
+    pub assets: Vec<IndexedAsset>,
+}
+
+/// An [`Asset`] in the [`AssetIndex`], along with where its page is generated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct IndexedAsset {
+    pub name: String,
+    /// Path of the page of the asset on the website, like `assets/2d/bevy_ecs_tilemap`.
+    pub path: String,
+    /// Names of the sections containing the asset, from the top-level one.
+    pub sections: Vec<String>,
+    pub link: String,
+    pub description: String,
+    /// Path of the image of the asset on the website.
+    pub image: Option<String>,
+    pub licenses: Vec<String>,
+    #[serde(deserialize_with = "deserialize_valid_versions")]
+    pub bevy_versions: Vec<BevyVersion>,
+    #[serde(default)]
+    pub latest_version: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub order: Option<usize>,
+    #[serde(default)]
+    pub deprecated: Option<Deprecation>,
+    #[serde(default)]
+    pub demo: Option<Demo>,
+    /// The icon of the asset, with the path of its image on the website.
+    #[serde(default)]
+    pub icon: Option<Icon>,
+    #[serde(default)]
+    pub pricing: Option<Pricing>,
+    #[serde(default)]
+    pub platforms: Vec<String>,
+    /// Names of the assets this asset depends on.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Names of the assets this asset extends.
+    #[serde(default)]
+    pub extends: Vec<String>,
+    #[serde(default)]
+    pub contacts: Contacts,
+    /// When the asset was added, as a `YYYY-MM-DD` date, if known.
+    #[serde(default)]
+    pub added: Option<String>,
+    /// When the asset was last changed, as a `YYYY-MM-DD` date, if known.
+    #[serde(default)]
+    pub updated: Option<String>,
+    /// Name of the source the asset was read from, if it isn't the asset folder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// A document of the client-side search index, written as `search_index.json`.
+///
+/// The index is a list of documents, which can be loaded as is by fuse.js, or added
+/// one by one to an elasticlunr index using `id` as the reference.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SearchDocument {
+    /// Path of the page of the asset, see [`IndexedAsset::path`].
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Names of the sections containing the asset, from the top-level one.
+    pub sections: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+/// The assets supporting a Bevy version, see [`AssetIndex::bevy_versions`].
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VersionListing {
+    /// A minor release of Bevy, like `0.13`, or `main`.
+    pub version: BevyVersion,
+    /// Paths of the assets supporting this version, see [`IndexedAsset::path`].
+    pub assets: Vec<String>,
+}
+
+/// The most recently added and updated assets, written as `recent.json`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RecentAssets {
+    /// Paths of the most recently added assets, from the newest.
+    pub added: Vec<String>,
+    /// Paths of the most recently updated assets, from the newest.
+    pub updated: Vec<String>,
+}
+
+/// The featured assets, written as `featured.json` for the assets landing page.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeaturedAssets {
+    /// Number of featured assets shown at once, or every one if not set.
+    pub shown: Option<usize>,
+    /// Number of days the same featured assets are shown before rotating to the next ones.
+    pub rotation_days: Option<usize>,
+    /// Featured assets, in the order of the featured file.
+    pub assets: Vec<FeaturedEntry>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct FeaturedEntry {
+    #[serde(flatten)]
+    pub asset: IndexedAsset,
+    /// First day the asset is featured, as a `YYYY-MM-DD` date.
+    pub from: Option<String>,
+    /// Last day the asset is featured, as a `YYYY-MM-DD` date.
+    pub until: Option<String>,
+}
+
+/// The assets under each license, written as `licenses.json` so the website can list
+/// assets by license, see [`normalize_licenses`].
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct LicenseListing {
+    /// Paths of the assets available under each license, alone or among others.
+    pub licenses: BTreeMap<String, Vec<String>>,
+    /// Paths of the assets available under exactly the same licenses, by licenses
+    /// joined with ` OR `, like `Apache-2.0 OR MIT`.
+    pub combinations: BTreeMap<String, Vec<String>>,
+    /// Paths of the assets without any license.
+    pub unlicensed: Vec<String>,
+}
+
+impl AssetIndex {
+    /// Builds the index of every asset of `root` and its subsections, sorted by path.
+    pub fn new(root: &Section) -> Self {
+        let mut assets = vec![];
+        collect(root, &root.slug, &mut vec![], &mut assets);
+        assets.sort_by(|asset1, asset2| asset1.path.cmp(&asset2.path));
+
+        AssetIndex {
+            version: INDEX_FORMAT_VERSION,
+            assets,
+        }
+    }
+
+    /// The documents of the client-side search index, one per asset.
+    pub fn search_documents(&self) -> Vec<SearchDocument> {
+        self.assets
+            .iter()
+            .map(|asset| SearchDocument {
+                id: asset.path.clone(),
+                name: asset.name.clone(),
+                description: asset.description.clone(),
+                sections: asset.sections.clone(),
+                tags: asset.tags.clone(),
+            })
+            .collect()
+    }
+
+    /// The paths of the assets using each tag, written as `tags.json` so the website can
+    /// list assets by tag across sections.
+    pub fn tags(&self) -> BTreeMap<String, Vec<String>> {
+        let mut tags: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for asset in &self.assets {
+            for tag in &asset.tags {
+                tags.entry(tag.clone())
+                    .or_default()
+                    .push(asset.path.clone());
+            }
+        }
+        tags
+    }
+
+    /// The assets of `config`, with how the website rotates through them. Featured names
+    /// that don't match any asset are left out, the `validate` binary reports them.
+    pub fn featured(&self, config: &FeaturedConfig) -> FeaturedAssets {
+        FeaturedAssets {
+            shown: config.shown.map(NonZeroUsize::get),
+            rotation_days: config.rotation_days.map(NonZeroUsize::get),
+            assets: config
+                .assets
+                .iter()
+                .filter_map(|featured| {
+                    let asset = self
+                        .assets
+                        .iter()
+                        .find(|asset| asset.name == featured.name)?;
+                    Some(FeaturedEntry {
+                        asset: asset.clone(),
+                        from: featured.from.clone(),
+                        until: featured.until.clone(),
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// The paths of the assets under each license, and each combination of licenses.
+    pub fn licenses(&self) -> LicenseListing {
+        let mut listing = LicenseListing::default();
+        for asset in &self.assets {
+            let licenses = normalize_licenses(&asset.licenses);
+            if licenses.is_empty() {
+                listing.unlicensed.push(asset.path.clone());
+                continue;
+            }
+            for license in &licenses {
+                listing
+                    .licenses
+                    .entry(license.clone())
+                    .or_default()
+                    .push(asset.path.clone());
+            }
+            listing
+                .combinations
+                .entry(licenses.join(" OR "))
+                .or_default()
+                .push(asset.path.clone());
+        }
+        listing
+    }
+
+    /// The `count` most recently added and updated assets. Assets without a known date
+    /// are left out, and assets changed on the same day are sorted by path.
+    pub fn recent(&self, count: usize) -> RecentAssets {
+        let newest = |date: fn(&IndexedAsset) -> Option<&String>| {
+            let mut assets: Vec<_> = self
+                .assets
+                .iter()
+                .filter_map(|asset| Some((date(asset)?, &asset.path)))
+                .collect();
+            assets.sort_by(|(date1, path1), (date2, path2)| {
+                date2.cmp(date1).then_with(|| path1.cmp(path2))
+            });
+            assets
+                .into_iter()
+                .take(count)
+                .map(|(_, path)| path.clone())
+                .collect()
+        };
+
+        RecentAssets {
+            added: newest(|asset| asset.added.as_ref()),
+            updated: newest(|asset| asset.updated.as_ref()),
+        }
+    }
+
+    /// The paths of the assets supporting each Bevy version, from `main` to the oldest
+    /// release, written as `bevy_versions.json` to back the version selector of the website.
+    ///
+    /// Patch releases, like `0.12.1`, are grouped under their minor release, so an asset
+    /// listing both `0.12` and `0.12.1` is only listed once. Versions that aren't releases of
+    /// Bevy yet, like `0.99`, are left out.
+    pub fn bevy_versions(&self) -> Vec<VersionListing> {
+        let mut by_version: BTreeMap<BevyVersion, Vec<String>> = BTreeMap::new();
+        for asset in &self.assets {
+            let mut versions: Vec<_> = asset
+                .bevy_versions
+                .iter()
+                .map(|version| version.minor())
+                .filter(|version| version.is_known())
+                .collect();
+            versions.sort_unstable();
+            versions.dedup();
+            for version in versions {
+                by_version
+                    .entry(version)
+                    .or_default()
+                    .push(asset.path.clone());
+            }
+        }
+
+        by_version
+            .into_iter()
+            .rev()
+            .map(|(version, assets)| VersionListing { version, assets })
+            .collect()
+    }
+
+    /// Writes the assets as CSV, one row per asset, for maintainers who triage the ecosystem
+    /// in spreadsheets. Lists like licenses are joined with `, ` in a single column.
+    pub fn write_csv(&self, writer: impl io::Write) -> anyhow::Result<()> {
+        let mut csv = csv::Writer::from_writer(writer);
+        csv.write_record([
+            "name",
+            "section",
+            "path",
+            "link",
+            "description",
+            "licenses",
+            "bevy_versions",
+            "tags",
+            "pricing",
+            "platforms",
+            "order",
+            "deprecated",
+            "added",
+            "updated",
+        ])?;
+        for asset in &self.assets {
+            csv.write_record([
+                asset.name.as_str(),
+                &asset.sections.join(" / "),
+                &asset.path,
+                &asset.link,
+                &asset.description,
+                &asset.licenses.join(", "),
+                &join_versions(&asset.bevy_versions),
+                &asset.tags.join(", "),
+                &asset
+                    .pricing
+                    .as_ref()
+                    .map(|pricing| pricing.to_string())
+                    .unwrap_or_default(),
+                &asset.platforms.join(", "),
+                &asset
+                    .order
+                    .map(|order| order.to_string())
+                    .unwrap_or_default(),
+                asset
+                    .deprecated
+                    .as_ref()
+                    .map_or("", |deprecation| deprecation.reason.as_str()),
+                asset.added.as_deref().unwrap_or_default(),
+                asset.updated.as_deref().unwrap_or_default(),
+            ])?;
+        }
+        csv.flush()?;
+        Ok(())
+    }
+}
+
+fn collect(
+    section: &Section,
+    section_path: &str,
+    sections: &mut Vec<String>,
+    assets: &mut Vec<IndexedAsset>,
+) {
+    for content in &section.content {
+        match content {
+            AssetNode::Section(subsection) => {
+                sections.push(subsection.name.clone());
+                let path = format!("{section_path}/{}", subsection.slug);
+                collect(subsection, &path, sections, assets);
+                sections.pop();
+            }
+            AssetNode::Asset(asset) => {
+                assets.push(IndexedAsset::new(asset, section_path, sections));
+            }
+        }
+    }
+}
+
+impl IndexedAsset {
+    fn new(asset: &Asset, section_path: &str, sections: &[String]) -> Self {
+        IndexedAsset {
+            name: asset.name.clone(),
+            path: format!("{section_path}/{}", asset.slug),
+            sections: sections.to_vec(),
+            link: asset.link.to_string(),
+            description: asset.description.clone(),
+            image: asset
+                .image
+                .as_ref()
+                .map(|image| format!("{section_path}/{image}")),
+            licenses: asset.licenses.clone().unwrap_or_default(),
+            bevy_versions: asset.bevy_versions.clone().unwrap_or_default(),
+            latest_version: asset.latest_version.clone(),
+            tags: asset.tags.clone().unwrap_or_default(),
+            order: asset.order,
+            deprecated: asset.deprecated.clone(),
+            demo: asset.demo.as_deref().and_then(|demo| Demo::new(demo).ok()),
+            icon: asset.icon.as_deref().map(|icon| match Icon::new(icon) {
+                Icon::Image(image) => Icon::Image(format!("{section_path}/{image}")),
+                emoji => emoji,
+            }),
+            pricing: asset.pricing.clone(),
+            platforms: asset.platforms.clone().unwrap_or_default(),
+            depends_on: asset.depends_on.clone().unwrap_or_default(),
+            extends: asset.extends.clone().unwrap_or_default(),
+            contacts: Contacts::new(asset),
+            added: asset.added.clone(),
+            updated: asset.updated.clone(),
+            source: asset.source.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(name: &str, content: Vec<AssetNode>) -> Section {
+        Section {
+            name: name.to_string(),
+            content,
+            slug: name.to_lowercase(),
+            ..Default::default()
+        }
+    }
+
+    fn asset(name: &str, image: Option<&str>, tags: &[&str], versions: &[&str]) -> AssetNode {
+        AssetNode::Asset(Asset {
+            link: format!("https://github.com/user/{name}").parse().unwrap(),
+            description: "A description".to_string(),
+            image: image.map(str::to_string),
+            licenses: Some(vec!["MIT".to_string()]),
+            bevy_versions: Some(versions.iter().map(|v| v.parse().unwrap()).collect()),
+            tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+            slug: name.to_string(),
+            added: Some("2024-01-01".to_string()),
+            updated: Some("2024-01-01".to_string()),
+            ..Asset::named(name)
+        })
+    }
+
+    #[test]
+    fn lists_nested_assets_sorted_by_path() {
+        let root = section(
+            "Assets",
+            vec![
+                AssetNode::Section(section(
+                    "Games",
+                    vec![asset("zombies", None, &["2d", "ai"], &["0.12.1", "0.99"])],
+                )),
+                AssetNode::Section(section(
+                    "Assets",
+                    vec![AssetNode::Section(section(
+                        "2D",
+                        vec![asset(
+                            "tilemap",
+                            Some("tilemap.png"),
+                            &["2d"],
+                            &["0.12", "main"],
+                        )],
+                    ))],
+                )),
+            ],
+        );
+
+        let index = AssetIndex::new(&root);
+        let paths: Vec<_> = index.assets.iter().map(|a| a.path.as_str()).collect();
+        assert_eq!(paths, ["assets/assets/2d/tilemap", "assets/games/zombies"]);
+
+        let tilemap = &index.assets[0];
+        assert_eq!(tilemap.sections, ["Assets", "2D"]);
+        assert_eq!(
+            tilemap.image.as_deref(),
+            Some("assets/assets/2d/tilemap.png")
+        );
+        assert_eq!(tilemap.licenses, ["MIT"]);
+
+        let documents = index.search_documents();
+        assert_eq!(documents[1].id, "assets/games/zombies");
+        assert_eq!(documents[1].sections, ["Games"]);
+
+        let tags = index.tags();
+        assert_eq!(
+            tags["2d"],
+            ["assets/assets/2d/tilemap", "assets/games/zombies"]
+        );
+        assert_eq!(tags["ai"], ["assets/games/zombies"]);
+
+        let licenses = index.licenses();
+        assert_eq!(
+            licenses.combinations["MIT"],
+            ["assets/assets/2d/tilemap", "assets/games/zombies"]
+        );
+        assert!(licenses.unlicensed.is_empty());
+
+        let mut csv = vec![];
+        index.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(
+            csv.lines().nth(2),
+            Some("zombies,Games,assets/games/zombies,https://github.com/user/zombies,A description,MIT,\"0.12.1, 0.99\",\"2d, ai\",,,,,2024-01-01,2024-01-01")
+        );
+
+        // Assets changed on the same day are sorted by path
+        assert_eq!(
+            index.recent(1),
+            RecentAssets {
+                added: vec!["assets/assets/2d/tilemap".to_string()],
+                updated: vec!["assets/assets/2d/tilemap".to_string()],
+            }
+        );
+
+        let versions = index.bevy_versions();
+        assert_eq!(
+            versions,
+            [
+                VersionListing {
+                    version: BevyVersion::Main,
+                    assets: vec!["assets/assets/2d/tilemap".to_string()],
+                },
+                VersionListing {
+                    version: BevyVersion::release(0, 12),
+                    assets: vec![
+                        "assets/assets/2d/tilemap".to_string(),
+                        "assets/games/zombies".to_string()
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_featured_assets_in_file_order() {
+        let root = section(
+            "Assets",
+            vec![AssetNode::Section(section(
+                "Games",
+                vec![asset("bar", None, &[], &[]), asset("foo", None, &[], &[])],
+            ))],
+        );
+        let config: FeaturedConfig = toml::from_str(
+            "shown = 1\nrotation_days = 7\n\n[[assets]]\nname = \"foo\"\nuntil = \"2024-06-01\"\n\n[[assets]]\nname = \"missing\"\n\n[[assets]]\nname = \"bar\"\n",
+        )
+        .unwrap();
+
+        let featured = AssetIndex::new(&root).featured(&config);
+        assert_eq!(featured.shown, Some(1));
+        assert_eq!(featured.rotation_days, Some(7));
+        let names: Vec<_> = featured
+            .assets
+            .iter()
+            .map(|f| f.asset.name.as_str())
+            .collect();
+        assert_eq!(names, ["foo", "bar"]);
+        assert_eq!(featured.assets[0].until.as_deref(), Some("2024-06-01"));
+    }
+
+    #[test]
+    fn reads_indexes_written_before_the_newer_fields() {
+        let index: AssetIndex = serde_json::from_str(
+            r#"{"version": 1, "assets": [{
+                "name": "tilemap",
+                "path": "assets/2d/tilemap",
+                "sections": ["Assets", "2D"],
+                "link": "https://github.com/user/tilemap",
+                "description": "A description",
+                "image": null,
+                "licenses": ["MIT"],
+                "bevy_versions": ["0.13"],
+                "order": null
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            index.assets,
+            [IndexedAsset {
+                name: "tilemap".to_string(),
+                path: "assets/2d/tilemap".to_string(),
+                sections: vec!["Assets".to_string(), "2D".to_string()],
+                link: "https://github.com/user/tilemap".to_string(),
+                description: "A description".to_string(),
+                licenses: vec!["MIT".to_string()],
+                bevy_versions: vec!["0.13".parse().unwrap()],
+                ..Default::default()
+            }]
+        );
+    }
+}
@@ -1,34 +1,211 @@
-use anyhow::{bail, Context};
+//! Reads the assets of the [bevy-assets](https://github.com/bevyengine/bevy-assets)
+//! repository, fills their metadata from crates.io, Github and Gitlab, validates them and
+//! writes the pages of the Bevy website. The `generate-assets` binary is a thin command line
+//! interface over this library, which other tools can use the same way.
+//!
+//! - The asset model: [`Asset`] and the [`Section`]s containing them, read with
+//!   [`parse_assets`], or file by file with the [`schema`] module.
+//! - The metadata: [`MetadataSource`] gathers the clients, like [`GithubClient`] and
+//!   [`GitlabClient`], and the cached crates.io database dump of [`prepare_crates_db`].
+//!   [`get_metadata`] gets the metadata of a single link.
+//! - The phases of a run: [`parse_assets`] is [`scan_assets`], parsing the asset files,
+//!   followed by [`enrich_assets`], gathering their metadata. The result can be saved as a
+//!   [`snapshot::Snapshot`] to write the output from later.
+//! - The validation: [`validation`] checks assets against [`Rules`], and [`write_back`]
+//!   applies the [`Fix`]es found, or writes the fetched metadata to the asset files.
+//! - The output: the Zola pages are written by the binary, the other formats by their
+//!   modules, like [`api`], [`badges`] or [`index`].
+//!
+//! Errors of the clients and of the parsing are [`GenerateAssetsError`]s, see [`error`].
+//!
+//! The types re-exported here, and the public items of the modules, follow semantic
+//! versioning, see the `CHANGELOG.md` file next to the manifest.
+//!
+//! ```no_run
+//! use generate_assets::{parse_assets, AssetFilter, MetadataSource};
+//!
+//! let assets = parse_assets("assets", &AssetFilter::default(), MetadataSource::default())?;
+//! for asset in assets.assets() {
+//!     println!("{}: {}", asset.name, asset.link);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::{anyhow, bail, Context};
+use bevy_releases::{BevyVersion, BEVY_MAIN};
+use concurrency::{for_each_parallel, Semaphore};
+use config::ConcurrencyConfig;
+use cratesio_dbdump_csvtab::cached_path::CacheBuilder;
 use cratesio_dbdump_csvtab::rusqlite;
 use cratesio_dbdump_csvtab::CratesIODumpLoader;
-use github_client::GithubClient;
-use gitlab_client::GitlabClient;
-use serde::Deserialize;
+use failures::{Failure, Failures};
+use gitlab_client::GitlabLicense;
+use links::{CratesIoLocator, GithubRepoLocator, GitlabProjectLocator};
+use progress::{MetadataOutcome, SectionProgress};
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::{fs, path::PathBuf, str::FromStr};
-
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use walk::{AssetWalker, Entry, SymlinkPolicy};
+
+pub mod api;
+pub mod archive;
+pub mod badges;
+pub mod bevy_releases;
+pub mod cards;
+pub mod cassette;
+pub mod clients;
+pub mod concurrency;
+pub mod config;
+pub mod contact;
+pub mod demo;
+pub mod diff;
+pub mod encoding;
+pub mod error;
+pub mod failures;
+pub mod git;
 pub mod github_client;
 pub mod gitlab_client;
+pub mod http;
+pub mod index;
+pub mod links;
+pub mod markdown;
+pub mod platforms;
+pub mod progress;
+pub mod schema;
+pub mod snapshot;
+pub mod sources;
+pub mod spdx;
+pub mod stats;
+pub mod tags;
+pub mod validation;
+pub mod walk;
+pub mod write_back;
+
+#[cfg(test)]
+mod mock_clients;
+
+pub use clients::{MetadataClient, RepositoryClient};
+pub use config::GeneratorConfig;
+pub use error::GenerateAssetsError;
+pub use failures::ErrorMode;
+pub use github_client::GithubClient;
+pub use gitlab_client::GitlabClient;
+pub use links::AssetLink;
+pub use progress::Progress;
+pub use validation::{AssetValidator, Rules};
+pub use write_back::Fix;
 
 type CratesIoDb = rusqlite::Connection;
 
 const OFFICIAL_BEVY_CRATE_PREFIX_RANGE_START: &str = "bevy";
 const OFFICIAL_BEVY_CRATE_PREFIX_RANGE_END: &str = "bevz";
+const OFFICIAL_BEVY_HOMEPAGE: &str = "https://bevyengine.org";
+const OFFICIAL_BEVY_REPOSITORY: &str = "https://github.com/bevyengine/bevy";
+/// Maximum number of manifests read to find the one of a repository without a root
+/// `Cargo.toml` file, shallowest first.
+const MAX_MANIFESTS_READ: usize = 5;
 
-#[derive(Deserialize, Debug, Clone)]
+/// An asset, read from its file.
+///
+/// It is serialized with the fields of its file, the fields set while parsing and enriching
+/// it are only serialized in a [`snapshot`].
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct Asset {
     pub name: String,
-    pub link: String,
+    pub link: AssetLink,
     pub description: String,
     pub order: Option<usize>,
     pub image: Option<String>,
     pub licenses: Option<Vec<String>>,
-    pub bevy_versions: Option<Vec<String>>,
-
-    // this field is not read from the toml file
+    pub bevy_versions: Option<Vec<BevyVersion>>,
+    /// Latest released version of the asset, filled from crates.io or its `Cargo.toml` when
+    /// not set.
+    pub latest_version: Option<String>,
+    /// Tags from [`tags::TAGS`], grouping assets across sections.
+    pub tags: Option<Vec<String>>,
+    /// Set when the asset is superseded, to point users at its replacement.
+    pub deprecated: Option<Deprecation>,
+    /// A Youtube video, itch.io game or hosted WASM build showing the asset, see
+    /// [`demo::Demo`].
+    pub demo: Option<String>,
+    /// An emoji, or an image file next to the asset file, shown instead of the image when
+    /// there is none, see [`Icon`].
+    pub icon: Option<String>,
+    /// Whether the asset is free, set for commercial assets so they can be labelled.
+    pub pricing: Option<Pricing>,
+    /// Platforms from [`platforms::PLATFORMS`] the asset supports. Inferred from its
+    /// `Cargo.toml` when not set.
+    pub platforms: Option<Vec<String>>,
+    /// Names of the assets this asset depends on, like a plugin built on `bevy_egui`.
+    pub depends_on: Option<Vec<String>>,
+    /// Names of the assets this asset adds features to.
+    pub extends: Option<Vec<String>>,
+    /// Invite link to the Discord server of the asset.
+    pub discord: Option<String>,
+    /// Mastodon handle or profile of the maintainer, like `@user@mastodon.social`.
+    pub mastodon: Option<String>,
+    /// Email address or link to reach the maintainer.
+    pub contact: Option<String>,
+    /// Link to the repository or crate of the asset, which its metadata is fetched from when
+    /// `link` points to another page, like its itch.io page or website.
+    pub repository: Option<AssetLink>,
+    /// Name of the crate of the asset on crates.io, when its link doesn't give it, like a link
+    /// to its repository or website. Its metadata is then read from the crates.io database
+    /// dump.
+    pub crate_name: Option<String>,
+    /// Set when the metadata found for the asset is wrong, so none is fetched and the fields
+    /// of its file are used as they are.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skip_metadata: bool,
+    /// Fields pinned in the asset file, which the metadata found for it doesn't fill or
+    /// overwrite.
+    #[serde(default, skip_serializing_if = "ManualFields::is_empty")]
+    pub manual: ManualFields,
+    /// Custom front matter passed through to the page of the asset.
+    #[serde(default)]
+    pub extra: Extra,
+
+    // these fields are not read from the toml file
     #[serde(skip)]
     pub original_path: Option<PathBuf>,
+    /// Set when the repository this asset links to doesn't exist anymore.
+    #[serde(skip)]
+    pub repository_gone: Option<RepositoryGone>,
+    /// Name of the page generated for this asset, unique within its section.
+    #[serde(skip)]
+    pub slug: String,
+    /// When the asset file was added, as a `YYYY-MM-DD` date, see [`Section::set_history`].
+    #[serde(skip)]
+    pub added: Option<String>,
+    /// When the asset file was last changed, as a `YYYY-MM-DD` date.
+    #[serde(skip)]
+    pub updated: Option<String>,
+    /// Previous URLs of the page of this asset, redirecting to it, see [`Section::set_aliases`].
+    #[serde(skip)]
+    pub aliases: Vec<String>,
+    /// Assets this asset works with, in both directions, see [`Section::set_related`].
+    #[serde(skip)]
+    pub related: Vec<RelatedAsset>,
+    /// Metadata found in crates.io, Github or Gitlab, before being merged with the fields
+    /// set in the asset file, without the [pinned](Asset::manual) ones, see [`write_back`].
+    #[serde(skip)]
+    pub fetched_metadata: Option<ExtraMetadata>,
+    /// Set offline when the licenses or Bevy versions of the asset couldn't be found, so its
+    /// page shows a placeholder, see [`Section::set_cached_metadata`].
+    #[serde(skip)]
+    pub metadata_missing: bool,
+    /// Name of the source the asset was read from, see [`sources`], `None` for the assets of
+    /// the asset folder.
+    #[serde(skip)]
+    pub source: Option<String>,
 }
 
 impl Asset {
@@ -46,7 +223,7 @@ impl Asset {
         }
     }
 
-    fn set_bevy_version(&mut self, version: Option<String>) {
+    fn set_bevy_version(&mut self, version: Option<BevyVersion>) {
         if self.bevy_versions.is_some() {
             return;
         }
@@ -54,691 +231,3385 @@ impl Asset {
             self.bevy_versions = Some(vec![version]);
         }
     }
-}
-
-#[derive(Debug, Clone)]
-pub struct Section {
-    pub name: String,
-    pub content: Vec<AssetNode>,
-    pub template: Option<String>,
-    pub header: Option<String>,
-    pub order: Option<usize>,
-    pub sort_order_reversed: bool,
-}
 
-#[derive(Debug, Clone)]
-pub enum AssetNode {
-    Section(Section),
-    Asset(Asset),
-}
-impl AssetNode {
-    pub fn name(&self) -> String {
-        match self {
-            AssetNode::Section(content) => content.name.clone(),
-            AssetNode::Asset(content) => content.name.clone(),
+    fn set_platforms(&mut self, platforms: Vec<String>) {
+        if self.platforms.is_some() || platforms.is_empty() {
+            return;
         }
+        self.platforms = Some(platforms);
     }
-    pub fn order(&self) -> usize {
-        match self {
-            AssetNode::Section(content) => content.order.unwrap_or(99999),
-            AssetNode::Asset(content) => content.order.unwrap_or(99999),
+
+    fn set_latest_version(&mut self, version: Option<String>) {
+        if self.latest_version.is_none() {
+            self.latest_version = version;
         }
     }
+
+    /// The link the metadata of the asset is fetched from: its
+    /// [`repository`](Asset::repository), or its `link` without one.
+    pub fn metadata_link(&self) -> &AssetLink {
+        self.repository.as_ref().unwrap_or(&self.link)
+    }
+
+    /// Sets the metadata found by an external source, if not already set or
+    /// [pinned](Asset::manual).
+    fn set_metadata(&mut self, metadata: ExtraMetadata) {
+        let metadata = self.manual.unpinned(metadata);
+        self.set_license(metadata.license.clone());
+        self.set_bevy_version(metadata.bevy_version);
+        self.set_platforms(metadata.platforms.clone());
+        self.set_latest_version(metadata.latest_version.clone());
+        self.fetched_metadata = Some(metadata);
+    }
 }
 
-#[derive(Default)]
-/// Where to find metadata (bevy version and license) for assets.
-pub struct MetadataSource<'a> {
-    /// Connection to the crates.io database sqlite dump.
-    pub crates_io_db: Option<&'a CratesIoDb>,
-    /// Connection to Github API.
-    pub github_client: Option<&'a GithubClient>,
-    /// Connection to Gitlab API.
-    pub gitlab_client: Option<&'a GitlabClient>,
-    /// Official bevy crates names from crates.io DB dump, in lexigographic order.
-    pub bevy_crates_names: Option<Vec<String>>,
-    /// Prepared statement to retrieve metadata from crates.io.
-    ///
-    /// Initialized with [`get_metadata_from_cratesio_statement`] at the beginning
-    /// of the algorithm, used by [`get_metadata_from_cratesio`] for each asset.
-    pub get_metadata_from_cratesio_statement: Option<rusqlite::Statement<'a>>,
+#[cfg(test)]
+impl Asset {
+    /// An asset named `name` linking to `https://example.com`, with no other field set, to
+    /// build the assets of tests with the struct update syntax.
+    pub(crate) fn named(name: &str) -> Self {
+        Asset {
+            name: name.to_string(),
+            link: "https://example.com".parse().unwrap(),
+            description: String::new(),
+            order: None,
+            image: None,
+            licenses: None,
+            bevy_versions: None,
+            latest_version: None,
+            tags: None,
+            deprecated: None,
+            demo: None,
+            icon: None,
+            pricing: None,
+            platforms: None,
+            depends_on: None,
+            extends: None,
+            discord: None,
+            mastodon: None,
+            contact: None,
+            repository: None,
+            crate_name: None,
+            skip_metadata: false,
+            manual: ManualFields::default(),
+            extra: Extra::default(),
+            original_path: None,
+            repository_gone: None,
+            slug: String::new(),
+            added: None,
+            updated: None,
+            aliases: vec![],
+            related: vec![],
+            fetched_metadata: None,
+            metadata_missing: false,
+            source: None,
+        }
+    }
 }
 
-/// Entry point the algorithm to find [`Asset`] files inside [`Section`] folders,
-/// parse asset files, and gather metadata information about assets from various external sources.
+/// The `[manual]` table of an asset file, pinning the fields set to `true` to the value of
+/// the file, even when it doesn't set them:
 ///
-/// This initialises the root [`Section`], and initialize [`MetadataSource`] with
-/// crates.io's database dump connection and information about official bevy crates.
-pub fn parse_assets(
-    asset_dir: &str,
-    mut metadata_source: MetadataSource,
-) -> anyhow::Result<Section> {
-    let mut asset_root_section = Section {
-        name: "Assets".to_string(),
-        content: vec![],
-        template: Some("assets.html".to_string()),
-        header: Some("Assets".to_string()),
-        order: None,
-        sort_order_reversed: false,
-    };
+/// ```toml
+/// licenses = ["MIT"]
+///
+/// [manual]
+/// licenses = true
+/// ```
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ManualFields {
+    pub licenses: bool,
+    pub bevy_versions: bool,
+    pub platforms: bool,
+    pub latest_version: bool,
+}
 
-    if let Some(db) = metadata_source.crates_io_db {
-        let bevy_crates_ids = if let Ok((bevy_crates_names, bevy_crates_ids)) =
-            get_official_bevy_crates_from_crates_io_db(db)
-        {
-            metadata_source.bevy_crates_names = Some(bevy_crates_names);
-            Some(bevy_crates_ids)
-        } else {
-            None
-        };
-        metadata_source.get_metadata_from_cratesio_statement =
-            Some(get_metadata_from_cratesio_statement(db, bevy_crates_ids)?);
+impl ManualFields {
+    /// Whether no field is pinned.
+    pub fn is_empty(&self) -> bool {
+        *self == ManualFields::default()
     }
 
-    visit_dirs(
-        PathBuf::from_str(asset_dir).unwrap(),
-        &mut asset_root_section,
-        &mut metadata_source,
-    )?;
-    Ok(asset_root_section)
+    /// `metadata` without the fields that are pinned.
+    pub fn unpinned(&self, mut metadata: ExtraMetadata) -> ExtraMetadata {
+        if self.licenses {
+            metadata.license = None;
+        }
+        if self.bevy_versions {
+            metadata.bevy_version = None;
+        }
+        if self.platforms {
+            metadata.platforms.clear();
+        }
+        if self.latest_version {
+            metadata.latest_version = None;
+        }
+        metadata
+    }
 }
 
-/// Recursive traversal of directories inside the cloned "Bevy Assets" project,
-/// each directory is a [`Section`], configured inside the `_category.toml` file,
-/// each other file with a `.toml` extension is an [`Asset`].
-fn visit_dirs(
-    dir: PathBuf,
-    section: &mut Section,
-    metadata_source: &mut MetadataSource,
-) -> anyhow::Result<()> {
-    if dir.is_file() {
-        return Ok(());
-    }
+/// Metadata of an [`Asset`] found in its `Cargo.toml` files or in the crates.io database dump.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExtraMetadata {
+    pub license: Option<String>,
+    pub bevy_version: Option<BevyVersion>,
+    /// Platforms inferred from target-specific dependencies, see
+    /// [`platforms::infer_platforms`].
+    pub platforms: Vec<String>,
+    pub latest_version: Option<String>,
+}
 
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.file_name().unwrap() == ".git" || path.file_name().unwrap() == ".github" {
-            continue;
-        }
-        if path.is_dir() {
-            let folder = path.file_name().unwrap();
-            let (order, sort_order_reversed) = if path.join("_category.toml").exists() {
-                let from_file: toml::Value =
-                    toml::de::from_str(&fs::read_to_string(path.join("_category.toml")).unwrap())
-                        .unwrap();
-                (
-                    from_file
-                        .get("order")
-                        .and_then(|v| v.as_integer())
-                        .map(|v| v as usize),
-                    from_file
-                        .get("sort_order_reversed")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(false),
-                )
-            } else {
-                (None, false)
-            };
-            let mut new_section = Section {
-                name: folder.to_str().unwrap().to_string(),
-                content: vec![],
-                template: None,
-                header: None,
-                order,
-                sort_order_reversed,
-            };
-            visit_dirs(path.clone(), &mut new_section, metadata_source)?;
-            section.content.push(AssetNode::Section(new_section));
-        } else {
-            if path.file_name().unwrap() == "_category.toml"
-                || path.extension().expect("file must have an extension") != "toml"
-            {
-                continue;
-            }
+/// An asset related to another one by their `depends_on` or `extends` fields, so their
+/// pages can link to each other.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RelatedAsset {
+    pub name: String,
+    /// URL of the page of the asset, like `/assets/2d/bevy_ecs_tilemap/`.
+    pub url: String,
+    pub link: String,
+    pub relation: Relation,
+}
 
-            let mut asset: Asset = toml::from_str(&fs::read_to_string(&path).unwrap())?;
-            asset.original_path = Some(path);
+/// How a [`RelatedAsset`] relates to the asset it is listed for.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Relation {
+    /// The asset depends on the related asset.
+    DependsOn,
+    /// The asset extends the related asset.
+    Extends,
+    /// The related asset depends on the asset.
+    DependencyOf,
+    /// The related asset extends the asset.
+    ExtendedBy,
+}
 
-            if let Err(err) = get_extra_metadata(&mut asset, metadata_source) {
-                // We don't want to stop execution here
-                eprintln!("Failed to get metadata for {}", asset.name);
-                eprintln!("ERROR: {err:?}");
-            }
+/// The icon of an [`Asset`], set with its `icon` field.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Icon {
+    Emoji(String),
+    /// Path of the image, relative to the asset file.
+    Image(String),
+}
 
-            section.content.push(AssetNode::Asset(asset));
+impl Icon {
+    /// Reads an `icon` field: text without any ASCII character, like `🦀` or `👨‍💻`, is an
+    /// emoji, anything else the path of an image.
+    pub fn new(icon: &str) -> Self {
+        let is_emoji =
+            !icon.is_empty() && icon.chars().count() <= 8 && !icon.chars().any(|c| c.is_ascii());
+        if is_emoji {
+            Icon::Emoji(icon.to_string())
+        } else {
+            Icon::Image(icon.to_string())
         }
     }
-
-    Ok(())
 }
 
-/// Tries to get bevy supported version and license information from various external sources.
-fn get_extra_metadata(
-    asset: &mut Asset,
-    metadata_source: &mut MetadataSource,
-) -> anyhow::Result<()> {
-    println!("Getting extra metadata for {}", asset.name);
+/// How an [`Asset`] is sold, set with a `pricing = { model = "paid", price = "$15" }`
+/// table.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Pricing {
+    pub model: PricingModel,
+    /// The price, in the format shown on the website, like `$15` or `5€/month`. Required
+    /// for paid assets, and for the paid tier of freemium ones.
+    pub price: Option<String>,
+}
 
-    let url = url::Url::parse(&asset.link)?;
-    let segments = url.path_segments().map(|c| c.collect::<Vec<_>>()).unwrap();
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PricingModel {
+    Free,
+    /// Free, with paid features or content.
+    Freemium,
+    Paid,
+}
 
-    let metadata = match url.host_str() {
-        Some("crates.io") => {
-            if let Some(ref mut statement) = metadata_source.get_metadata_from_cratesio_statement {
-                let crate_name = segments[1];
-                Some(get_metadata_from_crates_db(crate_name, statement)?)
-            } else {
-                None
-            }
-        }
-        Some("github.com") => {
-            if let Some(client) = metadata_source.github_client {
-                let username = segments[0];
-                let repository_name = segments[1];
-                Some(get_metadata_from_github(
-                    client,
-                    username,
-                    repository_name,
-                    &metadata_source.bevy_crates_names,
-                )?)
-            } else {
-                None
-            }
+impl std::fmt::Display for Pricing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.model {
+            PricingModel::Free => write!(f, "Free")?,
+            PricingModel::Freemium => write!(f, "Freemium")?,
+            PricingModel::Paid => write!(f, "Paid")?,
         }
-        Some("gitlab.com") => {
-            if let Some(client) = metadata_source.gitlab_client {
-                let repository_name = segments[1];
-                Some(get_metadata_from_gitlab(
-                    client,
-                    repository_name,
-                    &metadata_source.bevy_crates_names,
-                )?)
-            } else {
-                None
-            }
+        if let Some(price) = &self.price {
+            write!(f, " ({price})")?;
         }
-        None => None,
-        _ => bail!("Unknown host: {}", asset.link),
-    };
-
-    if let Some((license, version)) = metadata {
-        asset.set_license(license);
-        asset.set_bevy_version(version);
+        Ok(())
     }
+}
 
-    Ok(())
+/// Why an [`Asset`] is deprecated, set with a `deprecated = { reason = "..." }` table.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Deprecation {
+    pub reason: String,
+    /// The asset replacing this one, as the name of another asset or a link.
+    pub successor: Option<String>,
 }
 
-/// Merge two licenses, get the combination of both of them.
-fn merge_license(license1: Option<String>, license2: Option<String>) -> Option<String> {
-    if license1.is_none() {
-        return license2;
-    }
-    if license2.is_none() {
-        return license1;
-    }
+/// The repository an asset links to doesn't exist anymore, as opposed to being
+/// temporarily unreachable, see [`GenerateAssetsError::repository_gone`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RepositoryGone {
+    /// The HTTP status returned for the repository, `None` if it couldn't be found by a search.
+    pub status: Option<u16>,
+}
 
-    let license1 = license1.unwrap();
-    let license2 = license2.unwrap();
-    if license1.contains(&license2) {
-        return Some(license1);
-    }
-    if license2.contains(&license1) {
-        return Some(license2);
-    }
+/// A folder of bevy-assets, containing assets and sub-sections.
+///
+/// It is serialized in the format of a [`snapshot`], with every field of its assets.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Section {
+    pub name: String,
+    pub content: Vec<AssetNode>,
+    pub template: Option<String>,
+    pub header: Option<String>,
+    pub description: Option<String>,
+    pub order: Option<usize>,
+    pub sort_order_reversed: bool,
+    /// How the assets of this section are sorted.
+    pub sort_by: SortBy,
+    /// Number of assets per page, the section being on a single page when `None`.
+    pub paginate_by: Option<NonZeroUsize>,
+    /// Fields every asset of this section and its subsections must set.
+    pub required_fields: Vec<AssetField>,
+    /// Custom front matter passed through to the page of the section.
+    pub extra: Extra,
+    /// Name of the folder generated for this section, unique within its parent section.
+    pub slug: String,
+}
 
-    Some(license1 + " " + &license2)
+/// Configuration of a [`Section`], read from the `_category.toml` file of its folder.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CategoryConfig {
+    pub order: Option<usize>,
+    #[serde(default)]
+    pub sort_order_reversed: bool,
+    /// How the assets of this section are sorted.
+    #[serde(default)]
+    pub sort_by: SortBy,
+    /// Number of assets per page, the section being on a single page when not set.
+    pub paginate_by: Option<NonZeroUsize>,
+    /// Zola template used to render the section.
+    pub template: Option<String>,
+    /// Message displayed at the top of the section.
+    pub header: Option<String>,
+    pub description: Option<String>,
+    /// Fields every asset of this section and its subsections must set.
+    #[serde(default)]
+    pub required_fields: Vec<AssetField>,
+    /// Custom front matter passed through to the page of the section.
+    #[serde(default)]
+    pub extra: Extra,
 }
 
-/// Merge two versions, get the "maximum" of the two
-/// TODO: normalize versions to be able to compare them
-/// In the mean time this just returns version1 if it's Some
-fn merge_version(version1: Option<String>, version2: Option<String>) -> Option<String> {
-    if version1.is_some() {
-        return version1;
+/// Keys of the `extra` front matter written by the generator, which can't be set in
+/// [`Extra`].
+pub const RESERVED_EXTRA_KEYS: &[&str] = &[
+    "added",
+    "bevy_versions",
+    "card",
+    "contacts",
+    "demo",
+    "deprecated",
+    "depends_on",
+    "extends",
+    "header_message",
+    "icon",
+    "image",
+    "latest_version",
+    "licenses",
+    "link",
+    "metadata_missing",
+    "platforms",
+    "pricing",
+    "related",
+    "slug",
+    "sort_order_reversed",
+    "tags",
+    "updated",
+];
+
+/// Custom `[extra]` front matter of an asset or a section, like a `banner` image or a
+/// `hidden` flag, passed through to the generated content for the templates.
+///
+/// Any key can be set, except the [`RESERVED_EXTRA_KEYS`]. Keys known to the templates
+/// must have the expected type: `banner` is a string, and `hidden` a boolean.
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(transparent)]
+pub struct Extra(pub toml::Table);
+
+impl<'de> Deserialize<'de> for Extra {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let table = toml::Table::deserialize(deserializer)?;
+        for (key, value) in &table {
+            if RESERVED_EXTRA_KEYS.contains(&key.as_str()) {
+                return Err(D::Error::custom(format!(
+                    "invalid extra key `{key}`, it is set by the generator"
+                )));
+            }
+            let expected = match key.as_str() {
+                "banner" if !value.is_str() => "a string",
+                "hidden" if !value.is_bool() => "`true` or `false`",
+                _ => continue,
+            };
+            return Err(D::Error::custom(format!(
+                "invalid extra key `{key}`, expected {expected}"
+            )));
+        }
+        Ok(Extra(table))
     }
-    version2
 }
 
-/// Gets metadata from a Github project.
-///
-/// This algorithm, in order :
-/// - tries to get metadata from the root `Cargo.toml` file,
-/// - if the license is missing, search the license of the project on Github,
-/// - if metadata is missing, search all `Cargo.toml` files, then tries to get metadata
-/// from all of them, until we have the information we need.
-///
-/// Note:
-/// - The search call of the API has a tendency to return 403 errors after a few number
-/// of calls. Assets that are at the "end" might not have correct metadata because of that.
-/// - This algorithm tries to retain the "best" version and merge all licenses found.
-/// - If a licence and version is found, it will stop searching, but the information
-/// about the version and license could have gotten "better" by searching deper.
-/// - Likewise, the project license is never checked if a license is provided in the root
-/// `Cargo.toml` file.
-fn get_metadata_from_github(
-    client: &GithubClient,
-    username: &str,
-    repository_name: &str,
-    bevy_crates: &Option<Vec<String>>,
-) -> anyhow::Result<(Option<String>, Option<String>)> {
-    let result = get_metadata_from_github_manifest(
-        client,
-        username,
-        repository_name,
-        bevy_crates,
-        "Cargo.toml",
-    );
+/// How the assets of a [`Section`] are sorted, set with `sort_by` in `_category.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    /// Assets with an `order` first, by order, then the others by name, ignoring case.
+    #[default]
+    Order,
+    /// By name, ignoring case.
+    Name,
+    /// From the most recently updated asset, see [`Asset::updated`].
+    LastUpdated,
+}
 
-    let (mut license, mut version) = match result {
-        Ok(lic_ver) => lic_ver,
-        Err(err) => {
-            println!(
-                "Error getting metadata from root cargo file from github: {}",
-                err
-            );
-            (None, None)
-        }
-    };
+/// Name of the file, at the root of the assets directory, configuring which links can be
+/// listed in several top-level sections.
+pub const DUPLICATES_FILE: &str = "_duplicates.toml";
 
-    if license.is_none() {
-        license = client.get_license(username, repository_name).ok();
-    }
+/// Exceptions to the rule that a link is only listed in one top-level section, read from
+/// [`DUPLICATES_FILE`].
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DuplicatesConfig {
+    /// Links that are deliberately listed in several top-level sections, like a game
+    /// that is also listed as an asset.
+    #[serde(default)]
+    pub allowed_links: Vec<String>,
+}
 
-    if license.is_none() || version.is_none() {
-        let cargo_files = match client.search_file(username, repository_name, "Cargo.toml") {
-            Ok(cargo_files) => cargo_files,
-            Err(err) => {
-                println!("Error fetching cargo files from github: {:#}", err);
-                return Ok((license, version));
-            }
-        };
+/// Name of the file, at the root of the assets directory, listing the assets highlighted on
+/// the assets landing page.
+pub const FEATURED_FILE: &str = "_featured.toml";
 
-        let mut cargo_files = cargo_files
-            .iter()
-            //Exclude the root Cargo.toml, we already searched in it
-            .filter(|f| f != &"Cargo.toml");
+/// Assets highlighted on the assets landing page, read from [`FEATURED_FILE`].
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FeaturedConfig {
+    /// Number of featured assets shown at once, the website rotating through the others.
+    pub shown: Option<NonZeroUsize>,
+    /// Number of days the same featured assets are shown before rotating.
+    pub rotation_days: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub assets: Vec<FeaturedAsset>,
+}
 
-        let mut cargo_file = cargo_files.next();
-        while (license.is_none() || version.is_none()) && cargo_file.is_some() {
-            let cargo_file_path = cargo_file.unwrap();
+/// An asset of [`FeaturedConfig`].
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FeaturedAsset {
+    /// Name of the asset, as set in its file.
+    pub name: String,
+    /// First day the asset is featured, as a `YYYY-MM-DD` date.
+    pub from: Option<String>,
+    /// Last day the asset is featured, as a `YYYY-MM-DD` date.
+    pub until: Option<String>,
+}
 
-            let result = get_metadata_from_github_manifest(
-                client,
-                username,
-                repository_name,
-                bevy_crates,
-                cargo_file_path,
-            );
-            match result {
-                Ok((new_license, new_version)) => {
-                    (license, version) = (
-                        merge_license(license, new_license),
-                        merge_version(version, new_version),
-                    );
-                }
-                Err(err) => {
-                    println!(
-                        "Error getting metadata from other cargo file from github: {}",
-                        err
-                    );
-                    return Ok((license, version));
-                }
-            }
+/// Optional fields of an [`Asset`], which a [`Section`] can require.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetField {
+    Image,
+    Licenses,
+    BevyVersions,
+    Order,
+    Tags,
+    Demo,
+    Platforms,
+}
 
-            cargo_file = cargo_files.next();
+impl AssetField {
+    /// Name of the field in asset files.
+    pub fn name(self) -> &'static str {
+        match self {
+            AssetField::Image => "image",
+            AssetField::Licenses => "licenses",
+            AssetField::BevyVersions => "bevy_versions",
+            AssetField::Order => "order",
+            AssetField::Tags => "tags",
+            AssetField::Demo => "demo",
+            AssetField::Platforms => "platforms",
         }
     }
 
-    Ok((license, version))
+    /// Whether `asset` sets this field.
+    pub fn is_set(self, asset: &Asset) -> bool {
+        match self {
+            AssetField::Image => asset.image.is_some(),
+            AssetField::Licenses => asset.licenses.as_ref().is_some_and(|l| !l.is_empty()),
+            AssetField::BevyVersions => asset.bevy_versions.as_ref().is_some_and(|v| !v.is_empty()),
+            AssetField::Order => asset.order.is_some(),
+            AssetField::Tags => asset.tags.as_ref().is_some_and(|t| !t.is_empty()),
+            AssetField::Demo => asset.demo.is_some(),
+            AssetField::Platforms => asset.platforms.as_ref().is_some_and(|p| !p.is_empty()),
+        }
+    }
 }
 
-/// Gets metadata from a `Cargo.toml` file in a Github project.
-fn get_metadata_from_github_manifest(
-    client: &GithubClient,
-    username: &str,
-    repository_name: &str,
-    bevy_crates: &Option<Vec<String>>,
-    path: &str,
-) -> anyhow::Result<(Option<String>, Option<String>)> {
-    let content = client
-        .get_content(username, repository_name, path)
-        .context("Failed to get Cargo.toml from github")?;
+/// Where the page of an asset is, to list it as a [`RelatedAsset`].
+struct Page {
+    name: String,
+    url: String,
+    link: String,
+}
 
-    let cargo_manifest = toml::from_str::<cargo_toml::Manifest>(&content)?;
+impl Page {
+    fn related(&self, relation: Relation) -> RelatedAsset {
+        RelatedAsset {
+            name: self.name.clone(),
+            url: self.url.clone(),
+            link: self.link.clone(),
+            relation,
+        }
+    }
+}
 
-    Ok((
-        get_license(&cargo_manifest),
-        get_bevy_version_from_manifest(&cargo_manifest, bevy_crates),
-    ))
+/// Collects the [`Page`] of every asset of `section`, by name, `url` being the URL of the
+/// section.
+fn collect_pages(section: &Section, url: &str, pages: &mut HashMap<String, Page>) {
+    for content in &section.content {
+        match content {
+            AssetNode::Section(subsection) => {
+                collect_pages(subsection, &format!("{url}/{}", subsection.slug), pages);
+            }
+            AssetNode::Asset(asset) => {
+                pages.entry(asset.name.clone()).or_insert_with(|| Page {
+                    name: asset.name.clone(),
+                    url: format!("{url}/{}/", asset.slug),
+                    link: asset.link.to_string(),
+                });
+            }
+        }
+    }
 }
 
-/// Gets metadata from a Gitlab project.
+/// Turns a name into a URL slug: lowercase, with `/` replaced by `-`, whitespace replaced
+/// by `_`, and every other character that isn't a letter or a digit removed.
 ///
-/// This algorithm only looks into the root `Cargo.toml` file.
-fn get_metadata_from_gitlab(
-    client: &GitlabClient,
-    repository_name: &str,
-    bevy_crates: &Option<Vec<String>>,
-) -> anyhow::Result<(Option<String>, Option<String>)> {
-    let search_result = client.search_project_by_name(repository_name)?;
-
-    let repo = search_result
-        .first()
-        .context("Failed to find gitlab repo")?;
-
-    let content = client
-        .get_content(repo.id, &repo.default_branch, "Cargo.toml")
-        .context("Failed to get Cargo.toml from gitlab")?;
-
-    let cargo_manifest = toml::from_str::<cargo_toml::Manifest>(&content)?;
-    Ok((
-        get_license(&cargo_manifest),
-        get_bevy_version_from_manifest(&cargo_manifest, bevy_crates),
-    ))
-}
+/// Letters and digits of any script are kept, so names that aren't in English still get a
+/// meaningful slug. Slugs can collide, see [`Section::assign_slugs`].
+pub fn slugify(name: &str) -> String {
+    let slug: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            '/' => Some('-'),
+            c if c.is_whitespace() => Some('_'),
+            c if c.is_alphanumeric() || matches!(c, '-' | '_') => Some(c),
+            _ => None,
+        })
+        .collect();
 
-/// Gets the license from a `Cargo.toml` file
-/// Tries to emulate crates.io behavior.
-fn get_license(cargo_manifest: &cargo_toml::Manifest) -> Option<String> {
-    // Get the license from the package information
-    if let Some(cargo_toml::Package {
-        license,
-        license_file,
-        ..
-    }) = &cargo_manifest.package
-    {
-        if let Some(cargo_toml::Inheritable::Set(license)) = license {
-            Some(license.clone())
-        } else {
-            license_file.as_ref().map(|_| String::from("non-standard"))
-        }
+    if slug.is_empty() {
+        "untitled".to_string()
     } else {
-        None
+        slug
     }
 }
 
-/// Find any bevy dependency and get the corresponding bevy version from a `Cargo.toml` file.
+/// URL the page of an asset had at a `previous` location, when it was in `asset_dir`.
 ///
-/// This algorithm checks if a dependency to an official bevy crate is found, in order :
-/// - in the (regular) dependencies,
-/// - in the dev dependencies (used for examples, tests and benchmarks),
-/// - in the workspace dependencies.
-/// It doesn't go deeper if a version is already found.
-fn get_bevy_version_from_manifest(
-    cargo_manifest: &cargo_toml::Manifest,
-    bevy_crates: &Option<Vec<String>>,
+/// The slugs of the previous sections and asset are computed without deduplication, which
+/// is only wrong if they collided at the time.
+fn previous_url(
+    asset_dir: &Path,
+    root_url: &str,
+    previous: &git::PreviousLocation,
+    current_name: &str,
 ) -> Option<String> {
-    let search_range = OFFICIAL_BEVY_CRATE_PREFIX_RANGE_START.to_owned()
-        ..OFFICIAL_BEVY_CRATE_PREFIX_RANGE_END.to_owned();
-
-    let dependencies = cargo_manifest.dependencies.range(search_range.clone());
-    if let Some(bevy_crates) = bevy_crates {
-        let bevy_crates = bevy_crates.iter();
+    let relative = previous.path.strip_prefix(asset_dir).ok()?;
+    let mut url = root_url.to_string();
+    for folder in relative.parent()? {
+        url.push('/');
+        url.push_str(&slugify(folder.to_str()?));
+    }
+    let name = previous.name.as_deref().unwrap_or(current_name);
+    Some(format!("{url}/{}/", slugify(name)))
+}
 
-        // Tries to find an official bevy crate from the asset's dependencies.
-        let mut bevy_dependency =
-            search_bevy_in_manifest_dependencies(dependencies.clone(), bevy_crates.clone());
+impl Section {
+    /// Sets the slug of every asset and subsection of this section, recursively.
+    ///
+    /// Assets and subsections whose names give the same slug are deduplicated by adding a
+    /// numeric suffix (`-2`, `-3`, ...), in the order of their files, so the result doesn't
+    /// depend on the order in which the directory was read.
+    pub fn assign_slugs(&mut self) {
+        let mut contents: Vec<_> = self
+            .content
+            .iter_mut()
+            .map(|content| {
+                let (name, path) = match content {
+                    AssetNode::Section(section) => (&section.name, None),
+                    AssetNode::Asset(asset) => (&asset.name, asset.original_path.clone()),
+                };
+                (slugify(name), path, name.clone(), content)
+            })
+            .collect();
+        contents.sort_by(|(slug1, path1, name1, _), (slug2, path2, name2, _)| {
+            (slug1, path1, name1).cmp(&(slug2, path2, name2))
+        });
+
+        let mut taken: HashSet<String> = contents.iter().map(|(slug, ..)| slug.clone()).collect();
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for (slug, _, _, content) in contents {
+            let count = seen.entry(slug.clone()).or_default();
+            *count += 1;
+            let slug = if *count == 1 {
+                slug
+            } else {
+                let mut suffix = *count;
+                while taken.contains(&format!("{slug}-{suffix}")) {
+                    suffix += 1;
+                }
+                let unique = format!("{slug}-{suffix}");
+                taken.insert(unique.clone());
+                unique
+            };
 
-        if bevy_dependency.is_none() {
-            // Tries to find an official bevy crate from the asset's dev dependencies.
-            // An asset can indirectly depend on bevy through another crate,
-            // but would probably depend on bevy directly for its examples,
-            // benchmarks or tests, in its dev dependencies.
-            let dev_dependencies = cargo_manifest.dev_dependencies.range(search_range.clone());
-            bevy_dependency =
-                search_bevy_in_manifest_dependencies(dev_dependencies, bevy_crates.clone());
+            match content {
+                AssetNode::Section(section) => {
+                    section.slug = slug;
+                    section.assign_slugs();
+                }
+                AssetNode::Asset(asset) => asset.slug = slug,
+            }
+        }
+    }
 
-            if bevy_dependency.is_none() {
-                // Tries to find an official bevy crate from the asset's workspace dependencies.
-                if let Some(ref workspace) = cargo_manifest.workspace {
-                    let workspace_dependencies = workspace.dependencies.range(search_range);
-                    bevy_dependency =
-                        search_bevy_in_manifest_dependencies(workspace_dependencies, bevy_crates);
+    /// Sets when each asset of this section and its subsections was added and last changed,
+    /// from the `history` of their files read with [`git::read_history`].
+    pub fn set_history(&mut self, history: &HashMap<PathBuf, git::FileHistory>) {
+        for content in &mut self.content {
+            match content {
+                AssetNode::Section(section) => section.set_history(history),
+                AssetNode::Asset(asset) => {
+                    let file_history = asset
+                        .original_path
+                        .as_ref()
+                        .and_then(|path| path.canonicalize().ok())
+                        .and_then(|path| history.get(&path));
+                    if let Some(file_history) = file_history {
+                        asset.added = Some(file_history.added.clone());
+                        asset.updated = Some(file_history.updated.clone());
+                    }
                 }
             }
         }
+    }
 
-        bevy_dependency
-    } else {
-        None
+    /// Fills the licenses, Bevy versions, platforms and latest version the assets of this
+    /// section and its subsections don't set from `index`, the `assets.json` file of a
+    /// previous run, matching assets by link. Used offline instead of fetching them.
+    ///
+    /// Assets that still miss their licenses or Bevy versions, while their link could give
+    /// them, are marked with [`Asset::metadata_missing`].
+    pub fn set_cached_metadata(&mut self, index: &index::AssetIndex) {
+        let cached = index
+            .assets
+            .iter()
+            .map(|asset| (asset.link.as_str(), asset))
+            .collect();
+        self.set_cached_metadata_from(&cached);
     }
-}
 
-/// Search the first official bevy crate found in a collection of `Cargo.toml`
-/// dependencies and return its version.
-///
-/// If it was a bit more generic, this function could be called `find_first_intersect_in_sorted_iterators`.
-/// Both `dependencies` and `bevy_crates` are assumed to be sorted (by key for `dependencies`, they are in this context),
-/// and we find the first element that intersect both of them using that knowledge.
-fn search_bevy_in_manifest_dependencies(
-    mut dependencies: std::collections::btree_map::Range<'_, String, cargo_toml::Dependency>,
-    mut bevy_crates: std::slice::Iter<String>,
-) -> Option<String> {
-    let mut dependency = dependencies.next();
-    let mut bevy_crate = bevy_crates.next();
+    fn set_cached_metadata_from(&mut self, cached: &HashMap<&str, &index::IndexedAsset>) {
+        for content in &mut self.content {
+            match content {
+                AssetNode::Section(section) => section.set_cached_metadata_from(cached),
+                AssetNode::Asset(asset) if asset.skip_metadata => asset.metadata_missing = false,
+                AssetNode::Asset(asset) => {
+                    let manual = asset.manual;
+                    if let Some(cached) = cached.get(asset.link.as_str()) {
+                        fn non_empty<T: Clone>(list: &[T]) -> Option<Vec<T>> {
+                            Some(list.to_vec()).filter(|list| !list.is_empty())
+                        }
+                        if !manual.licenses {
+                            asset.licenses = asset
+                                .licenses
+                                .take()
+                                .or_else(|| non_empty(&cached.licenses));
+                        }
+                        if !manual.bevy_versions {
+                            asset.bevy_versions = asset
+                                .bevy_versions
+                                .take()
+                                .or_else(|| non_empty(&cached.bevy_versions));
+                        }
+                        if !manual.platforms {
+                            asset.platforms = asset
+                                .platforms
+                                .take()
+                                .or_else(|| non_empty(&cached.platforms));
+                        }
+                        if !manual.latest_version {
+                            asset.latest_version = asset
+                                .latest_version
+                                .take()
+                                .or_else(|| cached.latest_version.clone());
+                        }
+                    }
+                    asset.metadata_missing = (asset.crate_name.is_some()
+                        || matches!(
+                            MetadataLocation::from_link(asset.metadata_link()),
+                            Ok(MetadataLocation::CratesIo { .. }
+                                | MetadataLocation::Github { .. }
+                                | MetadataLocation::Gitlab { .. })
+                        ))
+                        && ((asset.licenses.is_none() && !manual.licenses)
+                            || (asset.bevy_versions.is_none() && !manual.bevy_versions));
+                }
+            }
+        }
+    }
 
-    while dependency.is_some() && bevy_crate.is_some() {
-        let dependency_name = dependency.unwrap().0;
-        let bevy_crate_name = bevy_crate.unwrap();
+    /// Sets the previous URLs of the page of each asset of this section and its subsections,
+    /// from the previous locations of their files read with [`git::read_renames`].
+    ///
+    /// A previous location gives an alias when moving the file or renaming the asset changed
+    /// the URL, so links to the old page redirect to the new one. `asset_dir` is the folder
+    /// of this section.
+    pub fn set_aliases(
+        &mut self,
+        renames: &HashMap<PathBuf, Vec<git::PreviousLocation>>,
+        asset_dir: &Path,
+    ) {
+        let Ok(asset_dir) = asset_dir.canonicalize() else {
+            return;
+        };
+        let url = format!("/{}", self.slug);
+        self.set_aliases_at(renames, &asset_dir, &url, &url);
+    }
 
-        match dependency_name.cmp(bevy_crate_name) {
-            Ordering::Less => dependency = dependencies.next(),
-            Ordering::Equal => {
-                let dependency_version =
-                    get_bevy_manifest_dependency_version(dependency.unwrap().1);
+    fn set_aliases_at(
+        &mut self,
+        renames: &HashMap<PathBuf, Vec<git::PreviousLocation>>,
+        asset_dir: &Path,
+        root_url: &str,
+        url: &str,
+    ) {
+        for content in &mut self.content {
+            match content {
+                AssetNode::Section(section) => {
+                    let url = format!("{url}/{}", section.slug);
+                    section.set_aliases_at(renames, asset_dir, root_url, &url);
+                }
+                AssetNode::Asset(asset) => {
+                    let Some(previous) = asset
+                        .original_path
+                        .as_ref()
+                        .and_then(|path| path.canonicalize().ok())
+                        .and_then(|path| renames.get(&path))
+                    else {
+                        continue;
+                    };
+                    let current_url = format!("{url}/{}/", asset.slug);
+                    for location in previous {
+                        let Some(alias) = previous_url(asset_dir, root_url, location, &asset.name)
+                        else {
+                            continue;
+                        };
+                        if alias != current_url && !asset.aliases.contains(&alias) {
+                            asset.aliases.push(alias);
+                        }
+                    }
+                }
+            }
+        }
+    }
 
-                if dependency_version.is_some() {
-                    return dependency_version;
+    /// Sets the related assets of each asset of this section and its subsections, from the
+    /// names listed in their `depends_on` and `extends` fields. Both assets get the
+    /// relationship, so the page of a dependency also links to the assets using it.
+    ///
+    /// Unknown names are ignored, they are reported by the validation.
+    pub fn set_related(&mut self) {
+        let mut pages = HashMap::new();
+        collect_pages(self, &format!("/{}", self.slug), &mut pages);
+
+        let mut related: HashMap<String, Vec<RelatedAsset>> = HashMap::new();
+        for asset in self.assets() {
+            let relations = [
+                (
+                    &asset.depends_on,
+                    Relation::DependsOn,
+                    Relation::DependencyOf,
+                ),
+                (&asset.extends, Relation::Extends, Relation::ExtendedBy),
+            ];
+            for (names, relation, reverse) in relations {
+                for name in names.iter().flatten() {
+                    let (Some(page), Some(target)) = (pages.get(&asset.name), pages.get(name))
+                    else {
+                        continue;
+                    };
+                    if name == &asset.name {
+                        continue;
+                    }
+                    related
+                        .entry(asset.name.clone())
+                        .or_default()
+                        .push(target.related(relation));
+                    related
+                        .entry(name.clone())
+                        .or_default()
+                        .push(page.related(reverse));
                 }
+            }
+        }
+        self.set_related_from(&related);
+    }
 
-                // In this case we found an official bevy crate but we couldn't get a version from it
-                dependency = dependencies.next();
-                bevy_crate = bevy_crates.next();
+    fn set_related_from(&mut self, related: &HashMap<String, Vec<RelatedAsset>>) {
+        for content in &mut self.content {
+            match content {
+                AssetNode::Section(section) => section.set_related_from(related),
+                AssetNode::Asset(asset) => {
+                    asset.related.clear();
+                    for related in related.get(&asset.name).into_iter().flatten() {
+                        if !asset.related.contains(related) {
+                            asset.related.push(related.clone());
+                        }
+                    }
+                }
             }
-            Ordering::Greater => bevy_crate = bevy_crates.next(),
         }
     }
 
-    None
-}
+    /// Adds the sections and assets of `other`, the root section of the assets scanned from
+    /// the source named `source`, to the sections of the same name, and sets their
+    /// [`Asset::source`], see [`sources`]. The slugs of every asset and section are assigned
+    /// again.
+    pub fn merge_source(&mut self, source: &str, mut other: Section) {
+        other.set_source(source);
+        self.merge(other);
+        self.assign_slugs();
+    }
 
-/// Gets the bevy version from the `Cargo.toml` bevy dependency provided.
-///
-/// Returns the version number if available.
-/// If is is a git dependency, return either "main" or "git" for anything that isn't "main".
-fn get_bevy_manifest_dependency_version(dep: &cargo_toml::Dependency) -> Option<String> {
-    match dep {
-        cargo_toml::Dependency::Simple(version) => Some(version.to_string()),
-        cargo_toml::Dependency::Detailed(detail) => {
-            if let Some(version) = &detail.version {
-                Some(version.to_string())
-            } else if detail.git.is_some() {
-                if detail.branch == Some(String::from("main")) {
-                    Some(String::from("main"))
-                } else {
-                    Some(String::from("git"))
+    fn set_source(&mut self, source: &str) {
+        for content in &mut self.content {
+            match content {
+                AssetNode::Section(section) => section.set_source(source),
+                AssetNode::Asset(asset) => asset.source = Some(source.to_string()),
+            }
+        }
+    }
+
+    /// Adds the content of `other`, merging its sections with the sections of the same name.
+    /// The configuration of the sections of `self`, from their `_category.toml`, is kept.
+    fn merge(&mut self, other: Section) {
+        for content in other.content {
+            match content {
+                AssetNode::Section(section) => {
+                    let existing = self.content.iter_mut().find_map(|content| match content {
+                        AssetNode::Section(existing) if existing.name == section.name => {
+                            Some(existing)
+                        }
+                        _ => None,
+                    });
+                    match existing {
+                        Some(existing) => existing.merge(section),
+                        None => self.content.push(AssetNode::Section(section)),
+                    }
                 }
-            } else {
-                None
+                AssetNode::Asset(asset) => self.content.push(AssetNode::Asset(asset)),
             }
         }
-        cargo_toml::Dependency::Inherited(_) => None,
     }
-}
 
-/// Downloads the crates.io database dump and open a connection to the db.
-pub fn prepare_crates_db() -> anyhow::Result<CratesIoDb> {
-    let cache_dir = {
-        let mut current_dir = std::env::current_dir()?;
-        current_dir.push("data");
-        current_dir
-    };
+    /// The sub-sections directly in this section, in the order they are written.
+    pub fn sorted_subsections(&self) -> Vec<&Section> {
+        let mut sections: Vec<_> = self
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                AssetNode::Section(section) => Some(section),
+                AssetNode::Asset(_) => None,
+            })
+            .collect();
+        sections.sort_by(|section1, section2| {
+            compare_orders(section1.order, section2.order)
+                .then_with(|| compare_names(&section1.name, &section2.name))
+                .then_with(|| section1.slug.cmp(&section2.slug))
+        });
+        sections
+    }
 
-    if cache_dir.exists() {
-        println!("Using crates.io data dump cache from: {:?}", cache_dir);
-    } else {
-        println!("Downloading crates.io data dump");
+    /// The assets directly in this section, sorted following [`Section::sort_by`].
+    pub fn sorted_assets(&self) -> Vec<&Asset> {
+        let mut assets: Vec<_> = self
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                AssetNode::Asset(asset) => Some(asset),
+                AssetNode::Section(_) => None,
+            })
+            .collect();
+        let by_name = |asset1: &&Asset, asset2: &&Asset| {
+            compare_names(&asset1.name, &asset2.name).then_with(|| asset1.slug.cmp(&asset2.slug))
+        };
+
+        match self.sort_by {
+            SortBy::Order => {
+                assets.sort_by(|asset1, asset2| {
+                    compare_orders(asset1.order, asset2.order).then_with(|| by_name(asset1, asset2))
+                });
+                assets
+            }
+            SortBy::Name => {
+                assets.sort_by(by_name);
+                assets
+            }
+            SortBy::LastUpdated => {
+                assets.sort_by(|asset1, asset2| {
+                    // Assets without a date come last
+                    (asset2.updated.is_some(), &asset2.updated)
+                        .cmp(&(asset1.updated.is_some(), &asset1.updated))
+                        .then_with(|| by_name(asset1, asset2))
+                });
+                assets
+            }
+        }
     }
 
-    Ok(CratesIODumpLoader::default()
-        .tables(&["crates", "dependencies", "versions"])
-        .preload(true)
-        .update()?
-        .open_db()?)
+    /// Every asset of this section and of its subsections.
+    pub fn assets(&self) -> Vec<&Asset> {
+        self.content
+            .iter()
+            .flat_map(|content| match content {
+                AssetNode::Section(section) => section.assets(),
+                AssetNode::Asset(asset) => vec![asset],
+            })
+            .collect()
+    }
 }
 
-/// Gets metadata of a crate from the crates.io database dump.
-///
-/// If the crate is not found, retries with `-` instead of `_`.
-fn get_metadata_from_crates_db(
-    crate_name: &str,
-    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
-) -> anyhow::Result<(Option<String>, Option<String>)> {
-    if let Ok(metadata) =
-        get_metadata_from_crates_db_by_name(crate_name, get_metadata_from_cratesio_statement)
-    {
-        Ok(metadata)
-    } else if let Ok(metadata) = get_metadata_from_crates_db_by_name(
-        &crate_name.replace('_', "-"),
-        get_metadata_from_cratesio_statement,
-    ) {
-        Ok(metadata)
-    } else {
-        bail!("Failed to get data from crates.io db for {crate_name}")
-    }
+/// Compares the `order` of sections or assets, those without one coming last.
+fn compare_orders(order1: Option<usize>, order2: Option<usize>) -> Ordering {
+    (order1.is_none(), order1).cmp(&(order2.is_none(), order2))
 }
 
-/// Gets metadata of a crate from the crates.io database dump using the exact crate
-/// name provided.
-fn get_metadata_from_crates_db_by_name(
-    crate_name: &str,
-    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
-) -> anyhow::Result<(Option<String>, Option<String>)> {
-    if let Ok((license, version)) =
-        get_metadata_from_cratesio(crate_name, get_metadata_from_cratesio_statement)
-    {
-        let license = if !license.is_empty() {
-            Some(license)
-        } else {
-            None
-        };
+/// Compares names ignoring case, which doesn't depend on the locale, then exactly so names
+/// differing only by case always come in the same order.
+fn compare_names(name1: &str, name2: &str) -> Ordering {
+    name1
+        .to_lowercase()
+        .cmp(&name2.to_lowercase())
+        .then_with(|| name1.cmp(name2))
+}
 
-        Ok((license, version))
-    } else {
-        bail!("Not found in crates.io db: {crate_name}")
+// Most nodes are assets, so boxing them would only add allocations
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetNode {
+    Section(Section),
+    Asset(#[serde(with = "snapshot::asset")] Asset),
+}
+impl AssetNode {
+    pub fn name(&self) -> String {
+        match self {
+            AssetNode::Section(content) => content.name.clone(),
+            AssetNode::Asset(content) => content.name.clone(),
+        }
+    }
+    pub fn order(&self) -> usize {
+        match self {
+            AssetNode::Section(content) => content.order.unwrap_or(99999),
+            AssetNode::Asset(content) => content.order.unwrap_or(99999),
+        }
     }
 }
 
-/// Gets at list of the official bevy crates from the crates.io database dump,
-/// in lexicographic order.
-fn get_official_bevy_crates_from_crates_io_db(
-    db: &CratesIoDb,
-) -> anyhow::Result<(Vec<String>, Vec<String>)> {
-    if let Ok(mut bevy_crates) = get_bevy_crates(db) {
-        bevy_crates.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
-        Ok(bevy_crates.into_iter().unzip())
-    } else {
-        bail!("Problem fetching official bevy crates from crates.io")
+#[derive(Default)]
+/// Where to find metadata (bevy version and license) for assets.
+pub struct MetadataSource<'a> {
+    /// Connection to the crates.io database sqlite dump.
+    pub crates_io_db: Option<&'a CratesIoDb>,
+    /// Connection to Github API, usually a [`GithubClient`].
+    pub github_client: Option<&'a dyn MetadataClient>,
+    /// Connection to Gitlab API, usually a [`GitlabClient`].
+    pub gitlab_client: Option<&'a dyn MetadataClient>,
+    /// Official bevy crates names from crates.io DB dump, in lexigographic order.
+    pub bevy_crates_names: Option<Vec<String>>,
+    /// Prepared statement to retrieve metadata from crates.io.
+    ///
+    /// Initialized with [`get_metadata_from_cratesio_statement`] at the beginning
+    /// of the algorithm, used by [`get_metadata_from_cratesio`] for each asset.
+    pub get_metadata_from_cratesio_statement: Option<rusqlite::Statement<'a>>,
+    /// Reports the progress of the metadata gathering, per section.
+    pub progress: Option<&'a Progress>,
+    /// How many assets can be fetched from Github and Gitlab at the same time.
+    pub concurrency: ConcurrencyConfig,
+    /// Only gather metadata for assets read from these files, given as canonical paths.
+    /// Metadata is gathered for every asset when `None`.
+    pub only_files: Option<&'a HashSet<PathBuf>>,
+    /// Whether errors stop the run, or are collected in `failures`.
+    pub error_mode: ErrorMode,
+    /// Collects the errors with [`ErrorMode::KeepGoing`], and the metadata that can't be
+    /// fetched with [`ErrorMode::Lenient`], as [tolerated](Failure::tolerated) failures.
+    pub failures: Option<&'a Failures>,
+}
+
+impl MetadataSource<'_> {
+    /// Finds the official bevy crates and prepares the statement getting metadata from the
+    /// crates.io database dump, if there is one.
+    fn prepare(&mut self) -> anyhow::Result<()> {
+        if let Some(db) = self.crates_io_db {
+            let bevy_crates_ids = if let Ok((bevy_crates_names, bevy_crates_ids)) =
+                get_official_bevy_crates_from_crates_io_db(db)
+            {
+                self.bevy_crates_names = Some(bevy_crates_names);
+                Some(bevy_crates_ids)
+            } else {
+                None
+            };
+            self.get_metadata_from_cratesio_statement =
+                Some(get_metadata_from_cratesio_statement(db, bevy_crates_ids)?);
+        }
+        Ok(())
     }
 }
 
-// Get official bevy crates name and ids from the crates.io database dump.
-#[allow(clippy::let_and_return)]
-fn get_bevy_crates(db: &CratesIoDb) -> Result<Vec<(String, String)>, rusqlite::Error> {
-    let mut bevy_crates_statement = db.prepare(
-        "\
-            SELECT name, id \
-            FROM crates \
-            WHERE homepage = ? \
-                AND repository = ?\
-        ",
-    )?;
+/// Handles the errors of [`parse_assets`] following its [`ErrorMode`].
+struct ErrorSink<'a> {
+    mode: ErrorMode,
+    failures: Option<&'a Failures>,
+    /// The first metadata error with [`ErrorMode::FailFast`], stopping the run.
+    stop: Mutex<Option<anyhow::Error>>,
+}
 
-    // Required let and return due to bevy_crates_statement not living long enough.
-    let bevy_crates = bevy_crates_statement
-        .query_and_then(
-            [
-                "https://bevyengine.org",
-                "https://github.com/bevyengine/bevy",
-            ],
-            |r| -> Result<(String, String), rusqlite::Error> {
-                Ok((r.get_unwrap::<_, String>(0), r.get_unwrap::<_, String>(1)))
-            },
-        )?
-        .collect();
+impl<'a> ErrorSink<'a> {
+    fn new(mode: ErrorMode, failures: Option<&'a Failures>) -> Self {
+        ErrorSink {
+            mode,
+            failures,
+            stop: Mutex::new(None),
+        }
+    }
 
-    bevy_crates
-}
+    /// Returns the error of a file failing to parse, unless keeping going.
+    fn parse_failed(&self, file: &Path, err: anyhow::Error) -> anyhow::Result<()> {
+        if self.mode != ErrorMode::KeepGoing {
+            return Err(err);
+        }
+        tracing::error!(file = %file.display(), "Failed to parse: {err:#}");
+        if let Some(failures) = self.failures {
+            failures.push(Failure::new(Some(file.to_path_buf()), None, &err));
+        }
+        Ok(())
+    }
 
-/// Get a prepared statement to get license and version for a crate from the
-/// crates.io database dump.
-///
-/// To be used later by [`get_metadata_from_cratesio`].
-pub fn get_metadata_from_cratesio_statement(
-    db: &CratesIoDb,
-    bevy_crates_ids: Option<Vec<String>>,
-) -> Result<rusqlite::Statement<'_>, rusqlite::Error> {
-    let bevy_crates_ids = bevy_crates_ids.unwrap_or_default();
+    /// Records that the metadata of `asset` couldn't be fetched.
+    fn metadata_failed(&self, asset: &Asset, err: &anyhow::Error) {
+        let failure = || Failure {
+            link: Some(asset.link.to_string()),
+            ..Failure::new(asset.original_path.clone(), Some(asset.name.clone()), err)
+        };
+        match self.mode {
+            ErrorMode::Lenient => {
+                if let Some(failures) = self.failures {
+                    failures.push(Failure {
+                        tolerated: true,
+                        ..failure()
+                    });
+                }
+            }
+            ErrorMode::FailFast => {
+                let mut stop = self.stop.lock().unwrap();
+                if stop.is_none() {
+                    *stop = Some(anyhow::anyhow!(
+                        "Failed to get the metadata of {}: {err:#}",
+                        asset.name
+                    ));
+                }
+            }
+            ErrorMode::KeepGoing => {
+                if let Some(failures) = self.failures {
+                    failures.push(failure());
+                }
+            }
+        }
+    }
 
-    db.prepare(&format!(
-        "\
-        SELECT last_version.license, dep.req \
-        FROM ( \
-            SELECT version_id, license, major, \
-                CAST(SUBSTR(minor_and_patch,0,second_point) AS INTEGER) minor, \
-                SUBSTR(minor_and_patch,second_point+1) patch \
-            FROM ( \
-                SELECT version_id, license, major, minor_and_patch, \
-                    INSTR(minor_and_patch, '.') second_point \
-                FROM ( \
-                    SELECT version_id, license, \
-                        CAST(SUBSTR(num,0,first_point) AS INTEGER) major, \
-                        SUBSTR(num,first_point+1) minor_and_patch \
-                    FROM ( \
-                        SELECT v.id version_id, v.license license, v.num num, \
-                            INSTR(v.num, '.') first_point \
-                        FROM crates c \
-                            INNER JOIN versions v ON c.id = v.crate_id \
-                        WHERE c.name = ? \
-                    ) \
-                ) \
-            ) \
-            ORDER BY major DESC, minor DESC, patch DESC \
-            LIMIT 1 \
-        ) last_version \
-            LEFT JOIN dependencies dep ON \
-            ( \
-                last_version.version_id = dep.version_id AND \
-                dep.crate_id IN ({}) \
-            ) \
-        ORDER BY dep.kind \
-        LIMIT 1\
-        ",
-        bevy_crates_ids.join(",")
-    ))
+    fn stopped(&self) -> bool {
+        self.stop.lock().unwrap().is_some()
+    }
+
+    /// The error stopping the run, if any.
+    fn finish(self) -> anyhow::Result<()> {
+        match self.stop.into_inner().unwrap() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
 }
 
-/// Get license and bevy version for a crate from crates.io,
-/// using the prepared statement provided by [`get_metadata_from_cratesio_statement`].
-pub fn get_metadata_from_cratesio(
-    crate_name: &str,
-    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
-) -> Result<(String, Option<String>), rusqlite::Error> {
-    get_metadata_from_cratesio_statement.query_row(
-        [crate_name],
-        |r| -> Result<(String, Option<String>), rusqlite::Error> {
-            Ok((
-                r.get_unwrap::<_, String>(0),
-                r.get_unwrap::<_, Option<String>>(1),
-            ))
+/// Restricts which sections and assets are parsed and enriched, to debug a few assets
+/// without running the whole pipeline. Sections and assets are matched by name or slug.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetFilter {
+    /// Only parse the assets of these sections, and of their sub-sections. Every section is
+    /// parsed when empty.
+    pub sections: Vec<String>,
+    /// Only keep these assets. Every asset is kept when empty.
+    pub assets: Vec<String>,
+}
+
+impl AssetFilter {
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty() && self.assets.is_empty()
+    }
+
+    /// Whether the section named `name` is one of [`AssetFilter::sections`].
+    pub fn matches_section(&self, name: &str) -> bool {
+        self.sections
+            .iter()
+            .any(|section| matches_name(section, name))
+    }
+
+    fn matches_asset(&self, asset: &Asset) -> bool {
+        self.assets.is_empty()
+            || self
+                .assets
+                .iter()
+                .any(|name| matches_name(name, &asset.name))
+    }
+}
+
+/// Whether `filter` is the name, ignoring case, or the slug of `name`.
+fn matches_name(filter: &str, name: &str) -> bool {
+    filter.eq_ignore_ascii_case(name) || filter == slugify(name)
+}
+
+/// Entry point the algorithm to find [`Asset`] files inside [`Section`] folders,
+/// parse asset files, and gather metadata information about assets from various external sources.
+///
+/// This is [`scan_assets`] followed by [`enrich_assets`]. Only the sections and assets
+/// matching `filter` are kept, and links are followed.
+///
+/// Errors are handled following [`MetadataSource::error_mode`].
+pub fn parse_assets(
+    asset_dir: impl AsRef<Path>,
+    filter: &AssetFilter,
+    metadata_source: MetadataSource,
+) -> anyhow::Result<Section> {
+    let mut asset_root_section = scan_assets(
+        asset_dir,
+        filter,
+        SymlinkPolicy::Follow,
+        metadata_source.error_mode,
+        metadata_source.failures,
+    )?;
+    enrich_assets(&mut asset_root_section, metadata_source)?;
+    Ok(asset_root_section)
+}
+
+/// Finds the [`Asset`] files inside [`Section`] folders and parses them, without their
+/// metadata, and initialises the root [`Section`]. Only the sections and assets matching
+/// `filter` are kept.
+///
+/// Links are handled following `symlinks`. Files failing to parse, and sections or asset
+/// files whose names aren't valid UTF-8, stop the scan, unless `error_mode` is
+/// [`ErrorMode::KeepGoing`], where they are collected in `failures`.
+pub fn scan_assets(
+    asset_dir: impl AsRef<Path>,
+    filter: &AssetFilter,
+    symlinks: SymlinkPolicy,
+    error_mode: ErrorMode,
+    failures: Option<&Failures>,
+) -> anyhow::Result<Section> {
+    let mut asset_root_section = Section {
+        name: "Assets".to_string(),
+        content: vec![],
+        template: Some("assets.html".to_string()),
+        header: Some("Assets".to_string()),
+        description: None,
+        order: None,
+        sort_order_reversed: false,
+        sort_by: SortBy::default(),
+        paginate_by: None,
+        required_fields: vec![],
+        extra: Extra::default(),
+        slug: "assets".to_string(),
+    };
+
+    let errors = ErrorSink::new(error_mode, failures);
+    let asset_dir = asset_dir.as_ref();
+    let walker = AssetWalker::new(asset_dir)?.with_symlinks(symlinks);
+    visit_dirs(
+        asset_dir.to_path_buf(),
+        &mut asset_root_section,
+        &walker,
+        filter,
+        filter.sections.is_empty(),
+        &errors,
+    )?;
+    asset_root_section.assign_slugs();
+    Ok(asset_root_section)
+}
+
+/// Gathers the metadata of the assets of `section`, read with [`scan_assets`] or from a
+/// [`snapshot::Snapshot`], from the sources of `metadata_source`.
+///
+/// This initialises [`MetadataSource`] with crates.io's database dump connection and
+/// information about official bevy crates. Errors are handled following
+/// [`MetadataSource::error_mode`].
+pub fn enrich_assets(
+    section: &mut Section,
+    mut metadata_source: MetadataSource,
+) -> anyhow::Result<()> {
+    metadata_source.prepare()?;
+
+    let errors = ErrorSink::new(metadata_source.error_mode, metadata_source.failures);
+    get_extra_metadata_for_section(section, &mut metadata_source, &errors);
+    errors.finish()
+}
+
+/// Gets the metadata of the asset at `link` like [`parse_assets`] does, to fill the fields
+/// of a new asset. Returns `None` when no source of `metadata_source` can give it.
+pub fn get_metadata(
+    link: &str,
+    mut metadata_source: MetadataSource,
+) -> anyhow::Result<Option<ExtraMetadata>> {
+    metadata_source.prepare()?;
+    let bevy_crates = &metadata_source.bevy_crates_names;
+    let metadata = match MetadataLocation::from_link(link)? {
+        MetadataLocation::CratesIo { crate_name } => metadata_source
+            .get_metadata_from_cratesio_statement
+            .as_mut()
+            .map(|statement| get_metadata_from_crates_db(&crate_name, statement))
+            .transpose(),
+        MetadataLocation::Github {
+            username,
+            repository_name,
+        } => metadata_source
+            .github_client
+            .map(|client| client.get_metadata(&username, &repository_name, bevy_crates))
+            .transpose(),
+        MetadataLocation::Gitlab {
+            namespace,
+            repository_name,
+        } => metadata_source
+            .gitlab_client
+            .map(|client| client.get_metadata(&namespace, &repository_name, bevy_crates))
+            .transpose(),
+        MetadataLocation::None => Ok(None),
+    };
+    Ok(metadata?)
+}
+
+/// Recursive traversal of directories inside the cloned "Bevy Assets" project,
+/// each directory is a [`Section`], configured inside the `_category.toml` file,
+/// each other file with a `.toml` extension is an [`Asset`]. Hidden files, editor artifacts
+/// and the paths of the [`walk::IGNORE_FILE`] are skipped, see [`walk`].
+///
+/// Asset files are only parsed in `selected` sections, or sections matching `filter`, and
+/// sections without assets matching `filter` are left out.
+fn visit_dirs(
+    dir: PathBuf,
+    section: &mut Section,
+    walker: &AssetWalker,
+    filter: &AssetFilter,
+    selected: bool,
+    errors: &ErrorSink,
+) -> anyhow::Result<()> {
+    for entry in walker.read_dir(&dir)? {
+        let path = match entry {
+            Entry::Dir(path) => {
+                let Some(folder) = path.file_name().unwrap_or_default().to_str() else {
+                    errors.parse_failed(&path, anyhow!("The name of the section isn't UTF-8"))?;
+                    continue;
+                };
+                let _span = tracing::debug_span!("section", section = %folder).entered();
+                let category_path = path.join("_category.toml");
+                let category = if category_path.exists() {
+                    match schema::parse_category_file(&category_path) {
+                        Ok(category) => category,
+                        Err(err) => {
+                            errors.parse_failed(&category_path, err.into())?;
+                            CategoryConfig::default()
+                        }
+                    }
+                } else {
+                    CategoryConfig::default()
+                };
+                let mut new_section = Section {
+                    name: folder.to_string(),
+                    content: vec![],
+                    template: category.template,
+                    header: category.header,
+                    description: category.description,
+                    order: category.order,
+                    sort_order_reversed: category.sort_order_reversed,
+                    sort_by: category.sort_by,
+                    paginate_by: category.paginate_by,
+                    required_fields: category.required_fields,
+                    extra: category.extra,
+                    slug: String::new(),
+                };
+                let selected = selected || filter.matches_section(&new_section.name);
+                visit_dirs(
+                    path.clone(),
+                    &mut new_section,
+                    walker,
+                    filter,
+                    selected,
+                    errors,
+                )?;
+                if filter.is_empty() || !new_section.content.is_empty() {
+                    section.content.push(AssetNode::Section(new_section));
+                }
+                continue;
+            }
+            Entry::File(path) if walk::is_asset_file(&path) && selected => {
+                if path.file_name().unwrap_or_default().to_str().is_none() {
+                    errors
+                        .parse_failed(&path, anyhow!("The name of the asset file isn't UTF-8"))?;
+                    continue;
+                }
+                path
+            }
+            Entry::File(_) => continue,
+            Entry::Unreadable(path, source) => {
+                let err = GenerateAssetsError::Io {
+                    path: path.clone(),
+                    source,
+                };
+                errors.parse_failed(&path, err.into())?;
+                continue;
+            }
+        };
+
+        tracing::trace!(file = %path.display(), "Parsing asset");
+        let asset = match schema::parse_asset_file(&path) {
+            Ok(asset) => asset,
+            Err(err) => {
+                errors.parse_failed(&path, err.into())?;
+                continue;
+            }
+        };
+        if filter.matches_asset(&asset) {
+            section.content.push(AssetNode::Asset(asset));
+        }
+    }
+
+    Ok(())
+}
+
+/// The external source an asset's metadata can be found in, based on its link.
+enum MetadataLocation {
+    CratesIo {
+        crate_name: String,
+    },
+    Github {
+        username: String,
+        repository_name: String,
+    },
+    Gitlab {
+        namespace: String,
+        repository_name: String,
+    },
+    /// The link has no host, there is nothing to look for.
+    None,
+}
+
+impl MetadataLocation {
+    /// Where the metadata of `asset` is found: in the crates.io database dump for its
+    /// [`crate_name`](Asset::crate_name) if it is set and the dump is loaded, otherwise from
+    /// its [`metadata_link`](Asset::metadata_link).
+    fn of_asset(asset: &Asset, metadata_source: &MetadataSource) -> anyhow::Result<Self> {
+        match &asset.crate_name {
+            Some(crate_name)
+                if metadata_source
+                    .get_metadata_from_cratesio_statement
+                    .is_some() =>
+            {
+                Ok(MetadataLocation::CratesIo {
+                    crate_name: crate_name.clone(),
+                })
+            }
+            _ => MetadataLocation::from_link(asset.metadata_link()),
+        }
+    }
+
+    fn from_link(link: &str) -> anyhow::Result<Self> {
+        let url = url::Url::parse(link)?;
+        Ok(match url.host_str() {
+            // The metadata is of the newest version, even if the link points to an older one
+            Some("crates.io" | "www.crates.io") => {
+                let locator: CratesIoLocator = link
+                    .parse()
+                    .with_context(|| format!("{link} doesn't link to a crate"))?;
+                MetadataLocation::CratesIo {
+                    crate_name: locator.name,
+                }
+            }
+            Some("github.com" | "www.github.com") => {
+                let locator: GithubRepoLocator = link
+                    .parse()
+                    .with_context(|| format!("{link} doesn't link to a repository"))?;
+                MetadataLocation::Github {
+                    username: locator.owner,
+                    repository_name: locator.repository,
+                }
+            }
+            Some("gitlab.com" | "www.gitlab.com") => {
+                let locator: GitlabProjectLocator = link
+                    .parse()
+                    .with_context(|| format!("{link} doesn't link to a project"))?;
+                MetadataLocation::Gitlab {
+                    namespace: locator.namespace,
+                    repository_name: locator.project,
+                }
+            }
+            None => MetadataLocation::None,
+            _ => bail!("Unknown host: {}", link),
+        })
+    }
+}
+
+/// Tries to get bevy supported version and license information for every asset of a [`Section`].
+///
+/// Assets found in the crates.io database dump are handled first, one at a time, then the
+/// remaining ones are fetched from Github and Gitlab in parallel, following
+/// [`MetadataSource::concurrency`].
+/// With [`ErrorMode::FailFast`], no more metadata is fetched after the first error.
+fn get_extra_metadata_for_section(
+    section: &mut Section,
+    metadata_source: &mut MetadataSource,
+    errors: &ErrorSink,
+) {
+    let mut section_progresses = vec![];
+    let mut assets = vec![];
+    collect_assets(
+        section,
+        metadata_source.progress,
+        metadata_source.only_files,
+        &mut section_progresses,
+        &mut assets,
+    );
+
+    let mut remote_assets = vec![];
+    for (asset, progress_index, span) in assets {
+        if errors.stopped() {
+            break;
+        }
+        let entered = span.enter();
+        let section_progress = progress_index.map(|index| &section_progresses[index]);
+        if asset.skip_metadata {
+            report_extra_metadata(section_progress, &asset.name, Ok(MetadataOutcome::Skipped));
+            continue;
+        }
+        let location = match MetadataLocation::of_asset(asset, metadata_source) {
+            Ok(location) => location,
+            Err(err) => {
+                report_extra_metadata(section_progress, &asset.name, Err(err));
+                continue;
+            }
+        };
+
+        let outcome = match (
+            location,
+            &mut metadata_source.get_metadata_from_cratesio_statement,
+        ) {
+            (MetadataLocation::CratesIo { crate_name }, Some(statement)) => {
+                get_metadata_from_crates_db(&crate_name, statement)
+                    .map(|metadata| {
+                        asset.set_metadata(metadata);
+                        MetadataOutcome::Cached
+                    })
+                    .map_err(Into::into)
+            }
+            (location @ (MetadataLocation::Github { .. } | MetadataLocation::Gitlab { .. }), _) => {
+                drop(entered);
+                remote_assets.push((asset, location, section_progress, span));
+                continue;
+            }
+            _ => Ok(MetadataOutcome::Skipped),
+        };
+        if let Err(err) = &outcome {
+            errors.metadata_failed(asset, err);
+        }
+        report_extra_metadata(section_progress, &asset.name, outcome);
+    }
+
+    let github_semaphore = Semaphore::new(metadata_source.concurrency.github_jobs);
+    let gitlab_semaphore = Semaphore::new(metadata_source.concurrency.gitlab_jobs);
+    let github_client = metadata_source.github_client;
+    let gitlab_client = metadata_source.gitlab_client;
+    let bevy_crates_names = &metadata_source.bevy_crates_names;
+
+    for_each_parallel(
+        remote_assets,
+        metadata_source.concurrency.jobs,
+        |(asset, location, section_progress, span)| {
+            if errors.stopped() {
+                return;
+            }
+            let _entered = span.enter();
+            let metadata = match (location, github_client, gitlab_client) {
+                (
+                    MetadataLocation::Github {
+                        username,
+                        repository_name,
+                    },
+                    Some(client),
+                    _,
+                ) => {
+                    let _permit = github_semaphore.acquire();
+                    Some(client.get_metadata(&username, &repository_name, bevy_crates_names))
+                }
+                (
+                    MetadataLocation::Gitlab {
+                        namespace,
+                        repository_name,
+                    },
+                    _,
+                    Some(client),
+                ) => {
+                    let _permit = gitlab_semaphore.acquire();
+                    Some(client.get_metadata(&namespace, &repository_name, bevy_crates_names))
+                }
+                _ => None,
+            };
+
+            let outcome = match metadata {
+                Some(Ok(metadata)) => {
+                    asset.set_metadata(metadata);
+                    Ok(MetadataOutcome::Fetched)
+                }
+                Some(Err(err)) => {
+                    asset.repository_gone = err.repository_gone();
+                    Err(err.into())
+                }
+                None => Ok(MetadataOutcome::Skipped),
+            };
+            if let Err(err) = &outcome {
+                errors.metadata_failed(asset, err);
+            }
+            report_extra_metadata(section_progress, &asset.name, outcome);
         },
-    )
+    );
+
+    for section_progress in &section_progresses {
+        section_progress.finish();
+    }
 }
 
-#[cfg(test)]
-mod tests {
+/// Collects every asset of a [`Section`] and its sub-sections read from `only_files`, along
+/// with the index of the [`SectionProgress`] of the section containing it, and a span to log
+/// the fetching of its metadata in.
+///
+/// The progress of each section is started here rather than while walking the asset folder,
+/// since the metadata of every section is gathered once the whole folder has been walked.
+fn collect_assets<'a>(
+    section: &'a mut Section,
+    progress: Option<&Progress>,
+    only_files: Option<&HashSet<PathBuf>>,
+    section_progresses: &mut Vec<SectionProgress>,
+    assets: &mut Vec<(&'a mut Asset, Option<usize>, tracing::Span)>,
+) {
+    let included = |asset: &Asset| {
+        only_files.is_none_or(|files| {
+            asset
+                .original_path
+                .as_ref()
+                .and_then(|path| path.canonicalize().ok())
+                .is_some_and(|path| files.contains(&path))
+        })
+    };
+    let asset_count = section
+        .content
+        .iter()
+        .filter(|content| matches!(content, AssetNode::Asset(asset) if included(asset)))
+        .count();
+    let progress_index = progress.filter(|_| asset_count > 0).map(|progress| {
+        section_progresses.push(progress.section(&section.name, asset_count));
+        section_progresses.len() - 1
+    });
+
+    let section_span = tracing::info_span!("section", section = %section.name);
+    for content in section.content.iter_mut() {
+        match content {
+            AssetNode::Section(section) => {
+                let _entered = section_span.enter();
+                collect_assets(section, progress, only_files, section_progresses, assets);
+            }
+            AssetNode::Asset(asset) if included(asset) => {
+                let span = tracing::info_span!(
+                    parent: &section_span,
+                    "asset",
+                    asset = %asset.name,
+                    link = %asset.link,
+                );
+                assets.push((asset, progress_index, span));
+            }
+            AssetNode::Asset(_) => {}
+        }
+    }
+}
+
+/// Reports the outcome of getting extra metadata for the asset named `asset`, in the span
+/// of the asset.
+fn report_extra_metadata(
+    section_progress: Option<&SectionProgress>,
+    asset: &str,
+    outcome: anyhow::Result<MetadataOutcome>,
+) {
+    match &outcome {
+        // The status line of the asset already shows the error
+        Err(err) if section_progress.is_some_and(SectionProgress::prints_status) => {
+            tracing::debug!("Failed to get metadata: {err:#}");
+        }
+        // We don't want to stop execution here
+        Err(err) => tracing::warn!("Failed to get metadata: {err:#}"),
+        Ok(outcome) => tracing::debug!(?outcome, "Got metadata"),
+    }
+    if let Some(section_progress) = section_progress {
+        section_progress.record(asset, &outcome);
+    }
+}
+
+/// Merge two licenses, get the combination of both of them.
+fn merge_license(license1: Option<String>, license2: Option<String>) -> Option<String> {
+    if license1.is_none() {
+        return license2;
+    }
+    if license2.is_none() {
+        return license1;
+    }
+
+    let license1 = license1.unwrap();
+    let license2 = license2.unwrap();
+    if license1.contains(&license2) {
+        return Some(license1);
+    }
+    if license2.contains(&license1) {
+        return Some(license2);
+    }
+
+    Some(license1 + " " + &license2)
+}
+
+/// Merge two versions, get the newest of the two.
+fn merge_version(
+    version1: Option<BevyVersion>,
+    version2: Option<BevyVersion>,
+) -> Option<BevyVersion> {
+    version1.max(version2)
+}
+
+/// Gets metadata from a Github project.
+///
+/// This algorithm, in order :
+/// - tries to get metadata from the root `Cargo.toml` file,
+/// - if the license is missing, search the license of the project on Github,
+/// - if metadata is missing, search all `Cargo.toml` files, then tries to get metadata
+///   from all of them, until we have the information we need.
+///
+/// Note:
+/// - The search call of the API has a tendency to return 403 errors after a few number
+///   of calls. Assets that are at the "end" might not have correct metadata because of that.
+/// - If the root `Cargo.toml` file can't be fetched, checks whether the repository still
+///   exists, failing with [`GenerateAssetsError::NotFound`] for the repository if it doesn't.
+/// - This algorithm tries to retain the "best" version and merge all licenses found.
+/// - If a licence and version is found, it will stop searching, but the information
+///   about the version and license could have gotten "better" by searching deper.
+/// - Likewise, the project license is never checked if a license is provided in the root
+///   `Cargo.toml` file.
+fn get_metadata_from_github(
+    client: &impl RepositoryClient,
+    username: &str,
+    repository_name: &str,
+    bevy_crates: &Option<Vec<String>>,
+) -> error::Result<ExtraMetadata> {
+    let result = get_metadata_from_github_manifest(
+        client,
+        username,
+        repository_name,
+        bevy_crates,
+        "Cargo.toml",
+    );
+
+    let mut manifest_path = None;
+    let mut metadata = match result {
+        Ok(metadata) => metadata,
+        Err(err) => {
+            if let Err(err) = client.get_repository(username, repository_name) {
+                if err.repository_gone().is_some() {
+                    return Err(err);
+                }
+            }
+            match err {
+                // Repositories with a docs-only root, or keeping their crate in a folder
+                GenerateAssetsError::NotFound { .. } => {
+                    match find_github_manifest(client, username, repository_name, bevy_crates) {
+                        Some((path, metadata)) => {
+                            manifest_path = Some(path);
+                            metadata
+                        }
+                        None => ExtraMetadata::default(),
+                    }
+                }
+                err => {
+                    tracing::warn!(
+                        "Error getting metadata from root cargo file from github: {err:#}"
+                    );
+                    ExtraMetadata::default()
+                }
+            }
+        }
+    };
+
+    if metadata.license.is_none() {
+        metadata.license = client.get_license(username, repository_name).ok();
+    }
+
+    if metadata.license.is_none() || metadata.bevy_version.is_none() {
+        let cargo_files = match client.search_file(username, repository_name, "Cargo.toml") {
+            Ok(cargo_files) => cargo_files,
+            Err(err) => {
+                tracing::warn!("Error fetching cargo files from github: {err:#}");
+                return Ok(metadata);
+            }
+        };
+
+        let mut cargo_files = cargo_files
+            .iter()
+            //Exclude the root Cargo.toml and the one found instead, we already searched in them
+            .filter(|f| f != &"Cargo.toml" && Some(f.as_str()) != manifest_path.as_deref());
+
+        let mut cargo_file = cargo_files.next();
+        while (metadata.license.is_none() || metadata.bevy_version.is_none())
+            && cargo_file.is_some()
+        {
+            let cargo_file_path = cargo_file.unwrap();
+
+            let result = get_metadata_from_github_manifest(
+                client,
+                username,
+                repository_name,
+                bevy_crates,
+                cargo_file_path,
+            );
+            match result {
+                Ok(new_metadata) => {
+                    metadata.license = merge_license(metadata.license, new_metadata.license);
+                    metadata.bevy_version =
+                        merge_version(metadata.bevy_version, new_metadata.bevy_version);
+                    for platform in new_metadata.platforms {
+                        if !metadata.platforms.contains(&platform) {
+                            metadata.platforms.push(platform);
+                        }
+                    }
+                    metadata.platforms.sort();
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        file = cargo_file_path,
+                        "Error getting metadata from other cargo file from github: {err:#}"
+                    );
+                    return Ok(metadata);
+                }
+            }
+
+            cargo_file = cargo_files.next();
+        }
+    }
+
+    Ok(metadata)
+}
+
+/// Finds the most likely manifest of a Github repository without a root `Cargo.toml` file:
+/// the shallowest one whose package depends on Bevy, among the first
+/// [`MAX_MANIFESTS_READ`] ones. Returns its path and metadata.
+fn find_github_manifest(
+    client: &impl RepositoryClient,
+    username: &str,
+    repository_name: &str,
+    bevy_crates: &Option<Vec<String>>,
+) -> Option<(String, ExtraMetadata)> {
+    let files = match client.list_files(username, repository_name) {
+        Ok(files) => files,
+        Err(err) => {
+            tracing::warn!("Error listing the files of the repository from github: {err:#}");
+            return None;
+        }
+    };
+    let mut manifests: Vec<_> = files
+        .into_iter()
+        .filter(|path| path.rsplit('/').next() == Some("Cargo.toml"))
+        .collect();
+    manifests.sort_by_key(|path| (path.matches('/').count(), path.clone()));
+
+    manifests
+        .into_iter()
+        .take(MAX_MANIFESTS_READ)
+        .find_map(|path| {
+            let content = client
+                .get_content(username, repository_name, &path)
+                .map_err(|err| tracing::warn!(file = path, "Error reading cargo file: {err:#}"))
+                .ok()?;
+            let manifest = toml::from_str::<cargo_toml::Manifest>(&content).ok()?;
+            manifest.package.as_ref()?;
+            let metadata = get_metadata_from_manifest(&manifest, bevy_crates);
+            metadata.bevy_version.is_some().then_some((path, metadata))
+        })
+}
+
+/// Gets metadata from a `Cargo.toml` file in a Github project.
+fn get_metadata_from_github_manifest(
+    client: &impl RepositoryClient,
+    username: &str,
+    repository_name: &str,
+    bevy_crates: &Option<Vec<String>>,
+    path: &str,
+) -> error::Result<ExtraMetadata> {
+    let content = client.get_content(username, repository_name, path)?;
+
+    let cargo_manifest = toml::from_str::<cargo_toml::Manifest>(&content).map_err(|err| {
+        GenerateAssetsError::parse(format!("{path} of {username}/{repository_name}"), err)
+    })?;
+
+    Ok(get_metadata_from_manifest(&cargo_manifest, bevy_crates))
+}
+
+/// Gets metadata from a Gitlab project.
+///
+/// This algorithm only looks into the root `Cargo.toml` file, and uses the license found by
+/// Gitlab when it doesn't give one.
+fn get_metadata_from_gitlab(
+    client: &GitlabClient,
+    namespace: &str,
+    repository_name: &str,
+    bevy_crates: &Option<Vec<String>>,
+) -> error::Result<ExtraMetadata> {
+    let path = format!("{namespace}/{repository_name}");
+    let project = client.get_project(&path)?;
+
+    let mut metadata = match client.get_content(project.id, &project.default_branch, "Cargo.toml") {
+        Ok(content) => {
+            let cargo_manifest = toml::from_str::<cargo_toml::Manifest>(&content)
+                .map_err(|err| GenerateAssetsError::parse(format!("Cargo.toml of {path}"), err))?;
+            get_metadata_from_manifest(&cargo_manifest, bevy_crates)
+        }
+        Err(err @ GenerateAssetsError::NotFound { .. }) => {
+            tracing::warn!("Error getting metadata from root cargo file from gitlab: {err:#}");
+            ExtraMetadata::default()
+        }
+        Err(err) => return Err(err),
+    };
+
+    if metadata.license.is_none() {
+        metadata.license = project
+            .license
+            .as_ref()
+            .and_then(GitlabLicense::spdx_id)
+            .map(str::to_string);
+    }
+    Ok(metadata)
+}
+
+fn get_metadata_from_manifest(
+    cargo_manifest: &cargo_toml::Manifest,
+    bevy_crates: &Option<Vec<String>>,
+) -> ExtraMetadata {
+    ExtraMetadata {
+        license: get_license(cargo_manifest),
+        bevy_version: get_bevy_version_from_manifest(cargo_manifest, bevy_crates),
+        platforms: platforms::infer_platforms(cargo_manifest.target.keys().map(String::as_str)),
+        latest_version: match &cargo_manifest.package {
+            Some(cargo_toml::Package {
+                version: cargo_toml::Inheritable::Set(version),
+                ..
+            }) => Some(version.clone()),
+            _ => None,
+        },
+    }
+}
+
+/// Gets the license from a `Cargo.toml` file
+/// Tries to emulate crates.io behavior.
+fn get_license(cargo_manifest: &cargo_toml::Manifest) -> Option<String> {
+    // Get the license from the package information
+    if let Some(cargo_toml::Package {
+        license,
+        license_file,
+        ..
+    }) = &cargo_manifest.package
+    {
+        if let Some(cargo_toml::Inheritable::Set(license)) = license {
+            Some(license.clone())
+        } else {
+            license_file.as_ref().map(|_| String::from("non-standard"))
+        }
+    } else {
+        None
+    }
+}
+
+/// Find any bevy dependency and get the corresponding bevy version from a `Cargo.toml` file.
+///
+/// This algorithm checks if a dependency to an official bevy crate is found, in order :
+/// - in the (regular) dependencies,
+/// - in the dev dependencies (used for examples, tests and benchmarks),
+/// - in the workspace dependencies.
+///
+/// It doesn't go deeper if a version is already found.
+/// If no official bevy crate is found, it falls back to the first `bevy*` (regular) dependency
+/// with a version, which is most likely a third party crate following bevy's versioning.
+fn get_bevy_version_from_manifest(
+    cargo_manifest: &cargo_toml::Manifest,
+    bevy_crates: &Option<Vec<String>>,
+) -> Option<BevyVersion> {
+    let search_range = OFFICIAL_BEVY_CRATE_PREFIX_RANGE_START.to_owned()
+        ..OFFICIAL_BEVY_CRATE_PREFIX_RANGE_END.to_owned();
+
+    let dependencies = cargo_manifest.dependencies.range(search_range.clone());
+    let mut bevy_dependency = None;
+    if let Some(bevy_crates) = bevy_crates {
+        let bevy_crates = bevy_crates.iter();
+
+        // Tries to find an official bevy crate from the asset's dependencies.
+        bevy_dependency =
+            search_bevy_in_manifest_dependencies(dependencies.clone(), bevy_crates.clone());
+
+        if bevy_dependency.is_none() {
+            // Tries to find an official bevy crate from the asset's dev dependencies.
+            // An asset can indirectly depend on bevy through another crate,
+            // but would probably depend on bevy directly for its examples,
+            // benchmarks or tests, in its dev dependencies.
+            let dev_dependencies = cargo_manifest.dev_dependencies.range(search_range.clone());
+            bevy_dependency =
+                search_bevy_in_manifest_dependencies(dev_dependencies, bevy_crates.clone());
+
+            if bevy_dependency.is_none() {
+                // Tries to find an official bevy crate from the asset's workspace dependencies.
+                if let Some(ref workspace) = cargo_manifest.workspace {
+                    let workspace_dependencies = workspace.dependencies.range(search_range);
+                    bevy_dependency =
+                        search_bevy_in_manifest_dependencies(workspace_dependencies, bevy_crates);
+                }
+            }
+        }
+    }
+
+    // Falls back to any third party bevy crate from the asset's dependencies.
+    bevy_dependency.or_else(|| {
+        dependencies
+            .filter_map(|(_, dependency)| get_bevy_manifest_dependency_version(dependency))
+            .next()
+    })
+}
+
+/// Search the first official bevy crate found in a collection of `Cargo.toml`
+/// dependencies and return its version.
+///
+/// If it was a bit more generic, this function could be called `find_first_intersect_in_sorted_iterators`.
+/// Both `dependencies` and `bevy_crates` are assumed to be sorted (by key for `dependencies`, they are in this context),
+/// and we find the first element that intersect both of them using that knowledge.
+fn search_bevy_in_manifest_dependencies(
+    mut dependencies: std::collections::btree_map::Range<'_, String, cargo_toml::Dependency>,
+    mut bevy_crates: std::slice::Iter<String>,
+) -> Option<BevyVersion> {
+    let mut dependency = dependencies.next();
+    let mut bevy_crate = bevy_crates.next();
+
+    while dependency.is_some() && bevy_crate.is_some() {
+        let dependency_name = dependency.unwrap().0;
+        let bevy_crate_name = bevy_crate.unwrap();
+
+        match dependency_name.cmp(bevy_crate_name) {
+            Ordering::Less => dependency = dependencies.next(),
+            Ordering::Equal => {
+                let dependency_version =
+                    get_bevy_manifest_dependency_version(dependency.unwrap().1);
+
+                if dependency_version.is_some() {
+                    return dependency_version;
+                }
+
+                // In this case we found an official bevy crate but we couldn't get a version from it
+                dependency = dependencies.next();
+                bevy_crate = bevy_crates.next();
+            }
+            Ordering::Greater => bevy_crate = bevy_crates.next(),
+        }
+    }
+
+    None
+}
+
+/// Gets the bevy version from the `Cargo.toml` bevy dependency provided.
+///
+/// Returns the version of its requirement if available, see [`BevyVersion::from_requirement`].
+/// If is is a git dependency on the `main` branch, return `main`, other branches and revisions
+/// not being a version.
+fn get_bevy_manifest_dependency_version(dep: &cargo_toml::Dependency) -> Option<BevyVersion> {
+    match dep {
+        cargo_toml::Dependency::Simple(version) => BevyVersion::from_requirement(version),
+        cargo_toml::Dependency::Detailed(detail) => {
+            if let Some(version) = &detail.version {
+                BevyVersion::from_requirement(version)
+            } else if detail.git.is_some() && detail.branch.as_deref() == Some(BEVY_MAIN) {
+                Some(BevyVersion::Main)
+            } else {
+                None
+            }
+        }
+        cargo_toml::Dependency::Inherited(_) => None,
+    }
+}
+
+/// Tables of the crates.io database dump extracted by [`prepare_crates_db`], as CSV files.
+pub const CRATES_DUMP_TABLES: &[&str] = &["crates", "dependencies", "versions"];
+
+/// Databases [`prepare_crates_db`] loads the CSV files into, depending on the
+/// [`CratesDumpFilter`].
+const CRATES_DUMP_DATABASES: &[&str] = &["db.sqlite", "db-bevy-related.sqlite"];
+
+/// The crates.io database dump cached in a folder, see [`crates_dump_status`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CratesDumpStatus {
+    /// When the CSV files were extracted from the dump.
+    pub extracted: SystemTime,
+    /// Databases loaded from the CSV files by previous runs.
+    pub databases: Vec<PathBuf>,
+}
+
+/// Checks the crates.io database dump cached in `cache_dir` by [`prepare_crates_db`]: every
+/// CSV file must be there with its header, and the databases loaded from them must pass
+/// the integrity check of sqlite.
+pub fn crates_dump_status(cache_dir: &Path) -> anyhow::Result<CratesDumpStatus> {
+    let mut extracted = None;
+    for table in CRATES_DUMP_TABLES {
+        let path = cache_dir.join(format!("{table}.csv"));
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("Reading {}", path.display()))?;
+        let headers = csv::Reader::from_path(&path)
+            .and_then(|mut reader| reader.headers().map(|headers| headers.len()))
+            .with_context(|| format!("Reading {}", path.display()))?;
+        if headers == 0 {
+            bail!("{} is empty", path.display());
+        }
+        extracted = Some(extracted.map_or(modified, |oldest: SystemTime| oldest.min(modified)));
+    }
+
+    let mut databases = vec![];
+    for database in CRATES_DUMP_DATABASES {
+        let path = cache_dir.join(database);
+        if !path.exists() {
+            continue;
+        }
+        let db = rusqlite::Connection::open(&path)?;
+        // The full database reads the CSV files through virtual tables
+        rusqlite::vtab::csvtab::load_module(&db)?;
+        let check: String = db
+            .query_row("PRAGMA quick_check", [], |row| row.get(0))
+            .with_context(|| format!("Checking {}", path.display()))?;
+        if check != "ok" {
+            bail!("{} is corrupted: {check}", path.display());
+        }
+        databases.push(path);
+    }
+
+    Ok(CratesDumpStatus {
+        extracted: extracted.expect("There is at least one table"),
+        databases,
+    })
+}
+
+/// Which rows of the crates.io database dump are loaded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CratesDumpFilter {
+    /// Load every crate, version and dependency.
+    #[default]
+    All,
+    /// Only load official bevy crates and versions of crates depending on them.
+    ///
+    /// The CSV files are streamed and filtered before being written to the database,
+    /// which uses a fraction of the memory and disk space needed to load everything.
+    /// Crates that don't depend directly on bevy won't have metadata.
+    BevyRelated,
+}
+
+/// Downloads the crates.io database dump to `cache_dir` and open a connection to the db.
+///
+/// When `offline`, the dump downloaded by a previous run is used even if crates.io has a
+/// newer one, and this fails if there is none.
+pub fn prepare_crates_db(
+    filter: CratesDumpFilter,
+    cache_dir: &Path,
+    offline: bool,
+) -> anyhow::Result<CratesIoDb> {
+    if cache_dir.exists() {
+        tracing::info!(
+            "Using crates.io data dump cache from: {}",
+            cache_dir.display()
+        );
+    } else {
+        tracing::info!("Downloading crates.io data dump");
+    }
+
+    let mut loader = CratesIODumpLoader::default();
+    if offline {
+        loader.cache(CacheBuilder::new().offline(true))?;
+    }
+    loader.target_path(cache_dir).tables(CRATES_DUMP_TABLES);
+
+    match filter {
+        CratesDumpFilter::All => Ok(loader.preload(true).update()?.open_db()?),
+        CratesDumpFilter::BevyRelated => open_bevy_related_crates_db(loader.update()?),
+    }
+}
+
+/// Opens a database containing only the bevy related rows of the crates.io database dump,
+/// creating it from the CSV files if it is missing or outdated.
+fn open_bevy_related_crates_db(loader: &CratesIODumpLoader) -> anyhow::Result<CratesIoDb> {
+    let db_path = loader.target_path.join("db-bevy-related.sqlite");
+    let csv_path = |table: &str| loader.target_path.join(format!("{table}.csv"));
+
+    let is_outdated = match (fs::metadata(&db_path), fs::metadata(csv_path("crates"))) {
+        (Ok(db), Ok(csv)) => db.modified()? < csv.modified()?,
+        _ => true,
+    };
+    if !is_outdated {
+        return Ok(rusqlite::Connection::open(&db_path)?);
+    }
+
+    // Built in a temporary file renamed once complete, so an interrupted run doesn't leave a
+    // database newer than the dump with missing tables
+    // The file names are quoted in the SQL reading the CSV files, which can't escape quotes
+    if loader.target_path.to_string_lossy().contains('\'') {
+        bail!(
+            "The crates.io database dump folder {} can't contain a quote",
+            loader.target_path.display()
+        );
+    }
+    let tmp_path = loader.target_path.join("db-bevy-related.sqlite.tmp");
+    let _ = fs::remove_file(&tmp_path);
+    let db = rusqlite::Connection::open(&tmp_path)?;
+    rusqlite::vtab::csvtab::load_module(&db)?;
+    // The virtual tables read the CSV files row by row, only the filtered rows are stored.
+    db.execute_batch(&format!(
+        "\
+        CREATE VIRTUAL TABLE temp.csv_crates USING csv(filename='{crates}', header=yes); \
+        CREATE VIRTUAL TABLE temp.csv_versions USING csv(filename='{versions}', header=yes); \
+        CREATE VIRTUAL TABLE temp.csv_dependencies USING csv(filename='{dependencies}', header=yes); \
+        CREATE TEMP TABLE official_crates AS \
+            SELECT id FROM csv_crates WHERE homepage = '{OFFICIAL_BEVY_HOMEPAGE}' \
+                AND repository = '{OFFICIAL_BEVY_REPOSITORY}'; \
+        CREATE TABLE dependencies AS \
+            SELECT version_id, crate_id, req, kind FROM csv_dependencies \
+            WHERE crate_id IN (SELECT id FROM official_crates); \
+        CREATE TABLE versions AS \
+            SELECT id, crate_id, num, license FROM csv_versions \
+            WHERE id IN (SELECT version_id FROM dependencies); \
+        CREATE TABLE crates AS \
+            SELECT id, name, homepage, repository FROM csv_crates \
+            WHERE id IN (SELECT id FROM official_crates) \
+                OR id IN (SELECT crate_id FROM versions);\
+        ",
+        crates = csv_path("crates").display(),
+        versions = csv_path("versions").display(),
+        dependencies = csv_path("dependencies").display(),
+    ))?;
+    db.close().map_err(|(_, err)| err)?;
+    fs::rename(&tmp_path, &db_path)
+        .with_context(|| format!("Renaming {} to {}", tmp_path.display(), db_path.display()))?;
+
+    Ok(rusqlite::Connection::open(&db_path)?)
+}
+
+/// Gets metadata of a crate from the crates.io database dump.
+///
+/// If the crate is not found, retries with `-` instead of `_`.
+fn get_metadata_from_crates_db(
+    crate_name: &str,
+    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
+) -> error::Result<ExtraMetadata> {
+    if let Some(metadata) =
+        get_metadata_from_crates_db_by_name(crate_name, get_metadata_from_cratesio_statement)
+    {
+        Ok(metadata)
+    } else if let Some(metadata) = get_metadata_from_crates_db_by_name(
+        &crate_name.replace('_', "-"),
+        get_metadata_from_cratesio_statement,
+    ) {
+        Ok(metadata)
+    } else {
+        Err(GenerateAssetsError::NotFound {
+            what: format!("Crate {crate_name} in the crates.io database dump"),
+            status: None,
+            repository: false,
+        })
+    }
+}
+
+/// Gets metadata of a crate from the crates.io database dump using the exact crate
+/// name provided.
+fn get_metadata_from_crates_db_by_name(
+    crate_name: &str,
+    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
+) -> Option<ExtraMetadata> {
+    if let Ok((license, version, latest_version)) =
+        get_metadata_from_cratesio(crate_name, get_metadata_from_cratesio_statement)
+    {
+        let license = if !license.is_empty() {
+            Some(license)
+        } else {
+            None
+        };
+
+        Some(ExtraMetadata {
+            license,
+            bevy_version: version.as_deref().and_then(BevyVersion::from_requirement),
+            platforms: vec![],
+            latest_version: Some(latest_version),
+        })
+    } else {
+        None
+    }
+}
+
+/// Gets at list of the official bevy crates from the crates.io database dump,
+/// in lexicographic order.
+fn get_official_bevy_crates_from_crates_io_db(
+    db: &CratesIoDb,
+) -> anyhow::Result<(Vec<String>, Vec<String>)> {
+    if let Ok(mut bevy_crates) = get_bevy_crates(db) {
+        bevy_crates.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+        Ok(bevy_crates.into_iter().unzip())
+    } else {
+        bail!("Problem fetching official bevy crates from crates.io")
+    }
+}
+
+// Get official bevy crates name and ids from the crates.io database dump.
+#[allow(clippy::let_and_return)]
+fn get_bevy_crates(db: &CratesIoDb) -> Result<Vec<(String, String)>, rusqlite::Error> {
+    let mut bevy_crates_statement = db.prepare(
+        "\
+            SELECT name, id \
+            FROM crates \
+            WHERE homepage = ? \
+                AND repository = ?\
+        ",
+    )?;
+
+    // Required let and return due to bevy_crates_statement not living long enough.
+    let bevy_crates = bevy_crates_statement
+        .query_and_then(
+            [OFFICIAL_BEVY_HOMEPAGE, OFFICIAL_BEVY_REPOSITORY],
+            |r| -> Result<(String, String), rusqlite::Error> {
+                Ok((r.get_unwrap::<_, String>(0), r.get_unwrap::<_, String>(1)))
+            },
+        )?
+        .collect();
+
+    bevy_crates
+}
+
+/// Get a prepared statement to get license and version for a crate from the
+/// crates.io database dump.
+///
+/// To be used later by [`get_metadata_from_cratesio`].
+pub fn get_metadata_from_cratesio_statement(
+    db: &CratesIoDb,
+    bevy_crates_ids: Option<Vec<String>>,
+) -> Result<rusqlite::Statement<'_>, rusqlite::Error> {
+    let bevy_crates_ids = bevy_crates_ids.unwrap_or_default();
+
+    db.prepare(&format!(
+        "\
+        SELECT last_version.license, dep.req, last_version.num \
+        FROM ( \
+            SELECT version_id, license, num, major, \
+                CAST(SUBSTR(minor_and_patch,0,second_point) AS INTEGER) minor, \
+                SUBSTR(minor_and_patch,second_point+1) patch \
+            FROM ( \
+                SELECT version_id, license, num, major, minor_and_patch, \
+                    INSTR(minor_and_patch, '.') second_point \
+                FROM ( \
+                    SELECT version_id, license, num, \
+                        CAST(SUBSTR(num,0,first_point) AS INTEGER) major, \
+                        SUBSTR(num,first_point+1) minor_and_patch \
+                    FROM ( \
+                        SELECT v.id version_id, v.license license, v.num num, \
+                            INSTR(v.num, '.') first_point \
+                        FROM crates c \
+                            INNER JOIN versions v ON c.id = v.crate_id \
+                        WHERE c.name = ? \
+                    ) \
+                ) \
+            ) \
+            ORDER BY major DESC, minor DESC, patch DESC \
+            LIMIT 1 \
+        ) last_version \
+            LEFT JOIN dependencies dep ON \
+            ( \
+                last_version.version_id = dep.version_id AND \
+                dep.crate_id IN ({}) \
+            ) \
+        ORDER BY dep.kind \
+        LIMIT 1\
+        ",
+        bevy_crates_ids.join(",")
+    ))
+}
+
+/// Get license, bevy version and latest version for a crate from crates.io,
+/// using the prepared statement provided by [`get_metadata_from_cratesio_statement`].
+pub fn get_metadata_from_cratesio(
+    crate_name: &str,
+    get_metadata_from_cratesio_statement: &mut rusqlite::Statement,
+) -> Result<(String, Option<String>, String), rusqlite::Error> {
+    get_metadata_from_cratesio_statement.query_row(
+        [crate_name],
+        |r| -> Result<(String, Option<String>, String), rusqlite::Error> {
+            Ok((
+                r.get_unwrap::<_, String>(0),
+                r.get_unwrap::<_, Option<String>>(1),
+                r.get_unwrap::<_, String>(2),
+            ))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    mod asset_slug {
+        use super::super::*;
+
+        fn asset(name: &str, path: &str) -> AssetNode {
+            AssetNode::Asset(Asset {
+                original_path: Some(PathBuf::from(path)),
+                ..Asset::named(name)
+            })
+        }
+
+        fn slugs(section: &Section) -> Vec<(String, String)> {
+            section
+                .content
+                .iter()
+                .map(|content| match content {
+                    AssetNode::Section(section) => (section.name.clone(), section.slug.clone()),
+                    AssetNode::Asset(asset) => (asset.name.clone(), asset.slug.clone()),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn names_differing_by_case_or_punctuation_collide() {
+            assert_eq!(slugify("Bevy Tilemap!"), "bevy_tilemap");
+            assert_eq!(slugify("bevy tilemap"), "bevy_tilemap");
+            assert_eq!(slugify("foo/bar"), "foo-bar");
+        }
+
+        #[test]
+        fn keeps_letters_of_any_script() {
+            assert_eq!(slugify("Äpfel & Birnen"), "äpfel__birnen");
+            assert_eq!(slugify("ベビー"), "ベビー");
+            assert_eq!(slugify("!!!"), "untitled");
+        }
+
+        #[test]
+        fn deduplicates_in_file_order() {
+            let mut section = Section {
+                name: "2D".to_string(),
+                content: vec![
+                    asset("Bevy Tilemap", "c.toml"),
+                    asset("bevy_tilemap-2", "b.toml"),
+                    asset("bevy tilemap", "a.toml"),
+                    asset("Other", "d.toml"),
+                ],
+                ..Default::default()
+            };
+            section.assign_slugs();
+
+            let expected = [
+                ("Bevy Tilemap", "bevy_tilemap-3"),
+                ("bevy_tilemap-2", "bevy_tilemap-2"),
+                ("bevy tilemap", "bevy_tilemap"),
+                ("Other", "other"),
+            ];
+            assert_eq!(
+                slugs(&section),
+                expected.map(|(name, slug)| (name.to_string(), slug.to_string()))
+            );
+        }
+    }
+
+    mod icon {
+        use super::super::*;
+
+        #[test]
+        fn reads_emojis_and_paths() {
+            assert_eq!(Icon::new("🦀"), Icon::Emoji("🦀".to_string()));
+            assert_eq!(Icon::new("👨‍💻"), Icon::Emoji("👨‍💻".to_string()));
+            assert_eq!(Icon::new("icon.png"), Icon::Image("icon.png".to_string()));
+            assert_eq!(Icon::new("🦀.png"), Icon::Image("🦀.png".to_string()));
+        }
+    }
+
+    mod pricing {
+        use super::super::*;
+
+        #[test]
+        fn reads_the_model_and_price() {
+            let asset: Asset = toml::from_str(
+                "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\npricing = { model = \"paid\", price = \"$15\" }\n",
+            )
+            .unwrap();
+            let pricing = asset.pricing.unwrap();
+            assert_eq!(pricing.model, PricingModel::Paid);
+            assert_eq!(pricing.to_string(), "Paid ($15)");
+        }
+    }
+
+    mod set_related {
+        use super::super::*;
+
+        fn asset(name: &str, depends_on: &[&str]) -> AssetNode {
+            AssetNode::Asset(Asset {
+                link: format!("https://github.com/user/{name}").parse().unwrap(),
+                depends_on: Some(depends_on.iter().map(|name| name.to_string()).collect()),
+                slug: name.to_string(),
+                ..Asset::named(name)
+            })
+        }
+
+        fn section(name: &str, content: Vec<AssetNode>) -> Section {
+            Section {
+                name: name.to_string(),
+                content,
+                slug: name.to_lowercase(),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn links_both_assets() {
+            let mut root = section(
+                "Assets",
+                vec![
+                    AssetNode::Section(section("UI", vec![asset("bevy_egui", &[])])),
+                    asset("inspector", &["bevy_egui", "unknown", "inspector"]),
+                ],
+            );
+            root.set_related();
+
+            let assets = root.assets();
+            assert_eq!(
+                assets[0].related,
+                [RelatedAsset {
+                    name: "inspector".to_string(),
+                    url: "/assets/inspector/".to_string(),
+                    link: "https://github.com/user/inspector".to_string(),
+                    relation: Relation::DependencyOf,
+                }]
+            );
+            assert_eq!(
+                assets[1].related,
+                [RelatedAsset {
+                    name: "bevy_egui".to_string(),
+                    url: "/assets/ui/bevy_egui/".to_string(),
+                    link: "https://github.com/user/bevy_egui".to_string(),
+                    relation: Relation::DependsOn,
+                }]
+            );
+        }
+    }
+
+    mod sorted_assets {
+        use super::super::*;
+
+        fn section(sort_by: SortBy, assets: &[(&str, Option<&str>)]) -> Section {
+            Section {
+                name: "Games".to_string(),
+                content: assets
+                    .iter()
+                    .map(|(name, updated)| {
+                        AssetNode::Asset(Asset {
+                            slug: name.to_lowercase(),
+                            updated: updated.map(str::to_string),
+                            ..Asset::named(name)
+                        })
+                    })
+                    .collect(),
+                sort_by,
+                slug: "games".to_string(),
+                ..Default::default()
+            }
+        }
+
+        fn names(section: &Section) -> Vec<&str> {
+            section
+                .sorted_assets()
+                .into_iter()
+                .map(|asset| asset.name.as_str())
+                .collect()
+        }
+
+        #[test]
+        fn by_name_ignoring_case() {
+            let section = section(SortBy::Name, &[("b", None), ("C", None), ("a", None)]);
+            assert_eq!(names(&section), ["a", "b", "C"]);
+        }
+
+        #[test]
+        fn by_last_updated_with_undated_last() {
+            let section = section(
+                SortBy::LastUpdated,
+                &[
+                    ("undated", None),
+                    ("old", Some("2022-01-01")),
+                    ("new", Some("2024-01-01")),
+                ],
+            );
+            assert_eq!(names(&section), ["new", "old", "undated"]);
+        }
+
+        #[test]
+        fn by_order_then_name() {
+            let mut section = section(
+                SortBy::Order,
+                &[("b", None), ("second", None), ("A", None), ("first", None)],
+            );
+            for content in &mut section.content {
+                if let AssetNode::Asset(asset) = content {
+                    asset.order = match asset.name.as_str() {
+                        "first" => Some(0),
+                        "second" => Some(10),
+                        _ => None,
+                    };
+                }
+            }
+            assert_eq!(names(&section), ["first", "second", "A", "b"]);
+        }
+
+        #[test]
+        fn subsections_by_order_then_name() {
+            let mut root = section(SortBy::Order, &[]);
+            for (name, order) in [
+                ("b", None),
+                ("tenth", Some(10)),
+                ("A", None),
+                ("second", Some(2)),
+            ] {
+                let mut subsection = section(SortBy::Order, &[]);
+                subsection.name = name.to_string();
+                subsection.order = order;
+                root.content.push(AssetNode::Section(subsection));
+            }
+            let names: Vec<_> = root
+                .sorted_subsections()
+                .into_iter()
+                .map(|section| section.name.as_str())
+                .collect();
+            assert_eq!(names, ["second", "tenth", "A", "b"]);
+        }
+    }
+
+    mod repository_gone {
+        use super::super::*;
+
+        fn not_found(repository: bool) -> GenerateAssetsError {
+            GenerateAssetsError::NotFound {
+                what: "Github repository bevy/bevy".to_string(),
+                status: Some(404),
+                repository,
+            }
+        }
+
+        #[test]
+        fn missing_repositories_are_gone() {
+            assert_eq!(
+                not_found(true).repository_gone(),
+                Some(RepositoryGone { status: Some(404) })
+            );
+        }
+
+        #[test]
+        fn other_errors_are_kept() {
+            let rate_limited = GenerateAssetsError::RateLimited {
+                url: "https://api.github.com".to_string(),
+                reset: None,
+            };
+            assert_eq!(not_found(false).repository_gone(), None);
+            assert_eq!(rate_limited.repository_gone(), None);
+        }
+    }
+
+    mod get_metadata_from_github {
+        use super::super::*;
+        use crate::mock_clients::{fixtures::*, MockRepositoryClient};
+
+        fn bevy_crates() -> Option<Vec<String>> {
+            Some(vec!["bevy".to_string(), "bevy_ecs".to_string()])
+        }
+
+        #[test]
+        fn reads_the_root_manifest() {
+            let client =
+                MockRepositoryClient::new().with_file("user/plugin", "Cargo.toml", PLUGIN_MANIFEST);
+            let metadata =
+                get_metadata_from_github(&client, "user", "plugin", &bevy_crates()).unwrap();
+            assert_eq!(metadata.license.as_deref(), Some("MIT OR Apache-2.0"));
+            assert_eq!(metadata.bevy_version, Some(BevyVersion::release(0, 13)));
+            assert_eq!(client.requests(), ["content Cargo.toml user/plugin"]);
+        }
+
+        #[test]
+        fn searches_the_workspace_members() {
+            let client = MockRepositoryClient::new()
+                .with_file("user/workspace", "Cargo.toml", WORKSPACE_MANIFEST)
+                .with_file("user/workspace", "crates/core/Cargo.toml", MEMBER_MANIFEST);
+            let metadata =
+                get_metadata_from_github(&client, "user", "workspace", &bevy_crates()).unwrap();
+            assert_eq!(metadata.license.as_deref(), Some("MIT"));
+            assert_eq!(metadata.bevy_version, Some(BevyVersion::release(0, 12)));
+            assert_eq!(
+                client.requests(),
+                [
+                    "content Cargo.toml user/workspace",
+                    "license user/workspace",
+                    "search Cargo.toml user/workspace",
+                    "content crates/core/Cargo.toml user/workspace",
+                ]
+            );
+        }
+
+        #[test]
+        fn keeps_going_past_a_broken_root_manifest() {
+            let client = MockRepositoryClient::new()
+                .with_file("user/broken", "Cargo.toml", BROKEN_MANIFEST)
+                .with_file("user/broken", "plugin/Cargo.toml", MEMBER_MANIFEST)
+                .with_license("user/broken", "MIT");
+            let metadata =
+                get_metadata_from_github(&client, "user", "broken", &bevy_crates()).unwrap();
+            assert_eq!(metadata.license.as_deref(), Some("MIT"));
+            assert_eq!(metadata.bevy_version, Some(BevyVersion::release(0, 12)));
+        }
+
+        #[test]
+        fn finds_the_manifest_of_a_repository_without_a_root_manifest() {
+            let client = MockRepositoryClient::new()
+                .with_file("user/nested", "build/Cargo.toml", TOOL_MANIFEST)
+                .with_file("user/nested", "plugin/Cargo.toml", PLUGIN_MANIFEST)
+                .with_file(
+                    "user/nested",
+                    "plugin/crates/core/Cargo.toml",
+                    MEMBER_MANIFEST,
+                );
+            let metadata =
+                get_metadata_from_github(&client, "user", "nested", &bevy_crates()).unwrap();
+            assert_eq!(metadata.license.as_deref(), Some("MIT OR Apache-2.0"));
+            assert_eq!(metadata.bevy_version, Some(BevyVersion::release(0, 13)));
+            assert_eq!(metadata.latest_version.as_deref(), Some("0.2.0"));
+            assert_eq!(
+                client.requests(),
+                [
+                    "content Cargo.toml user/nested",
+                    "repository user/nested",
+                    "tree user/nested",
+                    "content build/Cargo.toml user/nested",
+                    "content plugin/Cargo.toml user/nested",
+                ]
+            );
+        }
+
+        #[test]
+        fn fails_for_gone_repositories() {
+            let client = MockRepositoryClient::new();
+            let err =
+                get_metadata_from_github(&client, "user", "gone", &bevy_crates()).unwrap_err();
+            assert_eq!(
+                err.repository_gone(),
+                Some(RepositoryGone { status: Some(404) })
+            );
+        }
+    }
+
+    mod get_extra_metadata {
+        use super::super::*;
+        use crate::{
+            bevy_releases::join_versions, failures::FailureKind, mock_clients::MockMetadataClient,
+        };
+
+        fn section(links: &[&str]) -> Section {
+            Section {
+                name: "Assets".to_string(),
+                content: links
+                    .iter()
+                    .map(|link| {
+                        AssetNode::Asset(
+                            toml::from_str(&format!(
+                                "name = \"{link}\"\ndescription = \"\"\nlink = \"{link}\"\n"
+                            ))
+                            .unwrap(),
+                        )
+                    })
+                    .collect(),
+                slug: "assets".to_string(),
+                ..Default::default()
+            }
+        }
+
+        fn metadata(license: &str, bevy_version: &str) -> ExtraMetadata {
+            ExtraMetadata {
+                license: Some(license.to_string()),
+                bevy_version: Some(bevy_version.parse().unwrap()),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn dispatches_each_link_to_its_host() {
+            let github = MockMetadataClient::new()
+                .with_metadata("user/plugin", metadata("MIT", "0.13"))
+                .with_metadata("user/other", metadata("Apache-2.0", "0.12"));
+            let gitlab =
+                MockMetadataClient::new().with_metadata("group/project", metadata("MIT", "0.11"));
+            let mut section = section(&[
+                "https://github.com/user/plugin",
+                "https://gitlab.com/group/project",
+                "https://github.com/user/other",
+                "https://crates.io/crates/plugin",
+                "https://example.com",
+            ]);
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                gitlab_client: Some(&gitlab),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            assert_eq!(github.requests(), ["user/other", "user/plugin"]);
+            assert_eq!(gitlab.requests(), ["group/project"]);
+            let bevy_versions: Vec<_> = section
+                .assets()
+                .iter()
+                .map(|asset| {
+                    asset
+                        .bevy_versions
+                        .clone()
+                        .map(|versions| join_versions(&versions))
+                })
+                .collect();
+            assert_eq!(
+                bevy_versions,
+                [
+                    Some("0.13".to_string()),
+                    Some("0.11".to_string()),
+                    Some("0.12".to_string()),
+                    None,
+                    None
+                ]
+            );
+        }
+
+        #[test]
+        fn keeps_the_skipped_and_pinned_metadata() {
+            let github = MockMetadataClient::new()
+                .with_metadata("user/skipped", metadata("MIT", "0.13"))
+                .with_metadata("user/pinned", metadata("MIT", "0.13"));
+            let mut section = section(&[
+                "https://github.com/user/skipped",
+                "https://github.com/user/pinned",
+            ]);
+            for content in &mut section.content {
+                let AssetNode::Asset(asset) = content else {
+                    unreachable!()
+                };
+                asset.skip_metadata = asset.name.ends_with("skipped");
+                asset.manual.licenses = asset.name.ends_with("pinned");
+            }
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            assert_eq!(github.requests(), ["user/pinned"]);
+            let assets = section.assets();
+            assert!(assets[0].fetched_metadata.is_none());
+            assert_eq!(assets[0].licenses, None);
+            assert_eq!(assets[1].licenses, None);
+            assert_eq!(
+                assets[1].bevy_versions,
+                Some(vec![BevyVersion::release(0, 13)])
+            );
+            assert_eq!(assets[1].fetched_metadata.as_ref().unwrap().license, None);
+        }
+
+        #[test]
+        fn fetches_the_metadata_of_the_repository() {
+            let github =
+                MockMetadataClient::new().with_metadata("user/game", metadata("MIT", "0.13"));
+            let mut section = section(&["https://user.itch.io/game"]);
+            let AssetNode::Asset(asset) = &mut section.content[0] else {
+                unreachable!()
+            };
+            asset.repository = Some("https://github.com/user/game".parse().unwrap());
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            assert_eq!(github.requests(), ["user/game"]);
+            let asset = &section.assets()[0];
+            assert_eq!(asset.link, "https://user.itch.io/game");
+            assert_eq!(asset.licenses, Some(vec!["MIT".to_string()]));
+        }
+
+        #[test]
+        fn reads_the_crate_name_from_the_crates_io_dump() {
+            let db = rusqlite::Connection::open_in_memory().unwrap();
+            db.execute_batch(
+                "CREATE TABLE crates (name, license, req, version);
+                INSERT INTO crates VALUES ('bevy_plugin', 'MIT', '^0.13', '0.2.0');",
+            )
+            .unwrap();
+            let github = MockMetadataClient::new();
+            let mut section = section(&["https://github.com/user/plugin"]);
+            let AssetNode::Asset(asset) = &mut section.content[0] else {
+                unreachable!()
+            };
+            asset.crate_name = Some("bevy_plugin".to_string());
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                get_metadata_from_cratesio_statement: Some(
+                    db.prepare("SELECT license, req, version FROM crates WHERE name = ?")
+                        .unwrap(),
+                ),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            assert!(github.requests().is_empty());
+            let asset = &section.assets()[0];
+            assert_eq!(asset.licenses, Some(vec!["MIT".to_string()]));
+            assert_eq!(asset.bevy_versions, Some(vec![BevyVersion::release(0, 13)]));
+            assert_eq!(asset.latest_version.as_deref(), Some("0.2.0"));
+        }
+
+        #[test]
+        fn skips_hosts_without_a_client() {
+            let github = MockMetadataClient::new();
+            let mut section = section(&["https://gitlab.com/group/project"]);
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            assert!(github.requests().is_empty());
+            assert!(section.assets()[0].fetched_metadata.is_none());
+        }
+
+        #[test]
+        fn records_gone_repositories() {
+            let github = MockMetadataClient::new()
+                .with_metadata("user/plugin", metadata("MIT", "0.13"))
+                .rate_limited("user/limited");
+            let mut section = section(&[
+                "https://github.com/user/plugin",
+                "https://github.com/user/gone",
+                "https://github.com/user/limited",
+            ]);
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                ..Default::default()
+            };
+            enrich_assets(&mut section, metadata_source).unwrap();
+
+            let gone: Vec<_> = section
+                .assets()
+                .iter()
+                .map(|asset| asset.repository_gone)
+                .collect();
+            assert_eq!(
+                gone,
+                [None, Some(RepositoryGone { status: Some(404) }), None]
+            );
+        }
+
+        #[test]
+        fn collects_or_stops_on_failures() {
+            let github = MockMetadataClient::new().rate_limited("user/limited");
+            let links = [
+                "https://github.com/user/limited",
+                "https://github.com/user/gone",
+            ];
+
+            let failures = Failures::new();
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                error_mode: ErrorMode::KeepGoing,
+                failures: Some(&failures),
+                ..Default::default()
+            };
+            enrich_assets(&mut section(&links), metadata_source).unwrap();
+            let mut failures: Vec<_> = failures
+                .into_vec()
+                .into_iter()
+                .map(|failure| failure.error)
+                .collect();
+            failures.sort();
+            assert_eq!(
+                failures,
+                [
+                    "Rate limited by https://example.com/user/limited",
+                    "user/gone not found",
+                ]
+            );
+
+            let failures = Failures::new();
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                failures: Some(&failures),
+                ..Default::default()
+            };
+            enrich_assets(&mut section(&links), metadata_source).unwrap();
+            let mut failures: Vec<_> = failures
+                .into_vec()
+                .into_iter()
+                .map(|failure| (failure.link.unwrap(), failure.kind, failure.tolerated))
+                .collect();
+            failures.sort();
+            assert_eq!(
+                failures,
+                [
+                    (links[1].to_string(), FailureKind::RepositoryGone, true),
+                    (links[0].to_string(), FailureKind::RateLimited, true),
+                ]
+            );
+
+            let metadata_source = MetadataSource {
+                github_client: Some(&github),
+                error_mode: ErrorMode::FailFast,
+                ..Default::default()
+            };
+            assert!(enrich_assets(&mut section(&links), metadata_source).is_err());
+        }
+    }
+
+    mod replayed_metadata {
+        use super::super::*;
+        use crate::{
+            bevy_releases::join_versions,
+            cassette::{CassetteConfig, CassetteMode},
+            config::HttpConfig,
+            http::HttpContext,
+        };
+
+        #[test]
+        fn fetches_metadata_from_recorded_responses() {
+            let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+            let http = HttpContext::new(&HttpConfig {
+                cassette: Some(CassetteConfig {
+                    mode: CassetteMode::Replay,
+                    path: fixtures.join("cassettes/metadata.json"),
+                }),
+                ..Default::default()
+            })
+            .unwrap();
+            let github_client = GithubClient::new("token".to_string(), &http);
+            let gitlab_client = GitlabClient::new("token".to_string(), &http);
+
+            let section = parse_assets(
+                fixtures.join("assets").to_str().unwrap(),
+                &AssetFilter::default(),
+                MetadataSource {
+                    github_client: Some(&github_client),
+                    gitlab_client: Some(&gitlab_client),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut assets: Vec<_> = section
+                .assets()
+                .into_iter()
+                .map(|asset| {
+                    format!(
+                        "{}: {:?} {:?}{}",
+                        asset.name,
+                        asset
+                            .licenses
+                            .as_ref()
+                            .map(|licenses| licenses.join(" OR ")),
+                        asset
+                            .bevy_versions
+                            .as_ref()
+                            .map(|versions| join_versions(versions)),
+                        if asset.repository_gone.is_some() {
+                            " gone"
+                        } else {
+                            ""
+                        }
+                    )
+                })
+                .collect();
+            assets.sort();
+            assert_eq!(
+                assets,
+                [
+                    "Jumper: None None gone",
+                    "Mesh Tools: Some(\"MIT\") Some(\"0.13\")",
+                    "Pixel Camera: Some(\"Apache-2.0\") Some(\"0.11\")",
+                    "Space Race: None None",
+                    "bevy_terrain: Some(\"MIT\") Some(\"0.12\")",
+                    "bevy_tilemap: Some(\"MIT OR Apache-2.0\") Some(\"0.13\")",
+                ]
+            );
+        }
+    }
+
+    mod previous_url {
+        use super::super::*;
+
+        fn location(path: &str, name: Option<&str>) -> git::PreviousLocation {
+            git::PreviousLocation {
+                path: PathBuf::from(path),
+                name: name.map(str::to_string),
+            }
+        }
+
+        #[test]
+        fn uses_previous_sections_and_name() {
+            let asset_dir = Path::new("/repo/Assets");
+            assert_eq!(
+                previous_url(
+                    asset_dir,
+                    "/assets",
+                    &location("/repo/Assets/2D/Old Folder/foo.toml", Some("Old Name")),
+                    "New Name"
+                ),
+                Some("/assets/2d/old_folder/old_name/".to_string())
+            );
+            assert_eq!(
+                previous_url(
+                    asset_dir,
+                    "/assets",
+                    &location("/repo/Assets/Games/foo.toml", None),
+                    "New Name"
+                ),
+                Some("/assets/games/new_name/".to_string())
+            );
+        }
+
+        #[test]
+        fn ignores_locations_outside_asset_dir() {
+            assert_eq!(
+                previous_url(
+                    Path::new("/repo/Assets"),
+                    "/assets",
+                    &location("/repo/Drafts/foo.toml", None),
+                    "Foo"
+                ),
+                None
+            );
+        }
+    }
+
+    mod parse_assets_filter {
+        use super::super::*;
+
+        fn write_asset(dir: &Path, name: &str) {
+            fs::create_dir_all(dir).unwrap();
+            fs::write(
+                dir.join(format!("{}.toml", slugify(name))),
+                format!(
+                    "name = \"{name}\"\ndescription = \"The {name} asset.\"\n\
+                    link = \"https://github.com/user/{name}\"\n"
+                ),
+            )
+            .unwrap();
+        }
+
+        fn names(section: &Section) -> Vec<String> {
+            let mut names: Vec<_> = section
+                .assets()
+                .into_iter()
+                .map(|asset| asset.name.clone())
+                .collect();
+            names.sort();
+            names
+        }
+
+        #[test]
+        fn keeps_matching_sections_and_assets() {
+            let dir = std::env::temp_dir().join(format!(
+                "generate-assets-parse-assets-filter-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            write_asset(&dir.join("Assets/2D"), "Tilemap");
+            write_asset(&dir.join("Assets/2D/Shapes"), "Lyon");
+            write_asset(&dir.join("Assets/3D"), "Mesh");
+            write_asset(&dir.join("Games"), "Game");
+            let asset_dir = dir.to_str().unwrap();
+
+            let filter = AssetFilter {
+                sections: vec!["2d".to_string()],
+                assets: vec![],
+            };
+            let section = parse_assets(asset_dir, &filter, MetadataSource::default()).unwrap();
+            assert_eq!(names(&section), ["Lyon", "Tilemap"]);
+            assert_eq!(section.content.len(), 1);
+
+            let filter = AssetFilter {
+                sections: vec![],
+                assets: vec!["mesh".to_string(), "Game".to_string()],
+            };
+            let section = parse_assets(asset_dir, &filter, MetadataSource::default()).unwrap();
+            assert_eq!(names(&section), ["Game", "Mesh"]);
+
+            let section = parse_assets(
+                asset_dir,
+                &AssetFilter::default(),
+                MetadataSource::default(),
+            )
+            .unwrap();
+            assert_eq!(names(&section), ["Game", "Lyon", "Mesh", "Tilemap"]);
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
+    mod error_mode {
+        use super::super::*;
+
+        #[test]
+        fn keeps_going_past_files_failing_to_parse() {
+            let dir = std::env::temp_dir()
+                .join(format!("generate-assets-error-mode-{}", std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join("Assets")).unwrap();
+            fs::write(
+                dir.join("Assets/good.toml"),
+                "name = \"Good\"\ndescription = \"\"\nlink = \"https://example.com\"\n",
+            )
+            .unwrap();
+            fs::write(dir.join("Assets/broken.toml"), "name = \"Broken\"\n").unwrap();
+            let asset_dir = dir.to_str().unwrap();
+
+            for error_mode in [ErrorMode::Lenient, ErrorMode::FailFast] {
+                let metadata_source = MetadataSource {
+                    error_mode,
+                    ..Default::default()
+                };
+                assert!(parse_assets(asset_dir, &AssetFilter::default(), metadata_source).is_err());
+            }
+
+            let failures = Failures::new();
+            let metadata_source = MetadataSource {
+                error_mode: ErrorMode::KeepGoing,
+                failures: Some(&failures),
+                ..Default::default()
+            };
+            let section =
+                parse_assets(asset_dir, &AssetFilter::default(), metadata_source).unwrap();
+            let names: Vec<_> = section.assets().iter().map(|a| a.name.clone()).collect();
+            assert_eq!(names, ["Good"]);
+            let failures = failures.into_vec();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].file, Some(dir.join("Assets/broken.toml")));
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn reports_unreadable_entries_and_skips_other_files() {
+            let dir = std::env::temp_dir().join(format!(
+                "generate-assets-unreadable-entries-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(dir.join("Assets/drafts")).unwrap();
+            let asset = "name = \"Good\"\ndescription = \"\"\nlink = \"https://example.com\"\n";
+            fs::write(dir.join("Assets/good.toml"), asset).unwrap();
+            fs::write(dir.join("Assets/drafts/draft.toml"), "name = \"Draft\"\n").unwrap();
+            fs::write(dir.join("Assets/good.toml~"), "name = \"Backup\"\n").unwrap();
+            fs::write(dir.join("Assets/LICENSE"), "MIT").unwrap();
+            fs::write(dir.join(walk::IGNORE_FILE), "drafts/\n").unwrap();
+            let asset_dir = dir.to_str().unwrap();
+
+            let section = parse_assets(
+                asset_dir,
+                &AssetFilter::default(),
+                MetadataSource::default(),
+            )
+            .unwrap();
+            let names: Vec<_> = section.assets().iter().map(|a| a.name.clone()).collect();
+            assert_eq!(names, ["Good"]);
+
+            std::os::unix::fs::symlink(dir.join("missing.toml"), dir.join("Assets/link.toml"))
+                .unwrap();
+            assert!(parse_assets(
+                asset_dir,
+                &AssetFilter::default(),
+                MetadataSource::default()
+            )
+            .is_err());
+            let failures = Failures::new();
+            let metadata_source = MetadataSource {
+                error_mode: ErrorMode::KeepGoing,
+                failures: Some(&failures),
+                ..Default::default()
+            };
+            let section =
+                parse_assets(asset_dir, &AssetFilter::default(), metadata_source).unwrap();
+            assert_eq!(section.assets().len(), 1);
+            let failures = failures.into_vec();
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].file, Some(dir.join("Assets/link.toml")));
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+
+        #[cfg(unix)]
+        #[test]
+        fn reports_names_that_are_not_utf8() {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+            let dir = std::env::temp_dir().join(format!(
+                "generate-assets-non-utf8-names-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            let section = dir.join(OsStr::from_bytes(b"2D \xff"));
+            fs::create_dir_all(&section).unwrap();
+            fs::create_dir_all(dir.join("3D")).unwrap();
+            let asset = "name = \"Good\"\ndescription = \"\"\nlink = \"https://example.com\"\n";
+            fs::write(section.join("good.toml"), asset).unwrap();
+            fs::write(dir.join("3D/good.toml"), asset).unwrap();
+            let file = dir.join("3D").join(OsStr::from_bytes(b"\xfe.toml"));
+            fs::write(&file, asset).unwrap();
+
+            let failures = Failures::new();
+            let section = scan_assets(
+                &dir,
+                &AssetFilter::default(),
+                SymlinkPolicy::Follow,
+                ErrorMode::KeepGoing,
+                Some(&failures),
+            )
+            .unwrap();
+            assert_eq!(section.assets().len(), 1);
+            let mut files: Vec<_> = failures
+                .into_vec()
+                .into_iter()
+                .filter_map(|failure| failure.file)
+                .collect();
+            files.sort();
+            assert_eq!(files, [dir.join(OsStr::from_bytes(b"2D \xff")), file]);
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
+    mod set_cached_metadata {
+        use super::super::*;
+
+        fn section(assets: &[&str]) -> Section {
+            Section {
+                name: "Assets".to_string(),
+                content: assets
+                    .iter()
+                    .map(|asset| AssetNode::Asset(toml::from_str(asset).unwrap()))
+                    .collect(),
+                slug: "assets".to_string(),
+                ..Default::default()
+            }
+        }
+
+        #[test]
+        fn fills_the_fields_the_assets_dont_set() {
+            let previous = section(&["name = \"Cached\"\ndescription = \"\"\n\
+                link = \"https://crates.io/crates/cached\"\n\
+                licenses = [\"MIT\"]\nbevy_versions = [\"0.13\"]\nlatest_version = \"1.0.0\"\n"]);
+            let index = index::AssetIndex::new(&previous);
+
+            let mut section = section(&[
+                "name = \"Cached\"\ndescription = \"\"\n\
+                link = \"https://crates.io/crates/cached\"\nlicenses = [\"Apache-2.0\"]\n",
+                "name = \"New\"\ndescription = \"\"\nlink = \"https://github.com/user/new\"\n",
+                "name = \"Website\"\ndescription = \"\"\nlink = \"https://example.com\"\n",
+            ]);
+            section.set_cached_metadata(&index);
+
+            let assets = section.assets();
+            assert_eq!(assets[0].licenses, Some(vec!["Apache-2.0".to_string()]));
+            assert_eq!(
+                assets[0].bevy_versions,
+                Some(vec![BevyVersion::release(0, 13)])
+            );
+            assert_eq!(assets[0].latest_version.as_deref(), Some("1.0.0"));
+            assert!(!assets[0].metadata_missing);
+            assert!(assets[1].metadata_missing);
+            assert!(!assets[2].metadata_missing);
+        }
+
+        #[test]
+        fn keeps_the_skipped_and_pinned_fields() {
+            let cached = "name = \"Cached\"\ndescription = \"\"\n\
+                link = \"https://crates.io/crates/cached\"\n";
+            let previous = section(&[&format!(
+                "{cached}licenses = [\"MIT\"]\nbevy_versions = [\"0.13\"]\n"
+            )]);
+            let index = index::AssetIndex::new(&previous);
+
+            let mut section = section(&[
+                &format!("{cached}skip_metadata = true\n"),
+                &format!("{cached}[manual]\nlicenses = true\n"),
+            ]);
+            section.set_cached_metadata(&index);
+
+            let assets = section.assets();
+            assert_eq!(assets[0].licenses, None);
+            assert!(!assets[0].metadata_missing);
+            assert_eq!(assets[1].licenses, None);
+            assert_eq!(
+                assets[1].bevy_versions,
+                Some(vec![BevyVersion::release(0, 13)])
+            );
+            assert!(!assets[1].metadata_missing);
+        }
+    }
+
+    mod crates_dump_status {
+        use super::super::*;
+
+        #[test]
+        fn checks_every_file_of_the_dump() {
+            let dir = std::env::temp_dir().join(format!(
+                "generate-assets-crates-dump-status-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+            assert!(crates_dump_status(&dir).is_err());
+
+            for table in CRATES_DUMP_TABLES {
+                fs::write(dir.join(format!("{table}.csv")), "id,name\n1,bevy\n").unwrap();
+            }
+            let status = crates_dump_status(&dir).unwrap();
+            assert!(status.databases.is_empty());
+
+            fs::write(dir.join("db.sqlite"), "not a database").unwrap();
+            assert!(crates_dump_status(&dir).is_err());
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
+    mod open_bevy_related_crates_db {
+        use super::super::*;
+
+        fn write_csv(dir: &std::path::Path, table: &str, content: &str) {
+            fs::write(dir.join(format!("{table}.csv")), content).unwrap();
+        }
+
+        #[test]
+        fn keeps_only_bevy_related_rows() {
+            let dir = std::env::temp_dir().join(format!(
+                "generate-assets-bevy-related-crates-db-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).unwrap();
+
+            write_csv(
+                &dir,
+                "crates",
+                "id,name,homepage,repository\n\
+                1,bevy,https://bevyengine.org,https://github.com/bevyengine/bevy\n\
+                2,bevy_plugin,,\n\
+                3,serde,,\n",
+            );
+            write_csv(
+                &dir,
+                "versions",
+                "id,crate_id,num,license\n10,1,0.13.0,MIT\n20,2,0.1.0,MIT\n30,3,1.0.0,MIT\n",
+            );
+            write_csv(
+                &dir,
+                "dependencies",
+                "version_id,crate_id,req,kind\n20,1,^0.13,0\n30,4,^1,0\n",
+            );
+
+            // Left by an interrupted run
+            let tmp_path = dir.join("db-bevy-related.sqlite.tmp");
+            fs::write(&tmp_path, "partial").unwrap();
+
+            let mut loader = CratesIODumpLoader::default();
+            loader.target_path(&dir);
+            let db = open_bevy_related_crates_db(&loader).unwrap();
+            assert!(!tmp_path.exists());
+
+            let mut crates: Vec<String> = db
+                .prepare("SELECT name FROM crates")
+                .unwrap()
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .map(Result::unwrap)
+                .collect();
+            crates.sort();
+            assert_eq!(crates, vec!["bevy", "bevy_plugin"]);
+
+            let mut statement =
+                get_metadata_from_cratesio_statement(&db, Some(vec!["1".to_string()])).unwrap();
+            let metadata = get_metadata_from_cratesio("bevy_plugin", &mut statement).unwrap();
+            assert_eq!(
+                metadata,
+                (
+                    "MIT".to_string(),
+                    Some("^0.13".to_string()),
+                    "0.1.0".to_string()
+                )
+            );
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+
+        #[test]
+        fn rejects_folders_with_quotes() {
+            let mut loader = CratesIODumpLoader::default();
+            loader.target_path(
+                &std::env::temp_dir()
+                    .join(format!("generate-assets-o'dump-{}", std::process::id())),
+            );
+            let err = open_bevy_related_crates_db(&loader).unwrap_err();
+            assert!(err.to_string().contains("can't contain a quote"), "{}", err);
+        }
+    }
+
     mod get_bevy_version_from_manifest {
         use super::super::*;
 
@@ -828,7 +3699,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -844,7 +3715,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -857,7 +3728,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -871,7 +3742,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -888,7 +3759,7 @@ mod tests {
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
             // Note that this result is expected, but potentially wrong
-            assert_eq!(version, Some("0.5".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 5)));
         }
 
         #[test]
@@ -909,7 +3780,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -926,7 +3797,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -944,7 +3815,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -967,7 +3838,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -991,7 +3862,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &get_bevy_crates_names());
-            assert_eq!(version, Some("0.10".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 10)));
         }
 
         #[test]
@@ -1010,7 +3881,7 @@ mod tests {
 
             let manifest = get_manifest(dependencies, dev_dependencies, workspace_dependencies);
             let version = get_bevy_version_from_manifest(&manifest, &Some(vec![]));
-            assert_eq!(version, Some("0.5".to_string()));
+            assert_eq!(version, Some(BevyVersion::release(0, 5)));
         }
 
         #[test]
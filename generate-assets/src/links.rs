@@ -0,0 +1,863 @@
+use std::{
+    fmt::{self, Display},
+    io,
+    ops::Deref,
+    str::FromStr,
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Why a link could not be reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkError {
+    /// The server answered with a 404 or 410.
+    NotFound,
+    /// The server answered with another error status.
+    Status(u16),
+    /// The server took too long to answer.
+    Timeout,
+    /// The TLS certificate of the server was rejected.
+    Certificate(String),
+    /// The server could not be reached at all.
+    Unreachable(String),
+}
+
+impl Display for LinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LinkError::NotFound => write!(f, "Link not found."),
+            LinkError::Status(status) => write!(f, "Link answered with status {status}."),
+            LinkError::Timeout => write!(f, "Link timed out."),
+            LinkError::Certificate(err) => write!(f, "Invalid certificate: {err}"),
+            LinkError::Unreachable(err) => write!(f, "Link unreachable: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LinkError {}
+
+/// A link that could be reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkStatus {
+    Ok,
+    /// The link redirects to another page, which should be linked to directly.
+    Moved(String),
+}
+
+/// Checks that `link` can be reached.
+///
+/// A `HEAD` request is tried first, and a `GET` request is used as a fallback since
+/// some servers don't support `HEAD`. Redirects are followed, and reported when they
+/// lead to a different page, like a renamed repository.
+pub fn check_link(agent: &ureq::Agent, link: &str) -> Result<LinkStatus, LinkError> {
+    let response = match agent.head(link).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(..)) => agent.get(link).call()?,
+        Err(err) => return Err(LinkError::from(err)),
+    };
+
+    let final_link = response.get_url();
+    if canonical_link(final_link) == canonical_link(link) {
+        Ok(LinkStatus::Ok)
+    } else {
+        Ok(LinkStatus::Moved(normalize_link(final_link)))
+    }
+}
+
+impl From<ureq::Error> for LinkError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(404 | 410, _) => LinkError::NotFound,
+            ureq::Error::Status(status, _) => LinkError::Status(status),
+            ureq::Error::Transport(transport) => {
+                let timed_out = std::error::Error::source(&transport)
+                    .and_then(|source| source.downcast_ref::<io::Error>())
+                    .is_some_and(|err| {
+                        matches!(
+                            err.kind(),
+                            io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                        )
+                    });
+                let message = transport.to_string();
+
+                if timed_out {
+                    LinkError::Timeout
+                } else if message.to_ascii_lowercase().contains("certificate") {
+                    LinkError::Certificate(message)
+                } else {
+                    LinkError::Unreachable(message)
+                }
+            }
+        }
+    }
+}
+
+/// Query parameters only used to track where visitors come from.
+const TRACKING_PARAMETERS: &[&str] = &[
+    "ref", "ref_src", "fbclid", "gclid", "msclkid", "igshid", "mc_cid", "mc_eid",
+];
+
+fn is_tracking_parameter(name: &str) -> bool {
+    name.starts_with("utm_") || TRACKING_PARAMETERS.contains(&name)
+}
+
+/// Names of the tracking query parameters of a link, like `utm_source` or `ref`.
+pub fn tracking_parameters(link: &str) -> Vec<String> {
+    let Ok(url) = url::Url::parse(link.trim()) else {
+        return vec![];
+    };
+
+    url.query_pairs()
+        .map(|(name, _)| name.into_owned())
+        .filter(|name| is_tracking_parameter(name))
+        .collect()
+}
+
+/// Removes tracking query parameters from a link, keeping the other parameters.
+fn strip_tracking_parameters(url: &mut url::Url) {
+    if url.query().is_none() {
+        return;
+    }
+
+    let kept: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| !is_tracking_parameter(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        url.set_query(None);
+    } else {
+        url.query_pairs_mut().clear().extend_pairs(kept);
+    }
+}
+
+/// Rewrites a link in its preferred form: using `https`, without a `www.` prefix, a
+/// trailing slash, a `.git` suffix or tracking query parameters. Links to a version or
+/// another page of a crate on crates.io are rewritten to the page of the crate.
+///
+/// Links that can't be parsed are returned unchanged.
+pub fn normalize_link(link: &str) -> String {
+    let Ok(mut url) = url::Url::parse(link.trim()) else {
+        return link.to_string();
+    };
+    if let Ok(locator) = link.parse::<CratesIoLocator>() {
+        return locator.to_string();
+    }
+
+    if url.scheme() == "http" {
+        let _ = url.set_scheme("https");
+    }
+    if let Some(host) = url.host_str().and_then(|host| host.strip_prefix("www.")) {
+        let host = host.to_string();
+        let _ = url.set_host(Some(&host));
+    }
+    let path = url.path();
+    let trimmed = path.trim_end_matches('/');
+    let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed).to_string();
+    if trimmed != path {
+        url.set_path(&trimmed);
+    }
+    strip_tracking_parameters(&mut url);
+    url_to_link(&url)
+}
+
+/// Writes `url` as a link.
+fn url_to_link(url: &url::Url) -> String {
+    let mut link = url.to_string();
+    // `Url` always adds a slash after the host, even when the link had none
+    if url.path() == "/" && url.query().is_none() && url.fragment().is_none() {
+        link.pop();
+    }
+    link
+}
+
+/// Normalizes a link so that links to the same page compare equal.
+///
+/// The scheme, a `www.` prefix, the fragment, a trailing slash, a `.git` suffix and
+/// tracking query parameters are ignored. Paths on Github and Gitlab are case insensitive,
+/// so they are lowercased. Links to the pages of a crate on crates.io are the same as the
+/// link to the crate.
+pub fn canonical_link(link: &str) -> String {
+    let Ok(mut url) = url::Url::parse(link.trim()) else {
+        return link.trim().trim_end_matches('/').to_ascii_lowercase();
+    };
+    if let Ok(locator) = link.parse::<CratesIoLocator>() {
+        return format!("crates.io/crates/{}", locator.name);
+    }
+
+    strip_tracking_parameters(&mut url);
+    let host = url.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    let mut path = url.path().trim_end_matches('/');
+    path = path.strip_suffix(".git").unwrap_or(path);
+    let path = if matches!(host, "github.com" | "gitlab.com") {
+        path.to_ascii_lowercase()
+    } else {
+        path.to_string()
+    };
+
+    match url.query() {
+        Some(query) => format!("{host}{path}?{query}"),
+        None => format!("{host}{path}"),
+    }
+}
+
+/// The link of an asset, checked when its file is parsed: an absolute `http` or `https` link
+/// with a host, in the canonical form of [`url::Url`], like with a lowercase host.
+///
+/// Links to crates.io, Github and Gitlab must point to a crate or a repository, which their
+/// metadata is fetched from. Links aren't [normalized](normalize_link), so the validation can
+/// still report links that aren't in their preferred form.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct AssetLink(String);
+
+/// Why a link isn't a valid [`AssetLink`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssetLinkError {
+    Invalid(url::ParseError),
+    NotHttp,
+    /// A link to crates.io, Github or Gitlab that doesn't point to a crate or a repository,
+    /// with an example of the expected link.
+    NotAProject(&'static str),
+}
+
+impl Display for AssetLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetLinkError::Invalid(err) => write!(f, "Link is invalid: {err}."),
+            AssetLinkError::NotHttp => write!(f, "Link must start with https:// or http://."),
+            AssetLinkError::NotAProject(example) => {
+                write!(
+                    f,
+                    "Link must point to a crate or a repository, like {example}."
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AssetLinkError {}
+
+impl AssetLink {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for AssetLink {
+    type Err = AssetLinkError;
+
+    fn from_str(link: &str) -> Result<Self, Self::Err> {
+        let url = url::Url::parse(link.trim()).map_err(AssetLinkError::Invalid)?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(AssetLinkError::NotHttp);
+        }
+        let is_project = match url.host_str() {
+            None | Some("") => return Err(AssetLinkError::Invalid(url::ParseError::EmptyHost)),
+            Some("crates.io" | "www.crates.io") => link
+                .parse::<CratesIoLocator>()
+                .map(|_| ())
+                .map_err(|_| "https://crates.io/crates/<name>"),
+            Some("github.com" | "www.github.com") => link
+                .parse::<GithubRepoLocator>()
+                .map(|_| ())
+                .map_err(|_| "https://github.com/<owner>/<repository>"),
+            Some("gitlab.com" | "www.gitlab.com") => link
+                .parse::<GitlabProjectLocator>()
+                .map(|_| ())
+                .map_err(|_| "https://gitlab.com/<group>/<project>"),
+            Some(_) => Ok(()),
+        };
+        match is_project {
+            Ok(()) => Ok(AssetLink(url_to_link(&url))),
+            Err(example) => Err(AssetLinkError::NotAProject(example)),
+        }
+    }
+}
+
+/// Names that can't be the owner of a repository, as they are the first segment of the paths
+/// of other pages of Github, like `https://github.com/orgs/bevyengine/repositories`.
+const GITHUB_RESERVED_NAMES: &[&str] = &[
+    "about",
+    "apps",
+    "collections",
+    "enterprise",
+    "explore",
+    "features",
+    "marketplace",
+    "notifications",
+    "orgs",
+    "settings",
+    "sponsors",
+    "topics",
+    "users",
+];
+
+/// A Github repository, read from a link to it or to one of its pages, like
+/// `https://github.com/bevyengine/bevy/releases`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubRepoLocator {
+    pub owner: String,
+    pub repository: String,
+}
+
+/// Why a link isn't a link to a Github repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GithubLinkError {
+    Invalid(url::ParseError),
+    /// The link is to another host than Github.
+    NotGithub,
+    /// The link is to Github, but not to a repository or one of its pages.
+    NotARepository,
+}
+
+impl Display for GithubLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GithubLinkError::Invalid(err) => write!(f, "Link is invalid: {err}."),
+            GithubLinkError::NotGithub => write!(f, "Link isn't a Github link."),
+            GithubLinkError::NotARepository => write!(
+                f,
+                "Link must point to a repository, like https://github.com/<owner>/<repository>."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GithubLinkError {}
+
+impl FromStr for GithubRepoLocator {
+    type Err = GithubLinkError;
+
+    /// Reads the owner and repository of `link`, ignoring the scheme, a `www.` prefix, a
+    /// `.git` suffix, trailing and repeated slashes, the query, the fragment, and the path
+    /// of the page of the repository after them.
+    fn from_str(link: &str) -> Result<Self, Self::Err> {
+        let url = url::Url::parse(link.trim()).map_err(GithubLinkError::Invalid)?;
+        if !matches!(url.host_str(), Some("github.com" | "www.github.com")) {
+            return Err(GithubLinkError::NotGithub);
+        }
+        let mut segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty());
+        let (Some(owner), Some(repository)) = (segments.next(), segments.next()) else {
+            return Err(GithubLinkError::NotARepository);
+        };
+        let repository = repository.strip_suffix(".git").unwrap_or(repository);
+
+        // Owners are letters, digits and dashes, repositories can also have `_` and `.`
+        let is_owner = owner.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !GITHUB_RESERVED_NAMES.contains(&owner.to_ascii_lowercase().as_str());
+        let is_repository = !matches!(repository, "" | "." | "..")
+            && repository
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !is_owner || !is_repository {
+            return Err(GithubLinkError::NotARepository);
+        }
+        Ok(GithubRepoLocator {
+            owner: owner.to_string(),
+            repository: repository.to_string(),
+        })
+    }
+}
+
+impl Display for GithubRepoLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "https://github.com/{}/{}", self.owner, self.repository)
+    }
+}
+
+/// A crate on crates.io, read from a link to it or to one of its pages, like
+/// `https://crates.io/crates/bevy/versions`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CratesIoLocator {
+    pub name: String,
+    /// The version the link points to, like `0.14.0` in
+    /// `https://crates.io/crates/bevy/0.14.0`, which gets stale as the crate is released.
+    pub version: Option<String>,
+}
+
+/// Why a link isn't a link to a crate on crates.io.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CratesIoLinkError {
+    Invalid(url::ParseError),
+    /// The link is to another host than crates.io.
+    NotCratesIo,
+    /// The link is to crates.io, but not to a crate or one of its pages.
+    NotACrate,
+}
+
+impl Display for CratesIoLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CratesIoLinkError::Invalid(err) => write!(f, "Link is invalid: {err}."),
+            CratesIoLinkError::NotCratesIo => write!(f, "Link isn't a crates.io link."),
+            CratesIoLinkError::NotACrate => write!(
+                f,
+                "Link must point to a crate, like https://crates.io/crates/<name>."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CratesIoLinkError {}
+
+impl FromStr for CratesIoLocator {
+    type Err = CratesIoLinkError;
+
+    /// Reads the name of the crate of `link`, and the version it points to if any, ignoring
+    /// the scheme, a `www.` prefix, trailing and repeated slashes, the query, the fragment,
+    /// and the path of the page of the crate after them, like `/versions`.
+    fn from_str(link: &str) -> Result<Self, Self::Err> {
+        let url = url::Url::parse(link.trim()).map_err(CratesIoLinkError::Invalid)?;
+        if !matches!(url.host_str(), Some("crates.io" | "www.crates.io")) {
+            return Err(CratesIoLinkError::NotCratesIo);
+        }
+        let mut segments = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty());
+        let (Some("crates"), Some(name)) = (segments.next(), segments.next()) else {
+            return Err(CratesIoLinkError::NotACrate);
+        };
+        let is_name = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+        if !is_name {
+            return Err(CratesIoLinkError::NotACrate);
+        }
+
+        // Versions start with a digit, the other pages of a crate are words like `versions`
+        let version = segments.next().filter(|segment| {
+            segment.starts_with(|c: char| c.is_ascii_digit())
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+        });
+        Ok(CratesIoLocator {
+            name: name.to_string(),
+            version: version.map(str::to_string),
+        })
+    }
+}
+
+impl Display for CratesIoLocator {
+    /// Writes the link to the page of the crate, without the version.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "https://crates.io/crates/{}", self.name)
+    }
+}
+
+/// Names that can't be the top-level group of a project, as they are the first segment of the
+/// paths of other pages of Gitlab, like `https://gitlab.com/explore/projects`.
+const GITLAB_RESERVED_NAMES: &[&str] = &[
+    "dashboard",
+    "explore",
+    "groups",
+    "help",
+    "projects",
+    "search",
+    "users",
+];
+
+/// A Gitlab project, read from a link to it or to one of its pages, like
+/// `https://gitlab.com/group/subgroup/project/-/releases`.
+///
+/// Projects can be in subgroups, so the path of the project is everything before the `/-/`
+/// separating it from the path of its pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitlabProjectLocator {
+    /// The group of the project, with its subgroups, like `group/subgroup`.
+    pub namespace: String,
+    pub project: String,
+}
+
+/// Why a link isn't a link to a Gitlab project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitlabLinkError {
+    Invalid(url::ParseError),
+    /// The link is to another host than Gitlab.
+    NotGitlab,
+    /// The link is to Gitlab, but not to a project or one of its pages.
+    NotAProject,
+}
+
+impl Display for GitlabLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GitlabLinkError::Invalid(err) => write!(f, "Link is invalid: {err}."),
+            GitlabLinkError::NotGitlab => write!(f, "Link isn't a Gitlab link."),
+            GitlabLinkError::NotAProject => write!(
+                f,
+                "Link must point to a project, like https://gitlab.com/<group>/<project>."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GitlabLinkError {}
+
+impl GitlabProjectLocator {
+    /// The full path of the project, like `group/subgroup/project`, which identifies it in
+    /// the API of Gitlab.
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.namespace, self.project)
+    }
+}
+
+impl FromStr for GitlabProjectLocator {
+    type Err = GitlabLinkError;
+
+    /// Reads the namespace and project of `link`, ignoring the scheme, a `www.` prefix, a
+    /// `.git` suffix, trailing and repeated slashes, the query, the fragment, and the path
+    /// of the page of the project after `/-/`.
+    fn from_str(link: &str) -> Result<Self, Self::Err> {
+        let url = url::Url::parse(link.trim()).map_err(GitlabLinkError::Invalid)?;
+        if !matches!(url.host_str(), Some("gitlab.com" | "www.gitlab.com")) {
+            return Err(GitlabLinkError::NotGitlab);
+        }
+        let mut segments: Vec<_> = url
+            .path_segments()
+            .into_iter()
+            .flatten()
+            .filter(|segment| !segment.is_empty())
+            .take_while(|segment| *segment != "-")
+            .collect();
+        let Some(project) = segments.pop() else {
+            return Err(GitlabLinkError::NotAProject);
+        };
+        let project = project.strip_suffix(".git").unwrap_or(project);
+
+        // Groups and projects are letters, digits, `_`, `-` and `.`
+        let is_name = |name: &str| {
+            !matches!(name, "" | "." | "..")
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        let is_namespace = segments.first().is_some_and(|group| {
+            !GITLAB_RESERVED_NAMES.contains(&group.to_ascii_lowercase().as_str())
+        }) && segments.iter().all(|group| is_name(group));
+        if !is_namespace || !is_name(project) {
+            return Err(GitlabLinkError::NotAProject);
+        }
+        Ok(GitlabProjectLocator {
+            namespace: segments.join("/"),
+            project: project.to_string(),
+        })
+    }
+}
+
+impl Display for GitlabProjectLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "https://gitlab.com/{}", self.path())
+    }
+}
+
+impl<'de> Deserialize<'de> for AssetLink {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl Deref for AssetLink {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for AssetLink {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for AssetLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for AssetLink {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AssetLink {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_error(status: u16) -> ureq::Error {
+        ureq::Error::Status(status, ureq::Response::new(status, "", "").unwrap())
+    }
+
+    #[test]
+    fn classifies_status_errors() {
+        assert_eq!(LinkError::from(status_error(404)), LinkError::NotFound);
+        assert_eq!(LinkError::from(status_error(410)), LinkError::NotFound);
+        assert_eq!(LinkError::from(status_error(500)), LinkError::Status(500));
+    }
+
+    #[test]
+    fn normalize_link_rewrites_to_preferred_form() {
+        assert_eq!(
+            normalize_link("http://www.github.com/bevyengine/bevy.git/"),
+            "https://github.com/bevyengine/bevy"
+        );
+        assert_eq!(
+            normalize_link("https://crates.io/crates/bevy"),
+            "https://crates.io/crates/bevy"
+        );
+        assert_eq!(
+            normalize_link("https://bevyengine.org"),
+            "https://bevyengine.org"
+        );
+        assert_eq!(normalize_link("not a link"), "not a link");
+    }
+
+    #[test]
+    fn tracking_parameters_are_stripped() {
+        let link = "https://example.com/game?utm_source=reddit&page=2&ref=bevy";
+        assert_eq!(tracking_parameters(link), ["utm_source", "ref"]);
+        assert_eq!(normalize_link(link), "https://example.com/game?page=2");
+        assert_eq!(
+            normalize_link("https://example.com/game?utm_medium=social"),
+            "https://example.com/game"
+        );
+        assert_eq!(
+            canonical_link("https://example.com/game?fbclid=abc"),
+            canonical_link("https://example.com/game")
+        );
+    }
+
+    #[test]
+    fn canonical_link_ignores_insignificant_differences() {
+        let canonical = canonical_link("https://github.com/bevyengine/bevy");
+        for link in [
+            "https://github.com/bevyengine/bevy/",
+            "https://github.com/bevyengine/bevy.git",
+            "http://www.github.com/BevyEngine/Bevy",
+            "https://github.com/bevyengine/bevy#readme",
+        ] {
+            assert_eq!(canonical_link(link), canonical, "{link}");
+        }
+
+        assert_ne!(
+            canonical_link("https://github.com/bevyengine/bevy-assets"),
+            canonical
+        );
+        assert_ne!(
+            canonical_link("https://example.com/Game"),
+            canonical_link("https://example.com/game")
+        );
+    }
+
+    #[test]
+    fn asset_links_are_checked_and_canonical() {
+        let link: AssetLink = " https://GitHub.com/User/Repo ".parse().unwrap();
+        assert_eq!(link, "https://github.com/User/Repo");
+        assert_eq!(
+            "https://example.com/".parse::<AssetLink>().unwrap(),
+            "https://example.com"
+        );
+        assert!("https://crates.io/crates/bevy".parse::<AssetLink>().is_ok());
+
+        assert!(matches!(
+            "github.com/user/repo".parse::<AssetLink>(),
+            Err(AssetLinkError::Invalid(_))
+        ));
+        assert_eq!(
+            "ftp://example.com/file".parse::<AssetLink>(),
+            Err(AssetLinkError::NotHttp)
+        );
+        assert_eq!(
+            "https://github.com/user/".parse::<AssetLink>(),
+            Err(AssetLinkError::NotAProject(
+                "https://github.com/<owner>/<repository>"
+            ))
+        );
+        assert!("https://github.com/orgs/bevyengine"
+            .parse::<AssetLink>()
+            .is_err());
+    }
+
+    #[test]
+    fn github_links_are_read_to_their_repository() {
+        let expected = GithubRepoLocator {
+            owner: "user".to_string(),
+            repository: "bevy_repo.rs".to_string(),
+        };
+        for link in [
+            "https://github.com/user/bevy_repo.rs",
+            "https://github.com/user/bevy_repo.rs.git",
+            "https://github.com/user/bevy_repo.rs/",
+            "https://github.com/user/bevy_repo.rs.git/",
+            "https://github.com//user//bevy_repo.rs",
+            "https://github.com/user/bevy_repo.rs?tab=readme-ov-file",
+            "https://github.com/user/bevy_repo.rs#readme",
+            "https://github.com/user/bevy_repo.rs/releases",
+            "https://github.com/user/bevy_repo.rs/tree/main/crates/plugin",
+            "http://www.github.com/user/bevy_repo.rs",
+            " https://GitHub.com/user/bevy_repo.rs ",
+        ] {
+            assert_eq!(link.parse(), Ok(expected.clone()), "{}", link);
+        }
+        assert_eq!(expected.to_string(), "https://github.com/user/bevy_repo.rs");
+
+        for link in [
+            "https://github.com/user",
+            "https://github.com/user/",
+            "https://github.com/user/.git",
+            "https://github.com/orgs/bevyengine",
+            "https://github.com/topics/bevy",
+            "https://github.com/user/repo%20name",
+            "https://github.com/user name/repo",
+        ] {
+            assert_eq!(
+                link.parse::<GithubRepoLocator>(),
+                Err(GithubLinkError::NotARepository),
+                "{}",
+                link
+            );
+        }
+        assert_eq!(
+            "https://gitlab.com/user/repo".parse::<GithubRepoLocator>(),
+            Err(GithubLinkError::NotGithub)
+        );
+        assert!(matches!(
+            "github.com/user/repo".parse::<GithubRepoLocator>(),
+            Err(GithubLinkError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn gitlab_links_are_read_to_their_project() {
+        let expected = GitlabProjectLocator {
+            namespace: "group/subgroup".to_string(),
+            project: "bevy_project".to_string(),
+        };
+        for link in [
+            "https://gitlab.com/group/subgroup/bevy_project",
+            "https://gitlab.com/group/subgroup/bevy_project.git",
+            "https://gitlab.com/group/subgroup/bevy_project/",
+            "https://www.gitlab.com//group/subgroup//bevy_project",
+            "https://gitlab.com/group/subgroup/bevy_project/-/releases",
+            "https://gitlab.com/group/subgroup/bevy_project/-/tree/main/crates",
+            "https://gitlab.com/group/subgroup/bevy_project#readme",
+        ] {
+            assert_eq!(link.parse(), Ok(expected.clone()), "{}", link);
+        }
+        assert_eq!(expected.path(), "group/subgroup/bevy_project");
+        assert_eq!(
+            expected.to_string(),
+            "https://gitlab.com/group/subgroup/bevy_project"
+        );
+        assert_eq!(
+            "https://gitlab.com/user/project".parse(),
+            Ok(GitlabProjectLocator {
+                namespace: "user".to_string(),
+                project: "project".to_string(),
+            })
+        );
+
+        for link in [
+            "https://gitlab.com/user",
+            "https://gitlab.com/user/-/project",
+            "https://gitlab.com/explore/projects",
+            "https://gitlab.com/user/project%20name",
+        ] {
+            assert_eq!(
+                link.parse::<GitlabProjectLocator>(),
+                Err(GitlabLinkError::NotAProject),
+                "{}",
+                link
+            );
+            assert!(link.parse::<AssetLink>().is_err(), "{}", link);
+        }
+        assert_eq!(
+            "https://github.com/user/project".parse::<GitlabProjectLocator>(),
+            Err(GitlabLinkError::NotGitlab)
+        );
+    }
+
+    #[test]
+    fn crates_io_links_are_read_to_their_crate() {
+        let locator = |version: Option<&str>| CratesIoLocator {
+            name: "bevy_crate".to_string(),
+            version: version.map(str::to_string),
+        };
+        for (link, version) in [
+            ("https://crates.io/crates/bevy_crate", None),
+            ("https://crates.io/crates/bevy_crate/", None),
+            ("https://www.crates.io/crates/bevy_crate", None),
+            ("https://crates.io//crates//bevy_crate", None),
+            ("https://crates.io/crates/bevy_crate/versions", None),
+            (
+                "https://crates.io/crates/bevy_crate/reverse_dependencies",
+                None,
+            ),
+            ("https://crates.io/crates/bevy_crate?tab=readme", None),
+            ("https://crates.io/crates/bevy_crate/0.3.1", Some("0.3.1")),
+            (
+                "https://crates.io/crates/bevy_crate/0.4.0-rc.2",
+                Some("0.4.0-rc.2"),
+            ),
+            (
+                "https://crates.io/crates/bevy_crate/0.3.1/dependencies",
+                Some("0.3.1"),
+            ),
+        ] {
+            assert_eq!(link.parse(), Ok(locator(version)), "{}", link);
+            assert_eq!(
+                normalize_link(link),
+                "https://crates.io/crates/bevy_crate",
+                "{}",
+                link
+            );
+            assert_eq!(
+                canonical_link(link),
+                canonical_link("https://crates.io/crates/bevy_crate"),
+                "{}",
+                link
+            );
+        }
+
+        for link in [
+            "https://crates.io",
+            "https://crates.io/crates",
+            "https://crates.io/bevy_crate",
+            "https://crates.io/keywords/bevy",
+            "https://crates.io/users/user",
+            "https://crates.io/crates/bevy%20crate",
+        ] {
+            assert_eq!(
+                link.parse::<CratesIoLocator>(),
+                Err(CratesIoLinkError::NotACrate),
+                "{}",
+                link
+            );
+            assert!(link.parse::<AssetLink>().is_err(), "{}", link);
+        }
+        assert_eq!(
+            "https://lib.rs/crates/bevy_crate".parse::<CratesIoLocator>(),
+            Err(CratesIoLinkError::NotCratesIo)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Plain Markdown listings of the assets, without anything specific to Zola, to reuse them in
+//! the Bevy book, READMEs, or other static site generators.
+
+use std::{fs, path::Path};
+
+use anyhow::Context;
+
+use crate::{bevy_releases::join_versions, Asset, AssetNode, Section};
+
+/// Writes a Markdown file listing the assets of `section` to `dir`, as `<slug>.md`, and the
+/// files of its subsections to the `<slug>` folder, linked from the file of their parent.
+pub fn write_markdown(section: &Section, dir: &Path) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+    let path = dir.join(format!("{}.md", section.slug));
+    fs::write(&path, section_listing(section))
+        .with_context(|| format!("Writing {}", path.display()))?;
+
+    for subsection in subsections(section) {
+        write_markdown(subsection, &dir.join(&section.slug))?;
+    }
+    Ok(())
+}
+
+/// The subsections of `section`, in the order of the website.
+fn subsections(section: &Section) -> Vec<&Section> {
+    let mut subsections: Vec<_> = section
+        .content
+        .iter()
+        .filter(|content| matches!(content, AssetNode::Section(_)))
+        .collect();
+    subsections.sort_by_key(|section| format!("{}-{}", section.order(), section.name()));
+    subsections
+        .into_iter()
+        .filter_map(|content| match content {
+            AssetNode::Section(section) => Some(section),
+            AssetNode::Asset(_) => None,
+        })
+        .collect()
+}
+
+/// The Markdown listing of `section`: its header and description, links to its subsections,
+/// then its assets.
+fn section_listing(section: &Section) -> String {
+    let mut content = format!("# {}\n", section.name);
+    if let Some(header) = &section.header {
+        content += &format!("\n**{header}**\n");
+    }
+    if let Some(description) = &section.description {
+        content += &format!("\n{description}\n");
+    }
+
+    let subsections = subsections(section);
+    if !subsections.is_empty() {
+        content += "\n";
+        for subsection in subsections {
+            content += &format!(
+                "- [{}]({}/{}.md)\n",
+                subsection.name, section.slug, subsection.slug
+            );
+        }
+    }
+
+    let assets = section.sorted_assets();
+    if !assets.is_empty() {
+        content += "\n";
+        for asset in assets {
+            content += &asset_item(asset);
+        }
+    }
+    content
+}
+
+/// The list item of `asset`, with its description, and its Bevy versions and licenses when
+/// it has some.
+fn asset_item(asset: &Asset) -> String {
+    let mut item = format!(
+        "- [{}]({}): {}",
+        asset.name,
+        asset.link,
+        asset.description.trim().replace('\n', " ")
+    );
+    if let Some(deprecation) = &asset.deprecated {
+        item += &format!(" **Deprecated:** {}", deprecation.reason);
+        if let Some(successor) = &deprecation.successor {
+            item += &format!(" Use {successor} instead.");
+        }
+    }
+    let mut details = vec![];
+    if let Some(bevy_versions) = asset.bevy_versions.as_ref().filter(|v| !v.is_empty()) {
+        details.push(format!("Bevy {}", join_versions(bevy_versions)));
+    }
+    if let Some(licenses) = asset.licenses.as_ref().filter(|l| !l.is_empty()) {
+        details.push(licenses.join(", "));
+    }
+    if !details.is_empty() {
+        item += &format!(" ({})", details.join(" · "));
+    }
+    item + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SortBy;
+
+    fn section(name: &str, slug: &str, content: Vec<AssetNode>) -> Section {
+        Section {
+            name: name.to_string(),
+            content,
+            sort_by: SortBy::Name,
+            slug: slug.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn asset(name: &str, bevy_versions: Option<&[&str]>) -> AssetNode {
+        AssetNode::Asset(Asset {
+            link: format!("https://github.com/user/{name}").parse().unwrap(),
+            description: format!("The {name} crate.\n"),
+            licenses: Some(vec!["MIT".to_string(), "Apache-2.0".to_string()]),
+            bevy_versions: bevy_versions
+                .map(|versions| versions.iter().map(|v| v.parse().unwrap()).collect()),
+            slug: name.to_string(),
+            ..Asset::named(name)
+        })
+    }
+
+    #[test]
+    fn writes_a_file_per_section() {
+        let dir =
+            std::env::temp_dir().join(format!("generate-assets-markdown-{}", std::process::id()));
+        let mut root = section(
+            "Assets",
+            "assets",
+            vec![
+                AssetNode::Section(section(
+                    "2D",
+                    "2d",
+                    vec![asset("bevy_foo", Some(&["0.13"])), asset("bar", None)],
+                )),
+                AssetNode::Section(section("3D", "3d", vec![])),
+            ],
+        );
+        root.description = Some("Crates for Bevy.".to_string());
+        write_markdown(&root, &dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("assets.md")).unwrap(),
+            "# Assets\n\nCrates for Bevy.\n\n- [2D](assets/2d.md)\n- [3D](assets/3d.md)\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("assets/2d.md")).unwrap(),
+            "# 2D\n\n\
+            - [bar](https://github.com/user/bar): The bar crate. (MIT, Apache-2.0)\n\
+            - [bevy_foo](https://github.com/user/bevy_foo): The bevy_foo crate. \
+            (Bevy 0.13 · MIT, Apache-2.0)\n"
+        );
+        assert!(dir.join("assets/3d.md").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+//! Test doubles of the [`clients`](crate::clients), answering from memory and recording the
+//! requests made to them.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Mutex,
+};
+
+use crate::{
+    clients::{MetadataClient, RepositoryClient},
+    error::{GenerateAssetsError, Result},
+    ExtraMetadata,
+};
+
+/// `Cargo.toml` files of the repositories served by the mocks.
+pub mod fixtures {
+    /// A plugin depending on Bevy, with its license.
+    pub const PLUGIN_MANIFEST: &str = r#"[package]
+name = "bevy_plugin"
+version = "0.2.0"
+license = "MIT OR Apache-2.0"
+
+[dependencies]
+bevy = "0.13"
+"#;
+
+    /// The root of a workspace, without a package or dependencies.
+    pub const WORKSPACE_MANIFEST: &str = r#"[workspace]
+members = ["crates/*"]
+"#;
+
+    /// A member of a workspace, depending on a single Bevy crate.
+    pub const MEMBER_MANIFEST: &str = r#"[package]
+name = "bevy_plugin_core"
+version = "0.2.0"
+license = "MIT"
+
+[dependencies]
+bevy_ecs = "0.12"
+"#;
+
+    /// A tool of a repository, which doesn't depend on Bevy.
+    pub const TOOL_MANIFEST: &str = r#"[package]
+name = "xtask"
+version = "0.1.0"
+license = "MIT"
+
+[dependencies]
+clap = "4"
+"#;
+
+    /// A file that isn't a valid manifest.
+    pub const BROKEN_MANIFEST: &str = "[package\nname = ";
+}
+
+/// A [`RepositoryClient`] serving the files given to it, the other repositories being gone.
+#[derive(Default)]
+pub struct MockRepositoryClient {
+    /// Files of each repository, by `owner/name` then path.
+    repositories: HashMap<String, BTreeMap<String, String>>,
+    /// License found by the host for each repository, by `owner/name`.
+    licenses: HashMap<String, String>,
+    /// Every request made, like `content owner/name Cargo.toml`.
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockRepositoryClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the file at `path` to `repository`, written as `owner/name`.
+    #[must_use]
+    pub fn with_file(mut self, repository: &str, path: &str, content: &str) -> Self {
+        self.repositories
+            .entry(repository.to_string())
+            .or_default()
+            .insert(path.to_string(), content.to_string());
+        self
+    }
+
+    /// Sets the license the host found for `repository`, written as `owner/name`.
+    #[must_use]
+    pub fn with_license(mut self, repository: &str, license: &str) -> Self {
+        self.licenses
+            .insert(repository.to_string(), license.to_string());
+        self
+    }
+
+    /// The requests made so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Records a request to `repository`, and returns its files.
+    fn files(&self, request: &str, repository: &str) -> Result<&BTreeMap<String, String>> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push(format!("{request} {repository}"));
+        self.repositories
+            .get(repository)
+            .ok_or_else(|| not_found(repository, false))
+    }
+}
+
+impl RepositoryClient for MockRepositoryClient {
+    fn get_repository(&self, owner: &str, repository_name: &str) -> Result<()> {
+        let repository = format!("{owner}/{repository_name}");
+        self.files("repository", &repository)
+            .map(|_| ())
+            .map_err(|_| not_found(&repository, true))
+    }
+
+    fn get_content(&self, owner: &str, repository_name: &str, path: &str) -> Result<String> {
+        let repository = format!("{owner}/{repository_name}");
+        self.files(&format!("content {path}"), &repository)?
+            .get(path)
+            .cloned()
+            .ok_or_else(|| not_found(&format!("{repository}/{path}"), false))
+    }
+
+    fn get_license(&self, owner: &str, repository_name: &str) -> Result<String> {
+        let repository = format!("{owner}/{repository_name}");
+        self.files("license", &repository)?;
+        self.licenses
+            .get(&repository)
+            .cloned()
+            .ok_or_else(|| not_found(&format!("license of {repository}"), false))
+    }
+
+    fn list_files(&self, owner: &str, repository_name: &str) -> Result<Vec<String>> {
+        let repository = format!("{owner}/{repository_name}");
+        Ok(self.files("tree", &repository)?.keys().cloned().collect())
+    }
+
+    fn search_file(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        file_name: &str,
+    ) -> Result<Vec<String>> {
+        let repository = format!("{owner}/{repository_name}");
+        Ok(self
+            .files(&format!("search {file_name}"), &repository)?
+            .keys()
+            .filter(|path| path.rsplit('/').next() == Some(file_name))
+            .cloned()
+            .collect())
+    }
+}
+
+/// What a [`MockMetadataClient`] answers for a repository.
+enum MockResponse {
+    Metadata(ExtraMetadata),
+    RateLimited,
+}
+
+/// A [`MetadataClient`] answering with the metadata given to it, the other repositories
+/// being gone.
+#[derive(Default)]
+pub struct MockMetadataClient {
+    /// Responses by `owner/name`.
+    responses: HashMap<String, MockResponse>,
+    /// Every repository asked for, as `owner/name`.
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockMetadataClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers with `metadata` for `repository`, written as `owner/name`.
+    #[must_use]
+    pub fn with_metadata(mut self, repository: &str, metadata: ExtraMetadata) -> Self {
+        self.responses
+            .insert(repository.to_string(), MockResponse::Metadata(metadata));
+        self
+    }
+
+    /// Fails with [`GenerateAssetsError::RateLimited`] for `repository`.
+    #[must_use]
+    pub fn rate_limited(mut self, repository: &str) -> Self {
+        self.responses
+            .insert(repository.to_string(), MockResponse::RateLimited);
+        self
+    }
+
+    /// The repositories asked for so far, sorted since they are fetched in parallel.
+    pub fn requests(&self) -> Vec<String> {
+        let mut requests = self.requests.lock().unwrap().clone();
+        requests.sort();
+        requests
+    }
+}
+
+impl MetadataClient for MockMetadataClient {
+    fn get_metadata(
+        &self,
+        owner: &str,
+        repository_name: &str,
+        _bevy_crates: &Option<Vec<String>>,
+    ) -> Result<ExtraMetadata> {
+        let repository = format!("{owner}/{repository_name}");
+        self.requests.lock().unwrap().push(repository.clone());
+        match self.responses.get(&repository) {
+            Some(MockResponse::Metadata(metadata)) => Ok(metadata.clone()),
+            Some(MockResponse::RateLimited) => Err(GenerateAssetsError::RateLimited {
+                url: format!("https://example.com/{repository}"),
+                reset: None,
+            }),
+            None => Err(not_found(&repository, true)),
+        }
+    }
+}
+
+fn not_found(what: &str, repository: bool) -> GenerateAssetsError {
+    GenerateAssetsError::NotFound {
+        what: what.to_string(),
+        status: Some(404),
+        repository,
+    }
+}
@@ -0,0 +1,95 @@
+//! Validation of the platforms set in asset files, and their inference from `Cargo.toml`
+//! files.
+
+/// Platforms assets can support, letting the website filter them.
+pub const PLATFORMS: &[&str] = &["android", "ios", "linux", "macos", "wasm", "windows"];
+
+/// Whether `platform` is one of [`PLATFORMS`].
+pub fn is_known_platform(platform: &str) -> bool {
+    PLATFORMS.contains(&platform)
+}
+
+/// Suggests the known platform closest to an unknown `platform`, like `macos` for `macOS`
+/// or `wasm` for `web`.
+pub fn suggest_platform(platform: &str) -> Option<&'static str> {
+    let normalized = platform.trim().to_lowercase().replace([' ', '_', '-'], "");
+    let alias = match normalized.as_str() {
+        "web" | "wasm32" | "webassembly" | "browser" => "wasm",
+        "osx" | "mac" | "darwin" => "macos",
+        "win" | "win32" | "win64" => "windows",
+        "iphone" | "ipad" => "ios",
+        _ => normalized.as_str(),
+    };
+
+    PLATFORMS
+        .iter()
+        .find(|candidate| **candidate == alias)
+        .copied()
+        .or_else(|| {
+            PLATFORMS
+                .iter()
+                .map(|candidate| (strsim::jaro_winkler(candidate, alias), *candidate))
+                .filter(|(similarity, _)| *similarity >= 0.85)
+                .max_by(|(a, _), (b, _)| a.total_cmp(b))
+                .map(|(_, candidate)| candidate)
+        })
+}
+
+/// Infers the platforms a crate explicitly supports from the `[target.'...']` tables of its
+/// `Cargo.toml`, given as target triples like `wasm32-unknown-unknown` or `cfg(...)`
+/// expressions like `cfg(target_os = "android")`.
+///
+/// Crates without target-specific dependencies may support every platform, so nothing is
+/// inferred for them. Negated conditions, like `cfg(not(target_arch = "wasm32"))`, are
+/// ignored since they don't tell which platforms are supported.
+pub fn infer_platforms<'a>(targets: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut platforms = vec![];
+    for target in targets {
+        if target.contains("not(") {
+            continue;
+        }
+        let matching = [
+            ("wasm32", "wasm"),
+            ("android", "android"),
+            ("ios", "ios"),
+            ("windows", "windows"),
+            ("macos", "macos"),
+            ("darwin", "macos"),
+            ("linux", "linux"),
+        ];
+        for (pattern, platform) in matching {
+            if target.contains(pattern) && !platforms.contains(&platform.to_string()) {
+                platforms.push(platform.to_string());
+            }
+        }
+    }
+    platforms.sort();
+    platforms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_known_platforms() {
+        assert_eq!(suggest_platform("macOS"), Some("macos"));
+        assert_eq!(suggest_platform("Web"), Some("wasm"));
+        assert_eq!(suggest_platform("Windos"), Some("windows"));
+        assert_eq!(suggest_platform("playstation"), None);
+    }
+
+    #[test]
+    fn infers_platforms_from_targets() {
+        assert_eq!(
+            infer_platforms([
+                "cfg(target_arch = \"wasm32\")",
+                "cfg(any(target_os = \"android\", target_os = \"ios\"))",
+                "x86_64-pc-windows-msvc",
+                "cfg(not(target_os = \"linux\"))",
+            ]),
+            ["android", "ios", "wasm", "windows"]
+        );
+        assert!(infer_platforms([]).is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+use console::style;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Where the extra metadata of an asset came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataOutcome {
+    /// Metadata was fetched from a remote API.
+    Fetched,
+    /// Metadata was found in a local cache, like the crates.io database dump.
+    Cached,
+    /// No source could provide metadata for this asset.
+    Skipped,
+}
+
+/// How many assets of a section got each [`MetadataOutcome`], or failed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OutcomeCounts {
+    pub fetched: usize,
+    pub cached: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl OutcomeCounts {
+    fn add(&mut self, other: OutcomeCounts) {
+        self.fetched += other.fetched;
+        self.cached += other.cached;
+        self.skipped += other.skipped;
+        self.failed += other.failed;
+    }
+}
+
+/// Progress of the metadata gathering, displayed as one progress bar per [`Section`](crate::Section).
+///
+/// Logs should be written with it, as a [`MakeWriter`], to be printed above the progress bars
+/// instead of garbling them.
+#[derive(Default, Clone)]
+pub struct Progress {
+    bars: MultiProgress,
+    /// Whether a colored line is printed for each asset, see [`Progress::status_lines`].
+    status_lines: bool,
+    /// The name and counts of each section, in the order they were started.
+    sections: Arc<Mutex<Vec<(String, OutcomeCounts)>>>,
+}
+
+impl Progress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prints a line with the outcome of each asset above the progress bars, colored when
+    /// stderr is a terminal. Failures are then logged at the debug level, since their line
+    /// already shows the error.
+    #[must_use]
+    pub fn status_lines(mut self, enabled: bool) -> Self {
+        self.status_lines = enabled;
+        self
+    }
+
+    /// Starts tracking a section containing `len` assets.
+    pub fn section(&self, name: &str, len: usize) -> SectionProgress {
+        let bar = self.bars.add(ProgressBar::new(len as u64));
+        bar.set_style(
+            ProgressStyle::with_template("{prefix:>24} [{bar:30}] {pos}/{len} {msg}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+        bar.set_prefix(name.to_string());
+
+        let mut sections = self.sections.lock().unwrap();
+        sections.push((name.to_string(), OutcomeCounts::default()));
+        SectionProgress {
+            bar,
+            name: name.to_string(),
+            index: sections.len() - 1,
+            progress: self.clone(),
+        }
+    }
+
+    /// The counts of each section started so far, in the order they were started.
+    pub fn counts(&self) -> Vec<(String, OutcomeCounts)> {
+        self.sections.lock().unwrap().clone()
+    }
+
+    /// A table of the counts of each section, with their total, or `None` if no section
+    /// was started.
+    pub fn summary(&self) -> Option<String> {
+        let sections = self.counts();
+        if sections.is_empty() {
+            return None;
+        }
+        let mut total = OutcomeCounts::default();
+        for (_, counts) in &sections {
+            total.add(*counts);
+        }
+        let width = sections
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Section".len());
+
+        let row = |name: &str, counts: &OutcomeCounts| {
+            format!(
+                "{name:<width$}  {:>7}  {:>6}  {:>7}  {:>6}\n",
+                counts.fetched, counts.cached, counts.skipped, counts.failed
+            )
+        };
+        let mut table = format!(
+            "{:<width$}  {:>7}  {:>6}  {:>7}  {:>6}\n",
+            "Section", "Fetched", "Cached", "Skipped", "Failed"
+        );
+        for (name, counts) in &sections {
+            table += &row(name, counts);
+        }
+        table += &row("Total", &total);
+        Some(table)
+    }
+
+    /// Prints [`Progress::summary`] to stderr, below the progress bars, along with the status
+    /// lines.
+    pub fn print_summary(&self) {
+        if !self.status_lines {
+            return;
+        }
+        if let Some(summary) = self.summary() {
+            eprint!("\n{summary}");
+        }
+    }
+
+    /// Prints a line above the progress bars, or directly to stderr when they are hidden
+    /// because it isn't a terminal.
+    fn println(&self, line: &str) {
+        if self.bars.is_hidden() {
+            eprintln!("{line}");
+        } else {
+            let _ = self.bars.println(line);
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for Progress {
+    type Writer = LogWriter<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        LogWriter { bars: &self.bars }
+    }
+}
+
+/// Writes logs to stderr, hiding the progress bars while writing, see [`Progress`].
+pub struct LogWriter<'a> {
+    bars: &'a MultiProgress,
+}
+
+impl Write for LogWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.bars.suspend(|| io::stderr().write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+/// Counters of a single section, see [`Progress::section`].
+pub struct SectionProgress {
+    bar: ProgressBar,
+    name: String,
+    /// Index of the counts of the section in [`Progress::sections`].
+    index: usize,
+    progress: Progress,
+}
+
+impl SectionProgress {
+    /// Whether the outcome of each asset is printed, see [`Progress::status_lines`].
+    pub fn prints_status(&self) -> bool {
+        self.progress.status_lines
+    }
+
+    /// Records that the asset named `asset` has been processed.
+    pub fn record(&self, asset: &str, outcome: &anyhow::Result<MetadataOutcome>) {
+        let counts = {
+            let mut sections = self.progress.sections.lock().unwrap();
+            let counts = &mut sections[self.index].1;
+            match outcome {
+                Ok(MetadataOutcome::Fetched) => counts.fetched += 1,
+                Ok(MetadataOutcome::Cached) => counts.cached += 1,
+                Ok(MetadataOutcome::Skipped) => counts.skipped += 1,
+                Err(_) => counts.failed += 1,
+            }
+            *counts
+        };
+
+        if self.progress.status_lines {
+            let status = match outcome {
+                Ok(MetadataOutcome::Fetched) => style("fetched").green(),
+                Ok(MetadataOutcome::Cached) => style("cached").cyan(),
+                Ok(MetadataOutcome::Skipped) => style("skipped").yellow(),
+                Err(_) => style("failed").red().bold(),
+            };
+            let mut line = format!("{:>7}  {} / {asset}", status.for_stderr(), self.name);
+            if let Err(err) = outcome {
+                line += &format!(": {}", style(format!("{err:#}")).red().for_stderr());
+            }
+            self.progress.println(&line);
+        }
+
+        self.bar.set_message(format!(
+            "{} failed, {} cached",
+            counts.failed, counts.cached
+        ));
+        self.bar.inc(1);
+    }
+
+    /// Stops tracking the section, leaving its final counters on screen.
+    pub fn finish(&self) {
+        self.bar.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summarizes_every_section() {
+        let progress = Progress::new();
+        let games = progress.section("Games", 3);
+        games.record("Jumper", &Ok(MetadataOutcome::Fetched));
+        games.record("Runner", &Ok(MetadataOutcome::Skipped));
+        games.record("Shooter", &Err(anyhow::anyhow!("Not found")));
+        let assets = progress.section("2D", 1);
+        assets.record("Tilemap", &Ok(MetadataOutcome::Cached));
+
+        assert_eq!(
+            progress.summary().unwrap(),
+            "Section  Fetched  Cached  Skipped  Failed\n\
+            Games          1       0        1       1\n\
+            2D             0       1        0       0\n\
+            Total          1       1        1       1\n"
+        );
+    }
+}
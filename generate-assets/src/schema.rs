@@ -0,0 +1,630 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::error::Category;
+
+use crate::{
+    encoding::read_text_file,
+    error::{GenerateAssetsError, Result},
+    walk::{is_asset_file, AssetWalker},
+    Asset, CategoryConfig, DuplicatesConfig, FeaturedConfig, DUPLICATES_FILE, FEATURED_FILE,
+};
+
+/// The formats an asset file can be written in, from its extension. Only asset files can
+/// be written in JSON or YAML, the configuration files like `_category.toml` are TOML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetFormat {
+    Toml,
+    Json,
+    /// With the `.yaml` or `.yml` extension.
+    Yaml,
+}
+
+impl AssetFormat {
+    /// The format of the file at `path`, if its extension is the one of an asset file.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(AssetFormat::Toml),
+            "json" => Some(AssetFormat::Json),
+            "yaml" | "yml" => Some(AssetFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Deserializes `content`, read from `path`, with a [`SchemaError`] if it doesn't match
+    /// `T`.
+    pub fn parse<T: DeserializeOwned>(
+        self,
+        path: &Path,
+        content: &str,
+    ) -> std::result::Result<T, Box<SchemaError>> {
+        let error = match self {
+            AssetFormat::Toml => match toml::from_str(content) {
+                Ok(value) => return Ok(value),
+                Err(err) => SchemaError::from_toml(path, content, &err),
+            },
+            AssetFormat::Json => match serde_json::from_str(content) {
+                Ok(value) => return Ok(value),
+                Err(err) => SchemaError::from_json(path, content, &err),
+            },
+            AssetFormat::Yaml => match serde_yaml::from_str(content) {
+                Ok(value) => return Ok(value),
+                Err(err) => SchemaError::from_yaml(path, content, &err),
+            },
+        };
+        Err(Box::new(error))
+    }
+}
+
+impl Display for AssetFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetFormat::Toml => write!(f, "TOML"),
+            AssetFormat::Json => write!(f, "JSON"),
+            AssetFormat::Yaml => write!(f, "YAML"),
+        }
+    }
+}
+
+/// An asset or category file that doesn't match the schema of [`Asset`] or [`CategoryConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    pub path: PathBuf,
+    /// 1-based line of the error, if it could be located.
+    pub line: Option<usize>,
+    /// 1-based column of the error, if it could be located.
+    pub column: Option<usize>,
+    /// The key the error is about, if any.
+    pub key: Option<String>,
+    pub message: String,
+    /// A suggestion on how to fix the file.
+    pub hint: Option<String>,
+}
+
+impl SchemaError {
+    /// Builds an error from a failure to deserialize `content`, read from `path`.
+    pub fn from_toml(path: &Path, content: &str, error: &toml::de::Error) -> Self {
+        let location = error
+            .span()
+            .map(|span| line_and_column(content, span.start));
+        Self::new(
+            path,
+            content,
+            AssetFormat::Toml,
+            error.message().trim(),
+            location,
+            None,
+            true,
+        )
+    }
+
+    /// Builds an error from a failure to deserialize the JSON `content`, read from `path`.
+    pub fn from_json(path: &Path, content: &str, error: &serde_json::Error) -> Self {
+        // The line is 0 when the error isn't about a place of the content
+        let location = (error.line() > 0).then(|| (error.line(), error.column().max(1)));
+        let message = error.to_string();
+        let message = message
+            .strip_suffix(&format!(
+                " at line {} column {}",
+                error.line(),
+                error.column()
+            ))
+            .unwrap_or(&message);
+        let syntax = matches!(error.classify(), Category::Syntax | Category::Eof);
+        Self::new(
+            path,
+            content,
+            AssetFormat::Json,
+            message,
+            location,
+            None,
+            syntax,
+        )
+    }
+
+    /// Builds an error from a failure to deserialize the YAML `content`, read from `path`.
+    pub fn from_yaml(path: &Path, content: &str, error: &serde_yaml::Error) -> Self {
+        let message = error.to_string();
+        // The location is only part of the message when it is where the error is, the
+        // errors about a whole table are at its start
+        let (mut message, location) = match error.location() {
+            Some(location) => {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                match message.strip_suffix(&suffix) {
+                    Some(message) => (message, Some((location.line(), location.column()))),
+                    None => (message.as_str(), None),
+                }
+            }
+            None => (message.as_str(), None),
+        };
+        // Errors about a value start with its path, like `deprecated.reason: invalid type`
+        let mut path_key = None;
+        if let Some((value_path, rest)) = message.split_once(": ") {
+            if !value_path.contains(' ') {
+                path_key = value_path.split(['.', '[']).next().map(str::to_string);
+                message = rest;
+            }
+        }
+        let syntax = location.is_some() && path_key.is_none();
+        Self::new(
+            path,
+            content,
+            AssetFormat::Yaml,
+            message,
+            location,
+            path_key,
+            syntax,
+        )
+    }
+
+    fn new(
+        path: &Path,
+        content: &str,
+        format: AssetFormat,
+        message: &str,
+        location: Option<(usize, usize)>,
+        key: Option<String>,
+        syntax: bool,
+    ) -> Self {
+        let message = message.to_string();
+        let key = quoted_key(&message).or(key).or_else(|| {
+            let (line, _) = location?;
+            key_on_line(content.lines().nth(line - 1)?, format)
+        });
+        let hint = hint(&message, key.as_deref(), format, syntax);
+
+        SchemaError {
+            path: path.to_path_buf(),
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+            key,
+            message,
+            hint,
+        }
+    }
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, ":{line}:{column}")?;
+        }
+        write!(f, ": ")?;
+        if let Some(key) = &self.key {
+            write!(f, "`{key}`: ")?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(hint) = &self.hint {
+            write!(f, "\n  hint: {hint}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Parses an asset file, in the [`AssetFormat`] of its extension, reporting where and why it
+/// doesn't match the schema.
+pub fn parse_asset_file(path: &Path) -> Result<Asset> {
+    let format = AssetFormat::from_path(path).unwrap_or(AssetFormat::Toml);
+    let mut asset: Asset = parse_file(path, format)?;
+    asset.original_path = Some(path.to_path_buf());
+
+    Ok(asset)
+}
+
+/// Parses a `_category.toml` file, reporting where and why it doesn't match the schema.
+pub fn parse_category_file(path: &Path) -> Result<CategoryConfig> {
+    parse_file(path, AssetFormat::Toml)
+}
+
+/// Parses the file listing links allowed in several top-level sections, see [`DUPLICATES_FILE`].
+pub fn parse_duplicates_file(path: &Path) -> Result<DuplicatesConfig> {
+    parse_file(path, AssetFormat::Toml)
+}
+
+/// Parses the file listing the featured assets, see [`FEATURED_FILE`].
+pub fn parse_featured_file(path: &Path) -> Result<FeaturedConfig> {
+    parse_file(path, AssetFormat::Toml)
+}
+
+/// Reads and deserializes a file in `format`, with a [`SchemaError`] if it doesn't match `T`.
+fn parse_file<T: DeserializeOwned>(path: &Path, format: AssetFormat) -> Result<T> {
+    let content = read_text_file(path)?;
+    format
+        .parse(path, &content)
+        .map_err(GenerateAssetsError::SchemaError)
+}
+
+/// Checks every asset and category file in `asset_dir` against the schema, without
+/// fetching any metadata.
+pub fn validate_asset_files(asset_dir: &Path) -> Result<Vec<SchemaError>> {
+    let mut errors = vec![];
+    visit_schema_files(asset_dir, &mut |path| {
+        let result = if is_category_file(path) {
+            parse_category_file(path).map(|_| ())
+        } else if is_duplicates_file(path) {
+            parse_duplicates_file(path).map(|_| ())
+        } else if is_featured_file(path) {
+            parse_featured_file(path).map(|_| ())
+        } else {
+            parse_asset_file(path).map(|_| ())
+        };
+        match result {
+            Err(GenerateAssetsError::SchemaError(err)) => errors.push(*err),
+            Err(err) => return Err(err),
+            Ok(()) => {}
+        }
+        Ok(())
+    })?;
+
+    Ok(errors)
+}
+
+/// Parses every asset file in `asset_dir`, without fetching any metadata.
+pub fn read_asset_files(asset_dir: &Path) -> Result<Vec<Asset>> {
+    let mut assets = vec![];
+    visit_schema_files(asset_dir, &mut |path| {
+        if is_asset_file(path) {
+            assets.push(parse_asset_file(path)?);
+        }
+        Ok(())
+    })?;
+
+    Ok(assets)
+}
+
+fn is_category_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == "_category.toml")
+}
+
+fn is_duplicates_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == DUPLICATES_FILE)
+}
+
+fn is_featured_file(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == FEATURED_FILE)
+}
+
+/// Calls `f` with every file of `dir` in an [`AssetFormat`] that isn't skipped by its
+/// [`AssetWalker`].
+fn visit_schema_files(dir: &Path, f: &mut impl FnMut(&Path) -> Result<()>) -> Result<()> {
+    AssetWalker::new(dir)?.walk_files(dir, &mut |path| {
+        if AssetFormat::from_path(path).is_some() {
+            f(path)?;
+        }
+        Ok(())
+    })
+}
+
+/// Converts a byte offset into a 1-based line and column.
+fn line_and_column(content: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(content.len());
+    while !content.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &content[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+    (line, column)
+}
+
+/// Finds the key named in messages like "missing field `link`".
+fn quoted_key(message: &str) -> Option<String> {
+    if !message.starts_with("missing field")
+        && !message.starts_with("unknown field")
+        && !message.starts_with("duplicate field")
+        && !message.starts_with("invalid extra key")
+    {
+        return None;
+    }
+    let start = message.find('`')? + 1;
+    let end = start + message[start..].find('`')?;
+    Some(message[start..end].to_string())
+}
+
+/// Finds the key assigned on a line like `name = "value"`, `"name": "value"` in JSON or
+/// `name: value` in YAML.
+fn key_on_line(line: &str, format: AssetFormat) -> Option<String> {
+    let separator = match format {
+        AssetFormat::Toml => '=',
+        AssetFormat::Json | AssetFormat::Yaml => ':',
+    };
+    let (key, _) = line.split_once(separator)?;
+    let key = key.trim().trim_matches('"');
+    (!key.is_empty()).then(|| key.to_string())
+}
+
+/// A suggestion on how to fix the error, falling back on the syntax of `format` when it's a
+/// `syntax` error.
+fn hint(message: &str, key: Option<&str>, format: AssetFormat, syntax: bool) -> Option<String> {
+    let hint = if message.starts_with("missing field") {
+        let key = key?;
+        match format {
+            AssetFormat::Toml => format!("add `{key} = ...` to the file"),
+            AssetFormat::Json => format!("add `\"{key}\": ...` to the file"),
+            AssetFormat::Yaml => format!("add `{key}: ...` to the file"),
+        }
+    } else if message.starts_with("unknown field") {
+        "remove this key or check its spelling".to_string()
+    } else if message.starts_with("invalid type") {
+        match key? {
+            "licenses" | "bevy_versions" | "tags" | "platforms" | "depends_on" | "extends"
+            | "allowed_links" => "this key expects a list of strings, like `[\"MIT\"]`",
+            "order" => "this key expects a positive integer",
+            "paginate_by" | "shown" | "rotation_days" => {
+                "this key expects an integer greater than 0"
+            }
+            "assets" => "this key expects a list of tables, written as `[[assets]]`",
+            "deprecated" => {
+                "this key expects a table, like `{ reason = \"...\", successor = \"...\" }`"
+            }
+            "sort_order_reversed" => "this key expects `true` or `false`",
+            "pricing" => "this key expects a table, like `{ model = \"paid\", price = \"$15\" }`",
+            "sort_by" => "this key expects one of `order`, `name` or `last_updated`",
+            _ => "this key expects a string, check that the value is quoted",
+        }
+        .to_string()
+    } else if message.starts_with("invalid value")
+        && matches!(key, Some("paginate_by" | "shown" | "rotation_days"))
+    {
+        "this key expects an integer greater than 0".to_string()
+    } else if message.starts_with("invalid extra key") {
+        if message.ends_with("set by the generator") {
+            "use another name for this key".to_string()
+        } else {
+            "change the type of this value".to_string()
+        }
+    } else if message.starts_with("unknown variant") {
+        "use one of the expected values".to_string()
+    } else if message.starts_with("duplicate key")
+        || message.starts_with("duplicate field")
+        || message.starts_with("duplicate entry")
+    {
+        "each key can only be set once".to_string()
+    } else if syntax {
+        match format {
+            AssetFormat::Toml => "check the TOML syntax, strings must be quoted",
+            AssetFormat::Json => {
+                "check the JSON syntax, keys and strings must be quoted and the last value of \
+                a list or object can't be followed by a comma"
+            }
+            AssetFormat::Yaml => {
+                "check the YAML syntax, the keys of a table must be indented the same"
+            }
+        }
+        .to_string()
+    } else {
+        return None;
+    };
+    Some(hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema_error(content: &str) -> SchemaError {
+        let err = toml::from_str::<Asset>(content).unwrap_err();
+        SchemaError::from_toml(Path::new("asset.toml"), content, &err)
+    }
+
+    #[test]
+    fn reports_unknown_key_with_location() {
+        let err = schema_error(
+            "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\nlicence = \"MIT\"\n",
+        );
+        assert_eq!(err.line, Some(4));
+        assert_eq!(err.column, Some(1));
+        assert_eq!(err.key.as_deref(), Some("licence"));
+        assert!(err.hint.is_some());
+    }
+
+    #[test]
+    fn reports_missing_key() {
+        let err = schema_error("name = \"a\"\ndescription = \"b\"\n");
+        assert_eq!(err.key.as_deref(), Some("link"));
+        assert_eq!(err.hint.as_deref(), Some("add `link = ...` to the file"));
+    }
+
+    #[test]
+    fn reports_invalid_extra_key() {
+        let err = schema_error(
+            "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\n\n[extra]\nslug = \"c\"\n",
+        );
+        assert_eq!(err.key.as_deref(), Some("slug"));
+        assert_eq!(err.hint.as_deref(), Some("use another name for this key"));
+
+        let err = schema_error(
+            "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\n\n[extra]\nhidden = \"yes\"\n",
+        );
+        assert_eq!(err.key.as_deref(), Some("hidden"));
+        assert_eq!(err.hint.as_deref(), Some("change the type of this value"));
+
+        let asset: Asset = toml::from_str(
+            "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\n\n[extra]\nhidden = true\nbanner = \"banner.png\"\n",
+        )
+        .unwrap();
+        assert_eq!(asset.extra.0["banner"].as_str(), Some("banner.png"));
+    }
+
+    #[test]
+    fn reports_invalid_type() {
+        let err = schema_error(
+            "name = \"a\"\nlink = \"https://example.com\"\ndescription = \"b\"\nlicenses = \"MIT\"\n",
+        );
+        assert_eq!(err.line, Some(4));
+        assert_eq!(err.key.as_deref(), Some("licenses"));
+        assert!(err.to_string().starts_with("asset.toml:4:"));
+    }
+
+    #[test]
+    fn reports_invalid_link() {
+        let err = schema_error("name = \"a\"\nlink = \"github.com/user/a\"\ndescription = \"b\"\n");
+        assert_eq!(err.line, Some(2));
+        assert_eq!(err.key.as_deref(), Some("link"));
+        assert!(err.to_string().starts_with("asset.toml:2:"), "{}", err);
+        assert!(err.message.contains("Link is invalid"), "{}", err.message);
+    }
+
+    #[test]
+    fn reports_json_errors() {
+        let path = Path::new("asset.json");
+        let error = |content: &str| AssetFormat::Json.parse::<Asset>(path, content).unwrap_err();
+
+        let err = error("{\n  \"name\": \"a\",\n  \"description\": \"b\"\n}\n");
+        assert_eq!(err.key.as_deref(), Some("link"));
+        assert_eq!(err.hint.as_deref(), Some("add `\"link\": ...` to the file"));
+
+        let err = error(
+            "{\n  \"name\": \"a\",\n  \"link\": \"https://example.com\",\n  \"licenses\": \"MIT\"\n}\n",
+        );
+        assert_eq!(err.line, Some(4));
+        assert_eq!(err.key.as_deref(), Some("licenses"));
+        assert_eq!(
+            err.message,
+            "invalid type: string \"MIT\", expected a sequence"
+        );
+
+        let err = error("{\n  \"name\": \"a\",\n}\n");
+        assert_eq!((err.line, err.key.as_deref()), (Some(3), None));
+        assert!(
+            err.to_string()
+                .starts_with("asset.json:3:1: trailing comma"),
+            "{}",
+            err
+        );
+        assert!(err.hint.unwrap().starts_with("check the JSON syntax"));
+    }
+
+    #[test]
+    fn reports_yaml_errors() {
+        let path = Path::new("asset.yaml");
+        let error = |content: &str| AssetFormat::Yaml.parse::<Asset>(path, content).unwrap_err();
+
+        let err = error("name: a\nlink: https://example.com\ndescription: b\nlicences: [MIT]\n");
+        assert_eq!((err.line, err.column), (Some(4), Some(1)));
+        assert_eq!(err.key.as_deref(), Some("licences"));
+
+        let err = error("name: a\nlink: https://example.com\ndescription: b\norder: first\n");
+        assert_eq!(err.line, Some(4));
+        assert_eq!(err.key.as_deref(), Some("order"));
+        assert_eq!(
+            err.message,
+            "invalid type: string \"first\", expected usize"
+        );
+        assert_eq!(
+            err.hint.as_deref(),
+            Some("this key expects a positive integer")
+        );
+
+        let err = error("name: a\ndescription: b\n");
+        assert_eq!(err.hint.as_deref(), Some("add `link: ...` to the file"));
+
+        // Errors about the whole asset aren't located, rather than at its first line
+        let err = error("name: a\nlink: github.com/user/a\ndescription: b\n");
+        assert_eq!((err.line, err.key.as_deref(), err.hint), (None, None, None));
+
+        let err = error("name: a\n  link: https://example.com\n");
+        assert_eq!(err.line, Some(2));
+        assert!(err.hint.unwrap().starts_with("check the YAML syntax"));
+    }
+
+    #[test]
+    fn parses_asset_files_in_any_format() {
+        let dir = std::env::temp_dir().join(format!(
+            "generate-assets-schema-formats-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let files = [
+            (
+                "a.toml",
+                "name = \"A\"\nlink = \"https://example.com/a\"\ndescription = \"b\"\nbevy_versions = [\"0.10\"]\n",
+            ),
+            (
+                "b.json",
+                "{\"name\": \"B\", \"link\": \"https://example.com/b\", \"description\": \"b\", \"bevy_versions\": [\"0.10\"]}",
+            ),
+            (
+                "c.yml",
+                "name: C\nlink: https://example.com/c\ndescription: b\nbevy_versions: [0.10]\n",
+            ),
+            ("_category.toml", "order = 1\n"),
+            ("notes.txt", "name = \"Not an asset\"\n"),
+        ];
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+
+        let assets = read_asset_files(&dir).unwrap();
+        let names: Vec<_> = assets.iter().map(|asset| asset.name.as_str()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+        // Unquoted in YAML, the version is still read as written
+        for asset in &assets {
+            assert_eq!(asset.bevy_versions, assets[0].bevy_versions);
+        }
+        assert!(validate_asset_files(&dir).unwrap().is_empty());
+
+        std::fs::write(dir.join("c.yml"), "name: C\n").unwrap();
+        let errors = validate_asset_files(&dir).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].hint.as_deref(),
+            Some("add `link: ...` to the file")
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn reads_any_encoding_of_asset_files() {
+        let dir = std::env::temp_dir().join(format!(
+            "generate-assets-schema-encodings-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let content = "name = \"Bévy\"\nlink = \"https://example.com\"\ndescription = \"b\"\n";
+        let utf16: Vec<u8> = content.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let files = [
+            (
+                "bom.toml",
+                [&[0xEF, 0xBB, 0xBF], content.as_bytes()].concat(),
+            ),
+            ("utf16.toml", [&[0xFF, 0xFE], &utf16[..]].concat()),
+            ("latin1.toml", b"name = \"B\xE9vy\"\n".to_vec()),
+        ];
+        for (name, bytes) in &files {
+            std::fs::write(dir.join(name), bytes).unwrap();
+        }
+
+        for name in ["bom.toml", "utf16.toml"] {
+            let asset = parse_asset_file(&dir.join(name)).unwrap();
+            assert_eq!(asset.name, "Bévy");
+        }
+        let err = parse_asset_file(&dir.join("latin1.toml")).unwrap_err();
+        assert!(
+            matches!(err, GenerateAssetsError::Encoding { ref path, .. } if path.ends_with("latin1.toml")),
+            "{:?}",
+            err
+        );
+        let err = anyhow::Error::from(err);
+        assert!(
+            format!("{:#}", err).ends_with("invalid UTF-8 at line 1, column 10"),
+            "{:#}",
+            err
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn locates_errors_inside_characters() {
+        assert_eq!(line_and_column("a\néé", 5), (2, 2));
+    }
+}
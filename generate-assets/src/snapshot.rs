@@ -0,0 +1,228 @@
+//! The assets of bevy-assets with their metadata, saved between the two phases of a run:
+//! scanning parses the asset files and enriches them with their metadata, and emitting writes
+//! the website from the [`Snapshot`], without parsing the asset files or any request.
+//!
+//! A snapshot can be emitted again after a change of the output, kept as a cache of the
+//! metadata, or compared with the snapshot of another run.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::Section;
+
+/// Version of the format of [`Snapshot`], increased on every breaking change.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Every section and asset read from an asset folder, with every field of the assets.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Snapshot {
+    pub version: u32,
+    /// The folder the assets were read from, which their images are copied from. Relative
+    /// paths are relative to the working directory of the scan, like the paths of the assets.
+    pub asset_dir: PathBuf,
+    /// Whether the metadata of the assets was gathered, see [`crate::enrich_assets`].
+    pub enriched: bool,
+    pub root: Section,
+}
+
+impl Snapshot {
+    /// The snapshot of the assets of `root`, read from `asset_dir` and not enriched yet.
+    pub fn new(asset_dir: impl Into<PathBuf>, root: Section) -> Self {
+        Snapshot {
+            version: SNAPSHOT_FORMAT_VERSION,
+            asset_dir: asset_dir.into(),
+            enriched: false,
+            root,
+        }
+    }
+
+    /// Reads the snapshot written to `path` by [`Snapshot::write`].
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Reading {}", path.display()))?;
+        let snapshot: Snapshot = serde_json::from_str(&content)
+            .with_context(|| format!("Parsing {}", path.display()))?;
+        if snapshot.version != SNAPSHOT_FORMAT_VERSION {
+            bail!(
+                "{} has version {} of the format, expected {SNAPSHOT_FORMAT_VERSION}",
+                path.display(),
+                snapshot.version
+            );
+        }
+        Ok(snapshot)
+    }
+
+    /// Writes the snapshot as pretty-printed JSON to `path`.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write(path, self.to_json()?).with_context(|| format!("Writing {}", path.display()))
+    }
+
+    /// The snapshot as pretty-printed JSON.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+}
+
+/// Serializes an [`Asset`](crate::Asset) of a snapshot: the fields of its file, along with
+/// the ones set while scanning and enriching it.
+pub(crate) mod asset {
+    use std::path::PathBuf;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{Asset, ExtraMetadata, RelatedAsset, RepositoryGone};
+
+    #[derive(Serialize)]
+    struct SnapshotAssetRef<'a> {
+        asset: &'a Asset,
+        original_path: &'a Option<PathBuf>,
+        slug: &'a str,
+        repository_gone: &'a Option<RepositoryGone>,
+        added: &'a Option<String>,
+        updated: &'a Option<String>,
+        aliases: &'a [String],
+        related: &'a [RelatedAsset],
+        fetched_metadata: &'a Option<ExtraMetadata>,
+        metadata_missing: bool,
+        source: &'a Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct SnapshotAsset {
+        asset: Asset,
+        original_path: Option<PathBuf>,
+        slug: String,
+        repository_gone: Option<RepositoryGone>,
+        added: Option<String>,
+        updated: Option<String>,
+        aliases: Vec<String>,
+        related: Vec<RelatedAsset>,
+        fetched_metadata: Option<ExtraMetadata>,
+        metadata_missing: bool,
+        // Missing from the snapshots written before sources
+        #[serde(default)]
+        source: Option<String>,
+    }
+
+    pub fn serialize<S: Serializer>(asset: &Asset, serializer: S) -> Result<S::Ok, S::Error> {
+        SnapshotAssetRef {
+            asset,
+            original_path: &asset.original_path,
+            slug: &asset.slug,
+            repository_gone: &asset.repository_gone,
+            added: &asset.added,
+            updated: &asset.updated,
+            aliases: &asset.aliases,
+            related: &asset.related,
+            fetched_metadata: &asset.fetched_metadata,
+            metadata_missing: asset.metadata_missing,
+            source: &asset.source,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Asset, D::Error> {
+        let snapshot = SnapshotAsset::deserialize(deserializer)?;
+        Ok(Asset {
+            original_path: snapshot.original_path,
+            slug: snapshot.slug,
+            repository_gone: snapshot.repository_gone,
+            added: snapshot.added,
+            updated: snapshot.updated,
+            aliases: snapshot.aliases,
+            related: snapshot.related,
+            fetched_metadata: snapshot.fetched_metadata,
+            metadata_missing: snapshot.metadata_missing,
+            source: snapshot.source,
+            ..snapshot.asset
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        scan_assets, walk::SymlinkPolicy, AssetNode, ErrorMode, ExtraMetadata, RepositoryGone,
+    };
+
+    fn fixture_snapshot() -> Snapshot {
+        let asset_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures/assets");
+        let root = scan_assets(
+            &asset_dir,
+            &Default::default(),
+            SymlinkPolicy::Follow,
+            ErrorMode::FailFast,
+            None,
+        )
+        .unwrap();
+        Snapshot::new(asset_dir, root)
+    }
+
+    fn set_generated_fields(section: &mut Section) {
+        for node in &mut section.content {
+            match node {
+                AssetNode::Section(section) => set_generated_fields(section),
+                AssetNode::Asset(asset) => {
+                    asset.added = Some("2024-01-02".to_string());
+                    asset.aliases = vec!["/assets/old/".to_string()];
+                    asset.repository_gone = Some(RepositoryGone { status: Some(404) });
+                    asset.fetched_metadata = Some(ExtraMetadata {
+                        license: Some("MIT".to_string()),
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips_every_field() {
+        let mut snapshot = fixture_snapshot();
+        snapshot.enriched = true;
+        snapshot.root.set_related();
+        set_generated_fields(&mut snapshot.root);
+
+        let json = snapshot.to_json().unwrap();
+        let read: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.to_json().unwrap(), json);
+        assert!(read.enriched);
+        assert_eq!(read.root.assets().len(), snapshot.root.assets().len());
+        for asset in read.root.assets() {
+            assert!(asset.original_path.is_some(), "{}", asset.name);
+            assert!(!asset.slug.is_empty(), "{}", asset.name);
+            assert_eq!(asset.added.as_deref(), Some("2024-01-02"));
+            assert_eq!(
+                asset.repository_gone,
+                Some(RepositoryGone { status: Some(404) })
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let path = std::env::temp_dir().join(format!(
+            "generate-assets-snapshot-version-{}.json",
+            std::process::id()
+        ));
+        let mut snapshot = fixture_snapshot();
+        snapshot.write(&path).unwrap();
+        assert!(Snapshot::read(&path).is_ok());
+
+        snapshot.version = SNAPSHOT_FORMAT_VERSION + 1;
+        snapshot.write(&path).unwrap();
+        let err = Snapshot::read(&path).unwrap_err();
+        assert!(
+            err.to_string().contains("version 2 of the format"),
+            "{:#}",
+            err
+        );
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,244 @@
+//! Lists of assets from other places than the asset folder, declared with `[[sources]]`
+//! tables in the configuration and merged into its sections, to experiment with lists of
+//! assets maintained outside of bevy-assets.
+//!
+//! A source is either a git repository laid out like the asset folder, or a file at a URL
+//! listing assets by the path of their section, in TOML, JSON or YAML from its extension, JSON
+//! if it has none:
+//!
+//! ```json
+//! {
+//!     "Assets/2D": [
+//!         { "name": "...", "link": "...", "description": "..." }
+//!     ]
+//! }
+//! ```
+//!
+//! Sources are fetched into the [`SOURCES_DIR`] folder of the cache folder, where the assets
+//! listed in a file are written as asset files, so every source is scanned like the asset
+//! folder. Offline, the copies fetched by a previous run are read instead. The assets of a
+//! source keep its name in [`Asset::source`], and are listed in the sections of the same name
+//! as the ones of the asset folder.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    config::SourceConfig, git::git, http::HttpContext, schema::AssetFormat, slugify, Asset,
+};
+
+/// Folder of the cache folder the sources are fetched to, each in a folder named after it.
+pub const SOURCES_DIR: &str = "sources";
+
+/// The content of a file listing assets, by the path of their section like `Assets/2D`.
+type SourceFile = BTreeMap<String, Vec<Asset>>;
+
+/// Fetches `source` into `cache_dir`, returning the folder its assets can be scanned from.
+///
+/// Without `http`, nothing is fetched and the folder fetched by a previous run is returned.
+pub fn fetch_source(
+    source: &SourceConfig,
+    cache_dir: &Path,
+    http: Option<&HttpContext>,
+) -> anyhow::Result<PathBuf> {
+    let dir = cache_dir.join(SOURCES_DIR).join(&source.name);
+    let Some(http) = http else {
+        if !dir.is_dir() {
+            bail!(
+                "The source {} wasn't fetched to {} by a previous run",
+                source.name,
+                dir.display()
+            );
+        }
+        return Ok(dir);
+    };
+
+    let _span = tracing::info_span!("source", source = %source.name).entered();
+    match (&source.git, &source.url) {
+        (Some(repository), _) => fetch_repository(repository, source.rev.as_deref(), &dir),
+        (None, Some(url)) => fetch_file(url, http, &dir),
+        (None, None) => bail!("The source {} has neither `git` nor `url`", source.name),
+    }
+    .with_context(|| format!("Fetching the source {}", source.name))?;
+    Ok(dir)
+}
+
+/// Checks out the revision `rev` of `repository`, or its default branch, in `dir`, reusing
+/// the clone of a previous run. Only the last commit is fetched.
+fn fetch_repository(repository: &str, rev: Option<&str>, dir: &Path) -> anyhow::Result<()> {
+    if !dir.join(".git").is_dir() {
+        if dir.exists() {
+            fs::remove_dir_all(dir).with_context(|| format!("Removing {}", dir.display()))?;
+        }
+        fs::create_dir_all(dir).with_context(|| format!("Creating {}", dir.display()))?;
+        git(dir, &["init", "--quiet"])?;
+    }
+    tracing::info!("Fetching {repository}");
+    git(
+        dir,
+        &[
+            "fetch",
+            "--quiet",
+            "--depth",
+            "1",
+            repository,
+            rev.unwrap_or("HEAD"),
+        ],
+    )?;
+    git(dir, &["checkout", "--quiet", "--force", "FETCH_HEAD"])?;
+    Ok(())
+}
+
+/// Downloads the file listing assets at `url`, and writes its assets to `dir` as asset files.
+fn fetch_file(url: &str, http: &HttpContext, dir: &Path) -> anyhow::Result<()> {
+    let format = url::Url::parse(url)
+        .ok()
+        .and_then(|url| AssetFormat::from_path(Path::new(url.path())))
+        .unwrap_or(AssetFormat::Json);
+    tracing::info!("Downloading {url}");
+    let content = http
+        .agent()
+        .get(url)
+        .call()
+        .with_context(|| format!("Downloading {url}"))?
+        .into_string()
+        .with_context(|| format!("Reading {url}"))?;
+
+    // Kept next to the folder, so schema errors point at the downloaded file
+    let extension = match format {
+        AssetFormat::Toml => "toml",
+        AssetFormat::Json => "json",
+        AssetFormat::Yaml => "yaml",
+    };
+    let path = dir.with_extension(extension);
+    fs::create_dir_all(dir.parent().unwrap_or(dir))
+        .with_context(|| format!("Creating {}", dir.display()))?;
+    fs::write(&path, &content).with_context(|| format!("Writing {}", path.display()))?;
+    let file: SourceFile = format.parse(&path, &content)?;
+    write_asset_files(&file, dir)
+}
+
+/// Writes the assets of `file` to `dir`, replacing its content, as JSON files in the folders
+/// of their sections.
+fn write_asset_files(file: &SourceFile, dir: &Path) -> anyhow::Result<()> {
+    for section in file.keys() {
+        let is_relative = Path::new(section)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if !is_relative {
+            bail!("The section {section:?} must be a relative path, like \"Assets/2D\"");
+        }
+    }
+    if dir.exists() {
+        fs::remove_dir_all(dir).with_context(|| format!("Removing {}", dir.display()))?;
+    }
+    for (section, assets) in file {
+        let section_dir = dir.join(section);
+        fs::create_dir_all(&section_dir)
+            .with_context(|| format!("Creating {}", section_dir.display()))?;
+
+        let mut names = HashSet::new();
+        for asset in assets {
+            let slug = slugify(&asset.name);
+            let mut name = slug.clone();
+            let mut suffix = 1;
+            while !names.insert(name.clone()) {
+                suffix += 1;
+                name = format!("{slug}-{suffix}");
+            }
+            let path = section_dir.join(format!("{name}.json"));
+            fs::write(&path, serde_json::to_string_pretty(asset)? + "\n")
+                .with_context(|| format!("Writing {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scan_assets, walk::SymlinkPolicy, ErrorMode};
+
+    #[test]
+    fn merges_sources_into_the_asset_folder() {
+        let dir =
+            std::env::temp_dir().join(format!("generate-assets-sources-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let asset = |name: &str| {
+            format!(
+                "name = \"{name}\"\nlink = \"https://example.com/{name}\"\ndescription = \"d\"\n"
+            )
+        };
+        fs::create_dir_all(dir.join("assets/Assets/2D")).unwrap();
+        fs::write(dir.join("assets/Assets/2D/local.toml"), asset("Local")).unwrap();
+
+        let file: SourceFile = toml::from_str(&format!(
+            "[[\"Assets/2D\"]]\n{}\n[[\"Assets/2D\"]]\n{}\n[[\"Assets/Jam\"]]\n{}",
+            asset("Remote"),
+            asset("Remote"),
+            asset("Jam"),
+        ))
+        .unwrap();
+        let source_dir = dir.join("cache").join(SOURCES_DIR).join("jam");
+        write_asset_files(&file, &source_dir).unwrap();
+        assert!(source_dir.join("Assets/2D/remote-2.json").is_file());
+        let file: SourceFile =
+            toml::from_str(&format!("[[\"../Assets\"]]\n{}", asset("A"))).unwrap();
+        assert!(write_asset_files(&file, &source_dir).is_err());
+
+        let scan = |dir: &Path| {
+            scan_assets(
+                dir,
+                &Default::default(),
+                SymlinkPolicy::Follow,
+                ErrorMode::FailFast,
+                None,
+            )
+            .unwrap()
+        };
+        let mut root = scan(&dir.join("assets"));
+        root.merge_source("jam", scan(&source_dir));
+
+        let assets: Vec<_> = root
+            .assets()
+            .into_iter()
+            .map(|asset| {
+                (
+                    asset.name.as_str(),
+                    asset.slug.as_str(),
+                    asset.source.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            assets,
+            [
+                ("Local", "local", None),
+                ("Remote", "remote", Some("jam")),
+                ("Remote", "remote-2", Some("jam")),
+                ("Jam", "jam", Some("jam")),
+            ]
+        );
+        assert_eq!(root.content.len(), 1);
+
+        let cache_dir = dir.join("cache");
+        let source = SourceConfig {
+            name: "jam".to_string(),
+            git: None,
+            rev: None,
+            url: Some("https://example.com/jam.json".to_string()),
+        };
+        assert_eq!(fetch_source(&source, &cache_dir, None).unwrap(), source_dir);
+        let missing = SourceConfig {
+            name: "missing".to_string(),
+            ..source
+        };
+        assert!(fetch_source(&missing, &cache_dir, None).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,224 @@
+//! Validation of the SPDX license expressions set in asset files, against the SPDX license
+//! list of the [`spdx`](::spdx) crate, see <https://spdx.org/licenses/>.
+
+/// The licenses commonly used in the Rust and game development ecosystems, preferred when
+/// suggesting the identifier an invalid license was meant to be.
+const COMMON_LICENSES: &[&str] = &[
+    "0BSD",
+    "AGPL-3.0-only",
+    "AGPL-3.0-or-later",
+    "Apache-2.0",
+    "Artistic-2.0",
+    "BSD-1-Clause",
+    "BSD-2-Clause",
+    "BSD-3-Clause",
+    "BSL-1.0",
+    "CC-BY-4.0",
+    "CC-BY-NC-4.0",
+    "CC-BY-NC-SA-4.0",
+    "CC-BY-SA-4.0",
+    "CC0-1.0",
+    "EPL-2.0",
+    "EUPL-1.2",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "ISC",
+    "LGPL-2.1-only",
+    "LGPL-2.1-or-later",
+    "LGPL-3.0-only",
+    "LGPL-3.0-or-later",
+    "MIT",
+    "MIT-0",
+    "MPL-2.0",
+    "OFL-1.1",
+    "Unlicense",
+    "WTFPL",
+    "Zlib",
+];
+
+/// The operators of SPDX license expressions, in the case they must be written in.
+const OPERATORS: &[&str] = &["OR", "AND", "WITH"];
+
+/// Whether `license` is a valid SPDX license expression, like `MIT`, `MIT OR Apache-2.0`,
+/// `Apache-2.0 WITH LLVM-exception` or a custom `LicenseRef-Proprietary` license.
+///
+/// Deprecated identifiers like `GPL-3.0` are accepted, as they still are by crates.io.
+pub fn is_valid_license(license: &str) -> bool {
+    ::spdx::Expression::parse(license).is_ok()
+}
+
+/// The SPDX identifier written as `key` in any case, like `Apache-2.0` for `apache-2.0`.
+///
+/// The deprecated identifiers of the GNU licenses are replaced by their current ones, like
+/// `GPL-3.0-only` for `gpl-3.0` and `GPL-3.0-or-later` for `gpl-3.0+`.
+pub fn spdx_identifier(key: &str) -> Option<&'static str> {
+    let find = |key: &str| {
+        ::spdx::identifiers::LICENSES
+            .iter()
+            .find(|(name, ..)| name.eq_ignore_ascii_case(key))
+    };
+    let (base, suffix) = match key.strip_suffix('+') {
+        Some(base) => (base, "-or-later"),
+        None => (key, "-only"),
+    };
+    match find(base)? {
+        (name, _, flags) if flags & ::spdx::identifiers::IS_DEPRECATED != 0 => {
+            find(&format!("{name}{suffix}"))
+                .map(|(name, ..)| *name)
+                .or(Some(*name))
+        }
+        (name, ..) if base == key => Some(*name),
+        // Only the GNU licenses have an identifier for their later versions
+        _ => None,
+    }
+}
+
+/// Every SPDX license identifier, the common ones first.
+fn all_licenses() -> impl Iterator<Item = &'static str> {
+    COMMON_LICENSES.iter().copied().chain(
+        ::spdx::identifiers::LICENSES
+            .iter()
+            .map(|(name, ..)| *name)
+            .filter(|name| !COMMON_LICENSES.contains(name)),
+    )
+}
+
+/// Suggests the SPDX identifier closest to an invalid `license`, like `Apache-2.0`
+/// for `Apache 2.0` or `MIT` for `MIT License`.
+///
+/// Expressions combining licenses aren't suggested for, as they aren't one misspelled
+/// identifier.
+pub fn suggest_license(license: &str) -> Option<&'static str> {
+    if license
+        .split_whitespace()
+        .any(|word| OPERATORS.iter().any(|op| word.eq_ignore_ascii_case(op)))
+    {
+        return None;
+    }
+    let normalized = normalize(license);
+
+    if let Some(exact) = all_licenses().find(|candidate| normalize(candidate) == normalized) {
+        return Some(exact);
+    }
+
+    COMMON_LICENSES
+        .iter()
+        .map(|candidate| {
+            (
+                strsim::jaro_winkler(&normalize(candidate), &normalized),
+                *candidate,
+            )
+        })
+        .filter(|(similarity, _)| *similarity >= 0.8)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+/// Normalizes the licenses of an asset into a sorted list of SPDX identifiers, so assets
+/// can be grouped by license.
+///
+/// Expressions like `MIT OR Apache-2.0` or the older `MIT/Apache-2.0` are split, and
+/// identifiers written differently, like `Apache 2.0`, are replaced by their SPDX
+/// identifier. Unknown licenses are kept as is.
+pub fn normalize_licenses(licenses: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = licenses
+        .iter()
+        .flat_map(|license| license.split(" OR ").flat_map(|part| part.split('/')))
+        .map(|license| license.trim().trim_matches(|c| c == '(' || c == ')').trim())
+        .filter(|license| !license.is_empty())
+        .map(|license| {
+            if is_valid_license(license) {
+                return license.to_string();
+            }
+            let normalized = normalize(license);
+            all_licenses()
+                .find(|candidate| normalize(candidate) == normalized)
+                .map_or_else(|| license.to_string(), |exact| exact.to_string())
+        })
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+/// Lowercases a license and removes the separators and words that are often added to it.
+fn normalize(license: &str) -> String {
+    let lowercase = license.to_lowercase();
+    let lowercase = lowercase
+        .trim()
+        .trim_end_matches("licence")
+        .trim_end_matches("license")
+        .trim();
+    let lowercase = lowercase.strip_prefix("the ").unwrap_or(lowercase);
+
+    lowercase
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_spdx_identifiers() {
+        assert!(is_valid_license("MIT"));
+        assert!(is_valid_license("Apache-2.0"));
+        assert!(is_valid_license("LicenseRef-Proprietary"));
+        assert!(is_valid_license("GPL-3.0"));
+        for license in ["CC-BY-3.0", "Unicode-DFS-2016", "MPL-1.1", "OFL-1.1-RFN"] {
+            assert!(is_valid_license(license), "{}", license);
+        }
+        assert!(!is_valid_license("Apache 2.0"));
+        assert!(!is_valid_license("mit"));
+    }
+
+    #[test]
+    fn accepts_spdx_expressions() {
+        assert!(is_valid_license("MIT OR Apache-2.0"));
+        assert!(is_valid_license("(MIT OR Apache-2.0) AND CC-BY-4.0"));
+        assert!(is_valid_license("Apache-2.0 WITH LLVM-exception"));
+        assert!(!is_valid_license("MIT or Apache-2.0"));
+        assert!(!is_valid_license("MIT/Apache-2.0"));
+        assert_eq!(suggest_license("MIT or Apache 2.0"), None);
+    }
+
+    #[test]
+    fn reads_identifiers_in_any_case() {
+        assert_eq!(spdx_identifier("apache-2.0"), Some("Apache-2.0"));
+        assert_eq!(spdx_identifier("gpl-3.0"), Some("GPL-3.0-only"));
+        assert_eq!(spdx_identifier("gpl-3.0+"), Some("GPL-3.0-or-later"));
+        assert_eq!(spdx_identifier("lgpl-2.1"), Some("LGPL-2.1-only"));
+        assert_eq!(spdx_identifier("agpl-3.0"), Some("AGPL-3.0-only"));
+        assert_eq!(spdx_identifier("mit+"), None);
+        assert_eq!(
+            spdx_identifier("unicode-dfs-2016"),
+            Some("Unicode-DFS-2016")
+        );
+        assert_eq!(spdx_identifier("other"), None);
+    }
+
+    #[test]
+    fn suggests_closest_identifier() {
+        assert_eq!(suggest_license("Apache 2.0"), Some("Apache-2.0"));
+        assert_eq!(suggest_license("MIT License"), Some("MIT"));
+        assert_eq!(suggest_license("The Unlicense"), Some("Unlicense"));
+        assert_eq!(suggest_license("BSD3"), Some("BSD-3-Clause"));
+        assert_eq!(suggest_license("Proprietary"), None);
+        assert_eq!(suggest_license("MPL 1.1"), Some("MPL-1.1"));
+    }
+
+    #[test]
+    fn normalizes_license_expressions() {
+        let licenses = |licenses: &[&str]| -> Vec<String> {
+            normalize_licenses(&licenses.iter().map(|l| l.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(licenses(&["MIT", "Apache-2.0"]), ["Apache-2.0", "MIT"]);
+        assert_eq!(licenses(&["(MIT OR Apache 2.0)"]), ["Apache-2.0", "MIT"]);
+        assert_eq!(licenses(&["MIT/Apache-2.0", "MIT"]), ["Apache-2.0", "MIT"]);
+        assert_eq!(licenses(&["Proprietary"]), ["Proprietary"]);
+    }
+}
@@ -0,0 +1,166 @@
+//! Aggregate statistics about the ecosystem, for the stats page and release blog posts.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::{bevy_releases::BevyVersion, index::AssetIndex};
+
+/// Statistics about every asset, written as `stats.json`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    /// Total number of assets.
+    pub assets: usize,
+    /// Number of assets in each section, including its subsections, by path like `Assets/2D`.
+    pub sections: BTreeMap<String, usize>,
+    /// Number of assets under each license, an asset with several licenses counting for each.
+    pub licenses: BTreeMap<String, usize>,
+    /// Number of assets without any license.
+    pub without_license: usize,
+    /// Number of assets supporting each Bevy version, from `main` to the oldest release.
+    pub bevy_versions: Vec<VersionCount>,
+    /// Number of assets added each month, from the oldest month.
+    pub growth: Vec<MonthCount>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct VersionCount {
+    pub version: BevyVersion,
+    pub assets: usize,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct MonthCount {
+    /// Month, as `YYYY-MM`.
+    pub month: String,
+    /// Number of assets added this month.
+    pub added: usize,
+    /// Number of assets at the end of the month.
+    pub total: usize,
+}
+
+impl Statistics {
+    /// Computes statistics about the assets of `index`.
+    pub fn new(index: &AssetIndex) -> Self {
+        let mut sections: BTreeMap<String, usize> = BTreeMap::new();
+        let mut licenses: BTreeMap<String, usize> = BTreeMap::new();
+        let mut without_license = 0;
+        for asset in &index.assets {
+            for depth in 1..=asset.sections.len() {
+                *sections
+                    .entry(asset.sections[..depth].join("/"))
+                    .or_default() += 1;
+            }
+            for license in &asset.licenses {
+                *licenses.entry(license.clone()).or_default() += 1;
+            }
+            if asset.licenses.is_empty() {
+                without_license += 1;
+            }
+        }
+
+        let bevy_versions = index
+            .bevy_versions()
+            .into_iter()
+            .map(|listing| VersionCount {
+                version: listing.version,
+                assets: listing.assets.len(),
+            })
+            .collect();
+
+        let mut added_per_month: BTreeMap<&str, usize> = BTreeMap::new();
+        for asset in &index.assets {
+            if let Some(month) = asset.added.as_ref().and_then(|date| date.get(..7)) {
+                *added_per_month.entry(month).or_default() += 1;
+            }
+        }
+        let mut total = 0;
+        let growth = added_per_month
+            .into_iter()
+            .map(|(month, added)| {
+                total += added;
+                MonthCount {
+                    month: month.to_string(),
+                    added,
+                    total,
+                }
+            })
+            .collect();
+
+        Statistics {
+            assets: index.assets.len(),
+            sections,
+            licenses,
+            without_license,
+            bevy_versions,
+            growth,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::IndexedAsset;
+
+    fn asset(sections: &[&str], licenses: &[&str], versions: &[&str], added: &str) -> IndexedAsset {
+        IndexedAsset {
+            sections: sections.iter().map(|s| s.to_string()).collect(),
+            licenses: licenses.iter().map(|l| l.to_string()).collect(),
+            bevy_versions: versions.iter().map(|v| v.parse().unwrap()).collect(),
+            added: Some(added.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_assets() {
+        let index = AssetIndex {
+            version: 1,
+            assets: vec![
+                asset(
+                    &["Assets", "2D"],
+                    &["MIT", "Apache-2.0"],
+                    &["0.12"],
+                    "2023-01-10",
+                ),
+                asset(
+                    &["Assets", "3D"],
+                    &["MIT"],
+                    &["0.12.1", "0.11"],
+                    "2023-01-20",
+                ),
+                asset(&["Games"], &[], &[], "2023-03-01"),
+            ],
+        };
+        let stats = Statistics::new(&index);
+
+        assert_eq!(stats.assets, 3);
+        assert_eq!(stats.sections["Assets"], 2);
+        assert_eq!(stats.sections["Assets/2D"], 1);
+        assert_eq!(stats.licenses["MIT"], 2);
+        assert_eq!(stats.without_license, 1);
+        assert_eq!(
+            stats.bevy_versions[0],
+            VersionCount {
+                version: BevyVersion::release(0, 12),
+                assets: 2,
+            }
+        );
+        assert_eq!(
+            stats.growth,
+            [
+                MonthCount {
+                    month: "2023-01".to_string(),
+                    added: 2,
+                    total: 2,
+                },
+                MonthCount {
+                    month: "2023-03".to_string(),
+                    added: 1,
+                    total: 3,
+                },
+            ]
+        );
+    }
+}
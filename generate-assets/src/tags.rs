@@ -0,0 +1,82 @@
+//! Validation of the tags set in asset files.
+
+/// Tags assets can use, letting the website group them across sections. Tags are lowercase,
+/// with words separated by `-`. Add new tags here.
+pub const TAGS: &[&str] = &[
+    "2d",
+    "3d",
+    "ai",
+    "animation",
+    "assets",
+    "audio",
+    "camera",
+    "debugging",
+    "diagnostics",
+    "ecs",
+    "editor",
+    "input",
+    "lighting",
+    "localization",
+    "mobile",
+    "networking",
+    "particles",
+    "pathfinding",
+    "persistence",
+    "physics",
+    "procedural-generation",
+    "rendering",
+    "scripting",
+    "shaders",
+    "terrain",
+    "text",
+    "tilemap",
+    "ui",
+    "web",
+    "xr",
+];
+
+/// Whether `tag` is one of [`TAGS`].
+pub fn is_known_tag(tag: &str) -> bool {
+    TAGS.contains(&tag)
+}
+
+/// Suggests the known tag closest to an unknown `tag`, like `physics` for `Physics`
+/// or `procedural-generation` for `procedural generation`.
+pub fn suggest_tag(tag: &str) -> Option<&'static str> {
+    let normalized = normalize(tag);
+
+    if let Some(exact) = TAGS.iter().find(|candidate| **candidate == normalized) {
+        return Some(exact);
+    }
+
+    TAGS.iter()
+        .map(|candidate| (strsim::jaro_winkler(candidate, &normalized), *candidate))
+        .filter(|(similarity, _)| *similarity >= 0.85)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, candidate)| candidate)
+}
+
+fn normalize(tag: &str) -> String {
+    tag.trim()
+        .to_lowercase()
+        .replace(|c: char| c.is_whitespace() || c == '_', "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_known_tags() {
+        assert!(is_known_tag("physics"));
+        assert!(!is_known_tag("Physics"));
+
+        assert_eq!(suggest_tag("Physics"), Some("physics"));
+        assert_eq!(
+            suggest_tag("procedural generation"),
+            Some("procedural-generation")
+        );
+        assert_eq!(suggest_tag("netwroking"), Some("networking"));
+        assert_eq!(suggest_tag("cooking"), None);
+    }
+}
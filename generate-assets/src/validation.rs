@@ -0,0 +1,911 @@
+//! Checks that assets follow the rules of bevy-assets, beyond matching the schema of their
+//! files, like descriptions being short enough and images being readable.
+//!
+//! Problems are either [`ValidationError`]s or [`ValidationWarning`]s, each with the
+//! identifier of the rule it breaks. Some errors come with a [`Fix`] for the asset file.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    bevy_releases::{suggest_version, BEVY_MAIN, BEVY_RELEASES},
+    contact::{contact_link, discord_invite, mastodon_profile, ContactError},
+    demo::{Demo, DemoError},
+    links::{canonical_link, normalize_link, tracking_parameters},
+    platforms::{is_known_platform, suggest_platform, PLATFORMS},
+    slugify,
+    spdx::{is_valid_license, suggest_license},
+    tags::{is_known_tag, suggest_tag},
+    write_back::Fix,
+    Asset, AssetField, AssetNode, DuplicatesConfig, FeaturedConfig, Icon, PricingModel, Section,
+    DUPLICATES_FILE,
+};
+
+pub const MAX_DESCRIPTION_LENGTH: usize = 100;
+pub const MIN_DESCRIPTION_LENGTH: usize = 10;
+/// Openings that only repeat that the asset is made for Bevy.
+const DESCRIPTION_BOILERPLATE: &[&str] = &[
+    "a bevy plugin",
+    "a plugin for bevy",
+    "bevy plugin",
+    "this is a",
+    "this plugin",
+    "this crate",
+];
+pub const DEFAULT_MAX_IMAGE_BYTES: u64 = 2_097_152; // keep in sync with docs in bevy-assets
+pub const ALLOWED_IMAGE_EXTENSIONS: &[&str] = &["gif", "jpg", "jpeg", "png", "webp"];
+pub const MIN_IMAGE_DIMENSION: u32 = 64;
+pub const MAX_IMAGE_DIMENSION: u32 = 4096;
+
+/// How serious a problem is, ordered from least to most serious.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Rules checked by the validation, configured from the command line and the
+/// `_category.toml` files.
+#[derive(Clone)]
+pub struct Rules<'a> {
+    pub max_image_bytes: u64,
+    /// Fields assets must set, with the name of the section requiring them.
+    pub required_fields: Vec<(AssetField, String)>,
+    /// Names of every asset, which assets can name as their successor or in their
+    /// relationships.
+    pub asset_names: &'a HashSet<String>,
+}
+
+/// Problems found in a single asset file. Only errors fail the validation.
+#[derive(Debug)]
+pub struct AssetReport {
+    pub asset_name: String,
+    pub path: PathBuf,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// A problem failing the validation of an asset.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValidationError {
+    DescriptionTooLong,
+    DescriptionWithFormatting,
+    ImageInvalidLink,
+    ImageOutsideAssetDirectory,
+    ImageInvalidExtension,
+    ImageFileSizeTooLarge { path: PathBuf, size: u64, max: u64 },
+    ImageUnreadable,
+    ImageDimensionsTooSmall(u32, u32),
+    ImageDimensionsTooLarge(u32, u32),
+    LinkWithTrackingParameters(Vec<String>, String),
+    InvalidLicense(String, Option<&'static str>),
+    UnknownBevyVersion(String, Option<&'static str>),
+    UnknownTag(String, Option<&'static str>),
+    UnknownPlatform(String, Option<&'static str>),
+    DeprecationWithoutReason,
+    UnknownSuccessor(String),
+    UnknownRelatedAsset(&'static str, String),
+    InvalidDemo(DemoError),
+    InvalidContact(ContactError),
+    InvalidIcon,
+    PaidWithoutPrice,
+    FreeWithPrice,
+    MissingRequiredField(&'static str, String),
+}
+impl ValidationError {
+    /// The change to the file of `asset` fixing this error, when it can be guessed.
+    pub fn fix(&self, asset: &Asset) -> Option<Fix> {
+        match self {
+            ValidationError::InvalidLicense(license, Some(suggestion)) => Some(Fix::Replace {
+                key: "licenses",
+                old: license.clone(),
+                new: (*suggestion).to_string(),
+            }),
+            ValidationError::ImageInvalidLink => {
+                let image = asset.image.as_deref()?;
+                let found = asset
+                    .original_path
+                    .as_deref()
+                    .and_then(|path| find_image(path, image));
+                Some(match found {
+                    Some(found) => Fix::Set {
+                        key: "image",
+                        value: found,
+                    },
+                    None => Fix::Remove { key: "image" },
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Identifier of the check that failed, like `description-too-long`.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            ValidationError::DescriptionTooLong => "description-too-long",
+            ValidationError::DescriptionWithFormatting => "description-formatting",
+            ValidationError::ImageInvalidLink => "image-not-found",
+            ValidationError::ImageOutsideAssetDirectory => "image-outside-asset-directory",
+            ValidationError::ImageInvalidExtension => "image-extension",
+            ValidationError::ImageFileSizeTooLarge { .. } => "image-file-size",
+            ValidationError::ImageUnreadable => "image-unreadable",
+            ValidationError::ImageDimensionsTooSmall(..) => "image-too-small",
+            ValidationError::ImageDimensionsTooLarge(..) => "image-too-large",
+            ValidationError::LinkWithTrackingParameters(..) => "link-tracking-parameters",
+            ValidationError::InvalidLicense(..) => "license-spdx",
+            ValidationError::UnknownBevyVersion(..) => "bevy-version",
+            ValidationError::UnknownTag(..) => "unknown-tag",
+            ValidationError::UnknownPlatform(..) => "unknown-platform",
+            ValidationError::DeprecationWithoutReason | ValidationError::UnknownSuccessor(_) => {
+                "deprecation"
+            }
+            ValidationError::UnknownRelatedAsset(..) => "related-asset",
+            ValidationError::InvalidDemo(_) => "demo",
+            ValidationError::InvalidContact(_) => "contact",
+            ValidationError::InvalidIcon => "icon",
+            ValidationError::PaidWithoutPrice | ValidationError::FreeWithPrice => "pricing",
+            ValidationError::MissingRequiredField(..) => "required-field",
+        }
+    }
+}
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::DescriptionTooLong => write!(
+                f,
+                "Description must be at most {} chars in length.",
+                MAX_DESCRIPTION_LENGTH
+            ),
+            ValidationError::DescriptionWithFormatting => {
+                write!(f, "Description must not contain formatting.")
+            }
+            ValidationError::ImageInvalidLink => write!(f, "Image file not found."),
+            ValidationError::ImageOutsideAssetDirectory => {
+                write!(f, "Image must be in the same directory as the asset file.")
+            }
+            ValidationError::ImageInvalidExtension => write!(
+                f,
+                "Image extension not allowed. Must be one of: {}",
+                ALLOWED_IMAGE_EXTENSIONS.join(", ")
+            ),
+            ValidationError::ImageFileSizeTooLarge { path, size, max } => write!(
+                f,
+                "Image file {} is {} bytes, which exceeds the maximum of {} bytes.",
+                path.display(),
+                size,
+                max
+            ),
+            ValidationError::ImageUnreadable => {
+                write!(
+                    f,
+                    "Image dimensions could not be read, is it a valid image?"
+                )
+            }
+            ValidationError::ImageDimensionsTooSmall(width, height) => write!(
+                f,
+                "Image dimensions {}x{} are below the minimum {}x{} pixels.",
+                width, height, MIN_IMAGE_DIMENSION, MIN_IMAGE_DIMENSION
+            ),
+            ValidationError::ImageDimensionsTooLarge(width, height) => write!(
+                f,
+                "Image dimensions {}x{} exceed the maximum {}x{} pixels.",
+                width, height, MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION
+            ),
+            ValidationError::LinkWithTrackingParameters(parameters, link) => write!(
+                f,
+                "Link must not contain tracking parameters ({}), use {} instead.",
+                parameters.join(", "),
+                link
+            ),
+            ValidationError::InvalidLicense(license, suggestion) => {
+                write!(f, "License \"{}\" is not an SPDX identifier", license)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean \"{}\"?", suggestion),
+                    None => write!(
+                        f,
+                        ", see https://spdx.org/licenses/ or use a `LicenseRef-` prefix for custom licenses."
+                    ),
+                }
+            }
+            ValidationError::MissingRequiredField(field, section) => write!(
+                f,
+                "Field `{}` is required for assets in section {}.",
+                field, section
+            ),
+            ValidationError::UnknownBevyVersion(version, suggestion) => {
+                write!(
+                    f,
+                    "Bevy version \"{}\" must be a minor release like \"{}\", or \"{}\"",
+                    version,
+                    BEVY_RELEASES.last().unwrap(),
+                    BEVY_MAIN
+                )?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean \"{}\"?", suggestion),
+                    None => write!(f, "."),
+                }
+            }
+            ValidationError::DeprecationWithoutReason => {
+                write!(f, "Deprecated assets must explain why in `reason`.")
+            }
+            ValidationError::UnknownSuccessor(successor) => write!(
+                f,
+                "Successor \"{}\" must be the name of another asset or a link.",
+                successor
+            ),
+            ValidationError::UnknownPlatform(platform, suggestion) => {
+                write!(f, "Platform \"{}\" is unknown", platform)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean \"{}\"?", suggestion),
+                    None => write!(f, ", it must be one of: {}.", PLATFORMS.join(", ")),
+                }
+            }
+            ValidationError::UnknownRelatedAsset(field, name) => write!(
+                f,
+                "Asset \"{}\" in `{}` must be the name of another asset.",
+                name, field
+            ),
+            ValidationError::InvalidDemo(err) => write!(f, "{err}"),
+            ValidationError::InvalidContact(err) => write!(f, "{err}"),
+            ValidationError::PaidWithoutPrice => {
+                write!(f, "Paid assets must set their `price`.")
+            }
+            ValidationError::FreeWithPrice => write!(
+                f,
+                "Free assets can't set a `price`, use the `freemium` model for free assets \
+                with paid features."
+            ),
+            ValidationError::InvalidIcon => write!(
+                f,
+                "Icon must be an emoji, or an image in the same directory as the asset file \
+                with one of these extensions: {}",
+                ALLOWED_IMAGE_EXTENSIONS.join(", ")
+            ),
+            ValidationError::UnknownTag(tag, suggestion) => {
+                write!(f, "Tag \"{}\" is unknown", tag)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, ", did you mean \"{}\"?", suggestion),
+                    None => write!(f, ", see the list of tags in generate-assets/src/tags.rs."),
+                }
+            }
+        }
+    }
+}
+
+/// Assets that should be unique but aren't, found by comparing assets with each other.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DuplicateError {
+    Link {
+        link: String,
+        assets: Vec<(String, PathBuf)>,
+    },
+    /// The same link in several top-level sections, which isn't allowed by [`DUPLICATES_FILE`].
+    CrossSection {
+        link: String,
+        assets: Vec<(String, PathBuf)>,
+    },
+    Name {
+        section: String,
+        slug: String,
+        assets: Vec<(String, PathBuf)>,
+    },
+}
+impl DuplicateError {
+    /// Identifier of the check that failed, like `description-too-long`.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            DuplicateError::Link { .. } => "duplicate-link",
+            DuplicateError::CrossSection { .. } => "cross-section-duplicate",
+            DuplicateError::Name { .. } => "duplicate-name",
+        }
+    }
+
+    /// Paths of the duplicated asset files, relative to the assets directory.
+    pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
+        match self {
+            DuplicateError::Link { assets, .. }
+            | DuplicateError::CrossSection { assets, .. }
+            | DuplicateError::Name { assets, .. } => assets.iter().map(|(_, path)| path),
+        }
+    }
+}
+impl Display for DuplicateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DuplicateError::Link { link, assets } => {
+                writeln!(f, "Duplicate link {}", link)?;
+                for (name, path) in assets {
+                    writeln!(f, "  {} ({})", name, path.display())?;
+                }
+            }
+            DuplicateError::CrossSection { link, assets } => {
+                writeln!(
+                    f,
+                    "Link {} is listed in several sections, add it to `allowed_links` in {} if this is intended",
+                    link, DUPLICATES_FILE
+                )?;
+                for (name, path) in assets {
+                    writeln!(f, "  {} ({})", name, path.display())?;
+                }
+            }
+            DuplicateError::Name {
+                section,
+                slug,
+                assets,
+            } => {
+                writeln!(
+                    f,
+                    "Asset names in section {} all give the slug {}, only one of them keeps it",
+                    section, slug
+                )?;
+                for (name, path) in assets {
+                    writeln!(f, "  {} ({})", name, path.display())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds assets sharing a link or a slug.
+///
+/// A link listed once in several top-level sections, like a game also listed as an asset,
+/// is only allowed if it is in the `allowed_links` of `config`.
+pub fn find_duplicates(
+    asset_root_section: &Section,
+    asset_dir: &Path,
+    config: &DuplicatesConfig,
+) -> Vec<DuplicateError> {
+    let mut by_link: BTreeMap<String, Vec<(&str, &Asset)>> = BTreeMap::new();
+    for content in &asset_root_section.content {
+        let (top_level_section, assets) = match content {
+            AssetNode::Section(section) => (section.name.as_str(), section.assets()),
+            AssetNode::Asset(asset) => ("", vec![asset]),
+        };
+        for asset in assets {
+            by_link
+                .entry(canonical_link(&asset.link))
+                .or_default()
+                .push((top_level_section, asset));
+        }
+    }
+    let allowed_links: HashSet<_> = config
+        .allowed_links
+        .iter()
+        .map(|link| canonical_link(link))
+        .collect();
+
+    let mut duplicates: Vec<_> = by_link
+        .into_iter()
+        .filter(|(_, assets)| assets.len() > 1)
+        .filter_map(|(canonical, assets)| {
+            let sections: HashSet<_> = assets.iter().map(|(section, _)| section).collect();
+            let link = assets[0].1.link.to_string();
+            let assets = names_and_paths(
+                &assets.iter().map(|(_, asset)| *asset).collect::<Vec<_>>(),
+                asset_dir,
+            );
+            if sections.len() < assets.len() {
+                Some(DuplicateError::Link { link, assets })
+            } else if allowed_links.contains(&canonical) {
+                None
+            } else {
+                Some(DuplicateError::CrossSection { link, assets })
+            }
+        })
+        .collect();
+
+    find_duplicate_names(asset_root_section, asset_dir, &mut duplicates);
+    duplicates
+}
+
+/// Finds assets of a same section whose names only differ by case or punctuation,
+/// since they generate the same page.
+fn find_duplicate_names(section: &Section, asset_dir: &Path, duplicates: &mut Vec<DuplicateError>) {
+    let mut by_slug: BTreeMap<String, Vec<&Asset>> = BTreeMap::new();
+    for content in &section.content {
+        match content {
+            AssetNode::Section(section) => find_duplicate_names(section, asset_dir, duplicates),
+            AssetNode::Asset(asset) => by_slug.entry(slugify(&asset.name)).or_default().push(asset),
+        }
+    }
+
+    duplicates.extend(
+        by_slug
+            .into_iter()
+            .filter(|(_, assets)| assets.len() > 1)
+            .map(|(slug, assets)| DuplicateError::Name {
+                section: section.name.clone(),
+                slug,
+                assets: names_and_paths(&assets, asset_dir),
+            }),
+    );
+}
+
+fn names_and_paths(assets: &[&Asset], asset_dir: &Path) -> Vec<(String, PathBuf)> {
+    assets
+        .iter()
+        .map(|asset| (asset.name.clone(), relative_path(asset, asset_dir)))
+        .collect()
+}
+
+/// Path of the file of an asset, relative to the assets directory.
+fn relative_path(asset: &Asset, asset_dir: &Path) -> PathBuf {
+    let path = asset.original_path.clone().unwrap_or_default();
+    path.strip_prefix(asset_dir)
+        .map(Path::to_path_buf)
+        .unwrap_or(path)
+}
+
+/// A problem of an asset that only fails the validation when denied.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValidationWarning {
+    ShortDescription,
+    HtmlInDescription,
+    BoilerplateInDescription(String),
+    UnbalancedMarkdown(&'static str),
+    LinkNotNormalized(String),
+}
+impl ValidationWarning {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationWarning::LinkNotNormalized(_) => Severity::Info,
+            _ => Severity::Warning,
+        }
+    }
+
+    /// Identifier of the check that failed, like `description-too-long`.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            ValidationWarning::ShortDescription => "description-too-short",
+            ValidationWarning::HtmlInDescription => "description-html",
+            ValidationWarning::BoilerplateInDescription(_) => "description-boilerplate",
+            ValidationWarning::UnbalancedMarkdown(_) => "description-unbalanced-markdown",
+            ValidationWarning::LinkNotNormalized(_) => "link-not-normalized",
+        }
+    }
+}
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationWarning::ShortDescription => write!(
+                f,
+                "Description should be at least {} chars in length, describe what the asset does.",
+                MIN_DESCRIPTION_LENGTH
+            ),
+            ValidationWarning::HtmlInDescription => {
+                write!(f, "Description should not contain HTML tags.")
+            }
+            ValidationWarning::BoilerplateInDescription(opening) => write!(
+                f,
+                "Description should not start with \"{}\", every asset is made for Bevy, start with what it does instead.",
+                opening
+            ),
+            ValidationWarning::UnbalancedMarkdown(delimiter) => write!(
+                f,
+                "Description has an unbalanced \"{}\", it will be displayed as is.",
+                delimiter
+            ),
+            ValidationWarning::LinkNotNormalized(link) => {
+                write!(f, "Link should be written as {}", link)
+            }
+        }
+    }
+}
+
+/// Checks assets against [`Rules`], returning a report for each asset with problems.
+pub trait AssetValidator {
+    fn validate(&self, rules: &Rules) -> Vec<AssetReport>;
+}
+
+impl AssetValidator for Section {
+    fn validate(&self, rules: &Rules) -> Vec<AssetReport> {
+        let mut rules = rules.clone();
+        rules.required_fields.extend(
+            self.required_fields
+                .iter()
+                .map(|field| (*field, self.name.clone())),
+        );
+
+        self.content
+            .iter()
+            .flat_map(|content| content.validate(&rules))
+            .collect()
+    }
+}
+
+impl AssetValidator for AssetNode {
+    fn validate(&self, rules: &Rules) -> Vec<AssetReport> {
+        match self {
+            AssetNode::Section(content) => content.validate(rules),
+            AssetNode::Asset(content) => content.validate(rules),
+        }
+    }
+}
+
+impl AssetValidator for Asset {
+    fn validate(&self, rules: &Rules) -> Vec<AssetReport> {
+        let mut errors = vec![];
+        let mut warnings = lint_description(&self.description);
+
+        let normalized_link = normalize_link(&self.link);
+        let tracking_parameters = tracking_parameters(&self.link);
+        if !tracking_parameters.is_empty() {
+            errors.push(ValidationError::LinkWithTrackingParameters(
+                tracking_parameters,
+                normalized_link,
+            ));
+        } else if normalized_link != self.link.as_str() {
+            warnings.push(ValidationWarning::LinkNotNormalized(normalized_link));
+        }
+
+        if self.description.len() > MAX_DESCRIPTION_LENGTH {
+            errors.push(ValidationError::DescriptionTooLong);
+        }
+
+        if has_forbidden_formatting(&self.description) {
+            errors.push(ValidationError::DescriptionWithFormatting);
+        }
+
+        for (field, section) in &rules.required_fields {
+            if !field.is_set(self) {
+                errors.push(ValidationError::MissingRequiredField(
+                    field.name(),
+                    section.clone(),
+                ));
+            }
+        }
+
+        for license in self.licenses.iter().flatten() {
+            if !is_valid_license(license) {
+                errors.push(ValidationError::InvalidLicense(
+                    license.clone(),
+                    suggest_license(license),
+                ));
+            }
+        }
+
+        for version in self.bevy_versions.iter().flatten() {
+            if !version.is_known() {
+                let version = version.to_string();
+                let suggestion = suggest_version(&version);
+                errors.push(ValidationError::UnknownBevyVersion(version, suggestion));
+            }
+        }
+
+        if let Some(deprecation) = &self.deprecated {
+            if deprecation.reason.trim().is_empty() {
+                errors.push(ValidationError::DeprecationWithoutReason);
+            }
+            if let Some(successor) = &deprecation.successor {
+                let is_link = url::Url::parse(successor).is_ok_and(|url| url.has_host());
+                if *successor == self.name || !(is_link || rules.asset_names.contains(successor)) {
+                    errors.push(ValidationError::UnknownSuccessor(successor.clone()));
+                }
+            }
+        }
+
+        if let Some(Err(err)) = self.demo.as_deref().map(Demo::new) {
+            errors.push(ValidationError::InvalidDemo(err));
+        }
+
+        if let Some(Icon::Image(icon)) = self.icon.as_deref().map(Icon::new) {
+            let path = Path::new(&icon);
+            let is_valid = matches!(
+                path.components().collect::<Vec<_>>()[..],
+                [Component::Normal(_)]
+            ) && path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ALLOWED_IMAGE_EXTENSIONS.contains(&ext))
+                && self
+                    .original_path
+                    .as_ref()
+                    .is_some_and(|asset_path| asset_path.with_file_name(path).is_file());
+            if !is_valid {
+                errors.push(ValidationError::InvalidIcon);
+            }
+        }
+
+        let contacts = [
+            self.discord.as_deref().map(discord_invite),
+            self.mastodon.as_deref().map(mastodon_profile),
+            self.contact.as_deref().map(contact_link),
+        ];
+        for contact in contacts.iter().flatten() {
+            if let Err(err) = contact {
+                errors.push(ValidationError::InvalidContact(*err));
+            }
+        }
+
+        let relations = [("depends_on", &self.depends_on), ("extends", &self.extends)];
+        for (field, names) in relations {
+            for name in names.iter().flatten() {
+                if *name == self.name || !rules.asset_names.contains(name) {
+                    errors.push(ValidationError::UnknownRelatedAsset(field, name.clone()));
+                }
+            }
+        }
+
+        for platform in self.platforms.iter().flatten() {
+            if !is_known_platform(platform) {
+                errors.push(ValidationError::UnknownPlatform(
+                    platform.clone(),
+                    suggest_platform(platform),
+                ));
+            }
+        }
+
+        if let Some(pricing) = &self.pricing {
+            let has_price = pricing
+                .price
+                .as_ref()
+                .is_some_and(|price| !price.trim().is_empty());
+            match pricing.model {
+                PricingModel::Paid if !has_price => errors.push(ValidationError::PaidWithoutPrice),
+                PricingModel::Free if pricing.price.is_some() => {
+                    errors.push(ValidationError::FreeWithPrice);
+                }
+                _ => {}
+            }
+        }
+
+        for tag in self.tags.iter().flatten() {
+            if !is_known_tag(tag) {
+                errors.push(ValidationError::UnknownTag(tag.clone(), suggest_tag(tag)));
+            }
+        }
+
+        if let Some(image) = self.image.as_ref() {
+            if !Path::new(image)
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                errors.push(ValidationError::ImageOutsideAssetDirectory);
+            }
+
+            let mut image_path = self.original_path.clone().unwrap_or_default();
+            image_path.pop();
+            image_path.push(image);
+
+            if let Some(extension) = image_path.extension().and_then(|ext| ext.to_str()) {
+                if !ALLOWED_IMAGE_EXTENSIONS.contains(&extension) {
+                    errors.push(ValidationError::ImageInvalidExtension);
+                }
+            } else {
+                errors.push(ValidationError::ImageInvalidExtension);
+            }
+
+            errors.extend(validate_image(&image_path, rules));
+        }
+
+        if errors.is_empty() && warnings.is_empty() {
+            vec![]
+        } else {
+            vec![AssetReport {
+                asset_name: self.name.clone(),
+                path: self.original_path.clone().unwrap_or_default(),
+                errors,
+                warnings,
+            }]
+        }
+    }
+}
+
+fn has_forbidden_formatting(string: &str) -> bool {
+    if string.contains('\n') {
+        return true;
+    }
+    if string.starts_with('#') {
+        return true;
+    }
+    let re = Regex::new(r"\[(.+)\]\(((?:/|https?://)[\w\d./?=#]+)\)").unwrap();
+    if re.is_match(string) {
+        return true;
+    }
+
+    false
+}
+
+/// Finds the style problems of a description, which don't prevent displaying it.
+pub fn lint_description(description: &str) -> Vec<ValidationWarning> {
+    let mut warnings = vec![];
+
+    if description.trim().len() < MIN_DESCRIPTION_LENGTH {
+        warnings.push(ValidationWarning::ShortDescription);
+    }
+
+    let html = Regex::new(r"</?[a-zA-Z][a-zA-Z0-9]*(\s[^>]*)?/?>").unwrap();
+    if html.is_match(description) {
+        warnings.push(ValidationWarning::HtmlInDescription);
+    }
+
+    let lowercase = description.trim_start().to_lowercase();
+    if let Some(opening) = DESCRIPTION_BOILERPLATE
+        .iter()
+        .find(|opening| lowercase.starts_with(*opening))
+    {
+        let opening = &description.trim_start()[..opening.len()];
+        warnings.push(ValidationWarning::BoilerplateInDescription(
+            opening.to_string(),
+        ));
+    }
+
+    // `**` must be checked before `*`, so that bold text isn't counted twice
+    let without_bold = description.replace("**", "");
+    if !description.matches("**").count().is_multiple_of(2) {
+        warnings.push(ValidationWarning::UnbalancedMarkdown("**"));
+    }
+    if !without_bold.matches('*').count().is_multiple_of(2) {
+        warnings.push(ValidationWarning::UnbalancedMarkdown("*"));
+    }
+    if !description.matches('`').count().is_multiple_of(2) {
+        warnings.push(ValidationWarning::UnbalancedMarkdown("`"));
+    }
+    if description.matches('[').count() != description.matches(']').count() {
+        warnings.push(ValidationWarning::UnbalancedMarkdown("[]"));
+    }
+    if description.matches('(').count() != description.matches(')').count() {
+        warnings.push(ValidationWarning::UnbalancedMarkdown("()"));
+    }
+
+    warnings
+}
+
+/// The image next to the asset file at `asset_path` that `image` most likely meant, named
+/// with another case or extension, among the images with an allowed extension.
+fn find_image(asset_path: &Path, image: &str) -> Option<String> {
+    let image = Path::new(image);
+    let stem = image.file_stem()?.to_string_lossy().to_lowercase();
+    fs::read_dir(asset_path.parent()?)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .find(|name| {
+            let path = Path::new(name);
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ALLOWED_IMAGE_EXTENSIONS.contains(&ext))
+                && path
+                    .file_stem()
+                    .is_some_and(|name| name.to_string_lossy().to_lowercase() == stem)
+        })
+}
+
+fn validate_image(path: &Path, rules: &Rules) -> Vec<ValidationError> {
+    let size = match path.metadata() {
+        Ok(metadata) if metadata.is_file() => metadata.len(),
+        _ => return vec![ValidationError::ImageInvalidLink],
+    };
+
+    let mut errors = vec![];
+    if size > rules.max_image_bytes {
+        errors.push(ValidationError::ImageFileSizeTooLarge {
+            path: path.to_path_buf(),
+            size,
+            max: rules.max_image_bytes,
+        });
+    }
+
+    // Only the header of the image is read to get its dimensions
+    match image::image_dimensions(path) {
+        Ok((width, height)) if width.min(height) < MIN_IMAGE_DIMENSION => {
+            errors.push(ValidationError::ImageDimensionsTooSmall(width, height));
+        }
+        Ok((width, height)) if width.max(height) > MAX_IMAGE_DIMENSION => {
+            errors.push(ValidationError::ImageDimensionsTooLarge(width, height));
+        }
+        Ok(_) => {}
+        Err(_) => errors.push(ValidationError::ImageUnreadable),
+    }
+
+    errors
+}
+
+/// Checks that every featured asset exists, and is featured between valid dates.
+pub fn check_featured(config: &FeaturedConfig, asset_names: &HashSet<String>) -> Vec<String> {
+    let mut errors = vec![];
+    for featured in &config.assets {
+        if !asset_names.contains(&featured.name) {
+            errors.push(format!(
+                "Featured asset `{}` doesn't exist, its name must match the `name` of an asset file",
+                featured.name
+            ));
+        }
+        for (key, date) in [("from", &featured.from), ("until", &featured.until)] {
+            if let Some(date) = date.as_ref().filter(|date| !is_date(date)) {
+                errors.push(format!(
+                    "`{key}` of featured asset `{}` must be a `YYYY-MM-DD` date, found `{date}`",
+                    featured.name
+                ));
+            }
+        }
+        if let (Some(from), Some(until)) = (&featured.from, &featured.until) {
+            if from > until {
+                errors.push(format!(
+                    "Featured asset `{}` is featured until {until}, before it starts on {from}",
+                    featured.name
+                ));
+            }
+        }
+    }
+    errors
+}
+
+/// Whether `date` is a `YYYY-MM-DD` date.
+fn is_date(date: &str) -> bool {
+    let parts: Vec<_> = date.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return false;
+    };
+    let number = |part: &str, len: usize| {
+        (part.len() == len && part.chars().all(|c| c.is_ascii_digit()))
+            .then(|| part.parse::<u32>().ok())
+            .flatten()
+    };
+    number(year, 4).is_some()
+        && number(month, 2).is_some_and(|month| (1..=12).contains(&month))
+        && number(day, 2).is_some_and(|day| (1..=31).contains(&day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FeaturedAsset;
+
+    #[test]
+    fn lints_descriptions() {
+        assert!(lint_description("Tilemaps with chunked rendering").is_empty());
+        let warnings = lint_description("A Bevy plugin for *tilemaps");
+        assert_eq!(
+            warnings
+                .iter()
+                .map(ValidationWarning::rule)
+                .collect::<Vec<_>>(),
+            ["description-boilerplate", "description-unbalanced-markdown"]
+        );
+        assert!(matches!(
+            &lint_description("Tiles")[..],
+            [ValidationWarning::ShortDescription]
+        ));
+    }
+
+    #[test]
+    fn checks_featured_assets() {
+        let featured = |name: &str, from: Option<&str>, until: Option<&str>| FeaturedAsset {
+            name: name.to_string(),
+            from: from.map(str::to_string),
+            until: until.map(str::to_string),
+        };
+        let config = FeaturedConfig {
+            shown: None,
+            rotation_days: None,
+            assets: vec![
+                featured("Tilemap", Some("2024-01-01"), Some("2024-02-01")),
+                featured("Missing", None, None),
+                featured("Tilemap", Some("2024-13-01"), None),
+                featured("Tilemap", Some("2024-03-01"), Some("2024-02-01")),
+            ],
+        };
+        let asset_names = HashSet::from(["Tilemap".to_string()]);
+        assert_eq!(
+            check_featured(&config, &asset_names),
+            [
+                "Featured asset `Missing` doesn't exist, its name must match the `name` of an asset file",
+                "`from` of featured asset `Tilemap` must be a `YYYY-MM-DD` date, found `2024-13-01`",
+                "Featured asset `Tilemap` is featured until 2024-02-01, before it starts on 2024-03-01",
+            ]
+        );
+    }
+}
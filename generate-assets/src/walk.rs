@@ -0,0 +1,427 @@
+//! Walks the folders of bevy-assets, skipping the files that aren't part of the assets:
+//! hidden files and folders, like `.git` or `.github`, editor and system artifacts, like
+//! `asset.toml~` or `Thumbs.db`, and the paths matching the rules of the [`IGNORE_FILE`] at
+//! the root of the asset folder.
+//!
+//! The rules follow a subset of the `.gitignore` syntax, one per line:
+//!
+//! ```text
+//! # Comments and blank lines are skipped
+//! drafts/             # a folder named `drafts`, at any depth
+//! *.bak.toml          # a file name, at any depth
+//! /Games/wip-*.toml   # a path from the root of the asset folder, as it contains a `/`
+//! !/Games/wip-jam.toml # not ignored after all, the last matching rule wins
+//! ```
+//!
+//! Links are followed by default, unless they lead back to a folder containing them, which
+//! would be walked forever. They can be skipped instead with [`SymlinkPolicy::Skip`].
+//!
+//! Paths are kept as they are, even when they aren't valid UTF-8: it's up to the callers to
+//! report the names they can't use as strings, like the names of sections.
+
+use std::{
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    encoding::read_text_file,
+    error::{GenerateAssetsError, Result},
+    schema::AssetFormat,
+    DUPLICATES_FILE, FEATURED_FILE,
+};
+
+/// Name of the file, at the root of the assets directory, listing the paths to skip.
+pub const IGNORE_FILE: &str = ".assetignore";
+
+/// Names of files created by operating systems, which are never assets.
+const SYSTEM_ARTIFACTS: &[&str] = &["Thumbs.db", "desktop.ini"];
+
+/// Extensions of the backup, swap and merge files of editors and tools.
+const ARTIFACT_EXTENSIONS: &[&str] = &["bak", "orig", "rej", "swo", "swp", "tmp"];
+
+/// What to do with the links found while walking an asset folder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Read the files and folders they link to, except the links to a folder containing
+    /// them, which are skipped with a warning.
+    #[default]
+    Follow,
+    /// Skip every link, with a warning.
+    Skip,
+}
+
+/// An entry of a folder listed by [`AssetWalker::read_dir`].
+#[derive(Debug)]
+pub enum Entry {
+    Dir(PathBuf),
+    File(PathBuf),
+    /// An entry that couldn't be read, like a broken link, with why.
+    Unreadable(PathBuf, io::Error),
+}
+
+impl Entry {
+    pub fn path(&self) -> &Path {
+        match self {
+            Entry::Dir(path) | Entry::File(path) | Entry::Unreadable(path, _) => path,
+        }
+    }
+}
+
+/// Lists the folders of an asset folder, skipping hidden files, artifacts and the paths
+/// ignored by its [`IGNORE_FILE`], see the [module](self) docs.
+#[derive(Debug, Clone, Default)]
+pub struct AssetWalker {
+    root: PathBuf,
+    rules: Vec<IgnoreRule>,
+    symlinks: SymlinkPolicy,
+}
+
+/// A line of the [`IGNORE_FILE`].
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    pattern: glob::Pattern,
+    /// Whether matching paths are kept instead, with a leading `!`.
+    negated: bool,
+    /// Whether only folders match, with a trailing `/`.
+    dir_only: bool,
+    /// Whether the pattern matches the path from the root, instead of the name.
+    anchored: bool,
+}
+
+impl AssetWalker {
+    /// A walker of the asset folder `root`, following the rules of its [`IGNORE_FILE`] if
+    /// there is one.
+    pub fn new(root: &Path) -> Result<Self> {
+        let path = root.join(IGNORE_FILE);
+        let rules = if path.exists() {
+            read_text_file(&path)?
+        } else {
+            String::new()
+        };
+        Self::with_rules(root, &rules).map_err(|(line, source)| {
+            GenerateAssetsError::parse(format!("{}:{line}", path.display()), source)
+        })
+    }
+
+    /// A walker of the asset folder `root` following `rules`, in the format of the
+    /// [`IGNORE_FILE`]. Fails with the line of the first invalid pattern.
+    pub fn with_rules(
+        root: &Path,
+        rules: &str,
+    ) -> std::result::Result<Self, (usize, glob::PatternError)> {
+        let mut walker = AssetWalker {
+            root: root.to_path_buf(),
+            rules: vec![],
+            symlinks: SymlinkPolicy::default(),
+        };
+        for (index, line) in rules.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            let pattern =
+                glob::Pattern::new(line.trim_start_matches('/')).map_err(|err| (index + 1, err))?;
+            walker.rules.push(IgnoreRule {
+                pattern,
+                negated,
+                dir_only,
+                anchored,
+            });
+        }
+        Ok(walker)
+    }
+
+    /// Handles the links following `symlinks` instead of following them.
+    pub fn with_symlinks(mut self, symlinks: SymlinkPolicy) -> Self {
+        self.symlinks = symlinks;
+        self
+    }
+
+    /// Whether `path`, a folder if `is_dir`, is ignored by the rules of the [`IGNORE_FILE`].
+    /// The folders containing it aren't checked, they are skipped while walking.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return false;
+        };
+        let relative = relative
+            .iter()
+            .map(OsStr::to_string_lossy)
+            .collect::<Vec<_>>()
+            .join("/");
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let matched = if rule.anchored {
+                rule.pattern.matches_with(&relative, options)
+            } else {
+                rule.pattern.matches_with(&name, options)
+            };
+            if matched {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+
+    /// The entries of `dir` that aren't skipped, sorted by path so the assets are always
+    /// read and reported in the same order, whatever the file system.
+    pub fn read_dir(&self, dir: &Path) -> Result<Vec<Entry>> {
+        let io_error = |source| GenerateAssetsError::Io {
+            path: dir.to_path_buf(),
+            source,
+        };
+        let mut entries = vec![];
+        for entry in fs::read_dir(dir).map_err(io_error)? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    entries.push(Entry::Unreadable(dir.to_path_buf(), err));
+                    continue;
+                }
+            };
+            let path = entry.path();
+            if is_skipped_name(&entry.file_name()) {
+                continue;
+            }
+            let is_link = entry
+                .file_type()
+                .is_ok_and(|file_type| file_type.is_symlink());
+            if is_link && self.symlinks == SymlinkPolicy::Skip {
+                tracing::warn!("Skipping the link {}", path.display());
+                continue;
+            }
+            match fs::metadata(&path) {
+                Ok(metadata) if self.is_ignored(&path, metadata.is_dir()) => {}
+                Ok(metadata) if metadata.is_dir() && is_link => match fs::canonicalize(&path) {
+                    Ok(target) if self.contains_link(dir, &target) => tracing::warn!(
+                        "Skipping the link {} to {}, which contains it",
+                        path.display(),
+                        target.display()
+                    ),
+                    Ok(_) => entries.push(Entry::Dir(path)),
+                    Err(err) => entries.push(Entry::Unreadable(path, err)),
+                },
+                Ok(metadata) if metadata.is_dir() => entries.push(Entry::Dir(path)),
+                Ok(_) => entries.push(Entry::File(path)),
+                Err(err) => entries.push(Entry::Unreadable(path, err)),
+            }
+        }
+        entries.sort_by(|entry1, entry2| entry1.path().cmp(entry2.path()));
+        Ok(entries)
+    }
+
+    /// Whether the folder `target` contains `dir`, or one of the folders it was reached
+    /// through from the root, once the links are resolved. Following a link to `target`
+    /// from `dir` would then walk the same folders again and again.
+    fn contains_link(&self, dir: &Path, target: &Path) -> bool {
+        dir.ancestors()
+            .take_while(|ancestor| ancestor.starts_with(&self.root))
+            .filter_map(|ancestor| fs::canonicalize(ancestor).ok())
+            .any(|ancestor| ancestor.starts_with(target))
+    }
+
+    /// Calls `f` with every file of `dir` and its sub-folders that isn't skipped, in the
+    /// order of [`AssetWalker::read_dir`]. Fails on the first entry that can't be read.
+    pub fn walk_files<E: From<GenerateAssetsError>>(
+        &self,
+        dir: &Path,
+        f: &mut impl FnMut(&Path) -> std::result::Result<(), E>,
+    ) -> std::result::Result<(), E> {
+        for entry in self.read_dir(dir)? {
+            match entry {
+                Entry::Dir(path) => self.walk_files(&path, f)?,
+                Entry::File(path) => f(&path)?,
+                Entry::Unreadable(path, source) => {
+                    return Err(GenerateAssetsError::Io { path, source }.into())
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether a file or folder named `name` is hidden, or an artifact of an editor or of the
+/// operating system, like `asset.toml~`, `#asset.toml#` or `asset.toml.swp`.
+pub fn is_skipped_name(name: &OsStr) -> bool {
+    let name = name.to_string_lossy();
+    let extension = Path::new(&*name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_ascii_lowercase());
+    name.starts_with('.')
+        || name.ends_with('~')
+        || (name.len() > 1 && name.starts_with('#') && name.ends_with('#'))
+        || SYSTEM_ARTIFACTS.contains(&&*name)
+        || extension.is_some_and(|extension| ARTIFACT_EXTENSIONS.contains(&extension.as_str()))
+}
+
+/// Whether `path` is the file of an asset: a file in an [`AssetFormat`] other than the
+/// configuration files.
+pub fn is_asset_file(path: &Path) -> bool {
+    AssetFormat::from_path(path).is_some()
+        && path.file_name().is_some_and(|name| {
+            name != "_category.toml" && name != DUPLICATES_FILE && name != FEATURED_FILE
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_hidden_files_and_artifacts() {
+        for name in [
+            ".git",
+            ".asset.toml.swp",
+            "asset.toml~",
+            "#asset.toml#",
+            "asset.toml.bak",
+            "Thumbs.db",
+        ] {
+            assert!(is_skipped_name(OsStr::new(name)), "{}", name);
+        }
+        for name in ["asset.toml", "README", "#hashtag.toml", "logo.png"] {
+            assert!(!is_skipped_name(OsStr::new(name)), "{}", name);
+        }
+
+        assert!(is_asset_file(Path::new("2D/asset.toml")));
+        assert!(is_asset_file(Path::new("2D/asset.json")));
+        assert!(is_asset_file(Path::new("2D/asset.yml")));
+        assert!(!is_asset_file(Path::new("2D/_category.toml")));
+        assert!(!is_asset_file(Path::new("2D/LICENSE")));
+    }
+
+    #[test]
+    fn follows_ignore_rules() {
+        let root = Path::new("assets");
+        let walker = AssetWalker::with_rules(
+            root,
+            "# Drafts\ndrafts/\n*.draft.toml\n/Games/wip-*.toml\n!/Games/wip-jam.toml\n",
+        )
+        .unwrap();
+
+        assert!(walker.is_ignored(&root.join("2D/drafts"), true));
+        assert!(!walker.is_ignored(&root.join("2D/drafts"), false));
+        assert!(walker.is_ignored(&root.join("2D/Shapes/lyon.draft.toml"), false));
+        assert!(walker.is_ignored(&root.join("Games/wip-rpg.toml"), false));
+        assert!(!walker.is_ignored(&root.join("Games/wip-jam.toml"), false));
+        assert!(!walker.is_ignored(&root.join("Games/RPG/wip-rpg.toml"), false));
+        assert!(!walker.is_ignored(&root.join("Games/rpg.toml"), false));
+
+        assert_eq!(
+            AssetWalker::with_rules(root, "ok\n[unclosed\n")
+                .unwrap_err()
+                .0,
+            2
+        );
+    }
+
+    #[test]
+    fn lists_entries_in_order() {
+        let dir = std::env::temp_dir().join(format!("generate-assets-walk-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("Games/drafts")).unwrap();
+        fs::create_dir_all(dir.join(".github")).unwrap();
+        for file in [
+            "Games/b.toml",
+            "Games/a.toml",
+            "Games/a.toml~",
+            "Games/README",
+            "Games/drafts/c.toml",
+            ".github/ci.yml",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        fs::write(dir.join(IGNORE_FILE), "drafts/\n").unwrap();
+
+        let walker = AssetWalker::new(&dir).unwrap();
+        let mut files = vec![];
+        walker
+            .walk_files(&dir, &mut |path| -> Result<()> {
+                files.push(path.strip_prefix(&dir).unwrap().to_path_buf());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            files,
+            [
+                Path::new("Games/README"),
+                Path::new("Games/a.toml"),
+                Path::new("Games/b.toml")
+            ]
+        );
+
+        fs::write(dir.join(IGNORE_FILE), "[unclosed\n").unwrap();
+        let err = AssetWalker::new(&dir).unwrap_err().to_string();
+        assert!(err.ends_with(".assetignore:1"), "{}", err);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_links_without_cycles() {
+        use std::os::unix::fs::symlink;
+
+        let dir =
+            std::env::temp_dir().join(format!("generate-assets-walk-links-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("2D")).unwrap();
+        fs::create_dir_all(dir.join("3D")).unwrap();
+        fs::create_dir_all(dir.join("shared")).unwrap();
+        fs::write(dir.join("2D/a.toml"), "").unwrap();
+        fs::write(dir.join("shared/b.toml"), "").unwrap();
+        symlink(dir.join("shared"), dir.join("3D/shared")).unwrap();
+        symlink(dir.join("2D/a.toml"), dir.join("3D/a.toml")).unwrap();
+        // Links back to the root, and between two folders linking to each other
+        symlink(&dir, dir.join("2D/root")).unwrap();
+        symlink(dir.join("3D"), dir.join("shared/3D")).unwrap();
+
+        let files = |walker: AssetWalker| {
+            let mut files = vec![];
+            walker
+                .walk_files(&dir, &mut |path| -> Result<()> {
+                    files.push(path.strip_prefix(&dir).unwrap().to_path_buf());
+                    Ok(())
+                })
+                .unwrap();
+            files
+        };
+        assert_eq!(
+            files(AssetWalker::new(&dir).unwrap()),
+            [
+                Path::new("2D/a.toml"),
+                Path::new("3D/a.toml"),
+                Path::new("3D/shared/b.toml"),
+                Path::new("shared/3D/a.toml"),
+                Path::new("shared/b.toml"),
+            ]
+        );
+        assert_eq!(
+            files(
+                AssetWalker::new(&dir)
+                    .unwrap()
+                    .with_symlinks(SymlinkPolicy::Skip)
+            ),
+            [Path::new("2D/a.toml"), Path::new("shared/b.toml")]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,269 @@
+//! Writes the metadata fetched for the assets back to their files, so refreshing it changes
+//! the bevy-assets repository instead of only the generated website, and applies the
+//! [`Fix`]es suggested by the validation.
+//!
+//! Files are edited in place with `toml_edit`, keeping their comments, formatting and key
+//! order, so the diff only shows the values that changed. Asset files in JSON or YAML aren't
+//! edited, as they can't be without losing their formatting.
+
+use std::{fmt::Display, fs, path::Path};
+
+use anyhow::Context;
+use toml_edit::{Array, Document, Item, Value};
+
+use crate::{
+    bevy_releases::BevyVersion, encoding::read_text_file, schema::AssetFormat, Asset, ExtraMetadata,
+};
+
+/// Writes the metadata fetched for `asset` to its file, returns whether the file changed.
+///
+/// Assets without a TOML file or without fetched metadata are left as is.
+pub fn write_metadata(asset: &Asset) -> anyhow::Result<bool> {
+    let (Some(path), Some(metadata)) = (&asset.original_path, &asset.fetched_metadata) else {
+        return Ok(false);
+    };
+    if AssetFormat::from_path(path) != Some(AssetFormat::Toml) {
+        return Ok(false);
+    }
+    edit_file(path, |document| update_document(document, metadata))
+}
+
+/// Sets the `licenses`, `bevy_versions` and `latest_version` of an asset file to the ones of
+/// `metadata`, returns whether anything changed.
+///
+/// Licenses are replaced when they differ, ignoring their order. Bevy versions are only
+/// replaced when no listed version is of the minor release of the fetched one, so assets
+/// supporting several versions keep them, and `0.13` isn't replaced by `0.13.1`.
+pub fn update_document(document: &mut Document, metadata: &ExtraMetadata) -> bool {
+    let mut changed = false;
+
+    if let Some(license) = &metadata.license {
+        let mut licenses: Vec<&str> = license.split(" OR ").map(str::trim).collect();
+        let mut current = strings(document, "licenses");
+        licenses.sort_unstable();
+        current.sort_unstable();
+        if current != licenses {
+            let licenses: Array = license.split(" OR ").map(str::trim).collect();
+            set_value(document, "licenses", licenses);
+            changed = true;
+        }
+    }
+
+    if let Some(bevy_version) = &metadata.bevy_version {
+        let listed = strings(document, "bevy_versions")
+            .into_iter()
+            .filter_map(|version| version.parse::<BevyVersion>().ok())
+            .any(|version| version.minor() == bevy_version.minor());
+        if !listed {
+            let bevy_versions: Array = std::iter::once(bevy_version.to_string()).collect();
+            set_value(document, "bevy_versions", bevy_versions);
+            changed = true;
+        }
+    }
+
+    if let Some(latest_version) = &metadata.latest_version {
+        let current = document.get("latest_version").and_then(Item::as_str);
+        if current != Some(latest_version.as_str()) {
+            set_value(document, "latest_version", latest_version);
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// A change to a single field of an asset file, fixing a problem found by the validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// Replaces `old` with `new` in the array at `key`, like a misspelled license.
+    Replace {
+        key: &'static str,
+        old: String,
+        new: String,
+    },
+    /// Sets `key` to `value`, like a link that redirects.
+    Set { key: &'static str, value: String },
+    /// Removes `key`, like an image that doesn't exist.
+    Remove { key: &'static str },
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fix::Replace { key, old, new } => {
+                write!(f, "replace \"{old}\" with \"{new}\" in `{key}`")
+            }
+            Fix::Set { key, value } => write!(f, "set `{key}` to \"{value}\""),
+            Fix::Remove { key } => write!(f, "remove `{key}`"),
+        }
+    }
+}
+
+/// Applies `fix` to the asset file at `path`, returns whether the file changed.
+pub fn apply_fix(path: &Path, fix: &Fix) -> anyhow::Result<bool> {
+    edit_file(path, |document| fix_document(document, fix))
+}
+
+/// Applies `fix` to the document of an asset file, returns whether anything changed.
+pub fn fix_document(document: &mut Document, fix: &Fix) -> bool {
+    match fix {
+        Fix::Replace { key, old, new } => {
+            let Some(array) = document.get_mut(key).and_then(Item::as_array_mut) else {
+                return false;
+            };
+            let mut changed = false;
+            for value in array.iter_mut() {
+                if value.as_str() == Some(old.as_str()) {
+                    let decor = value.decor().clone();
+                    *value = new.into();
+                    *value.decor_mut() = decor;
+                    changed = true;
+                }
+            }
+            changed
+        }
+        Fix::Set { key, value } => {
+            if document.get(key).and_then(Item::as_str) == Some(value.as_str()) {
+                return false;
+            }
+            set_value(document, key, value);
+            true
+        }
+        Fix::Remove { key } => document.remove(key).is_some(),
+    }
+}
+
+/// Edits the TOML file at `path` with `edit`, only writing it when `edit` returns that it
+/// changed the document.
+fn edit_file(path: &Path, edit: impl FnOnce(&mut Document) -> bool) -> anyhow::Result<bool> {
+    if let Some(format @ (AssetFormat::Json | AssetFormat::Yaml)) = AssetFormat::from_path(path) {
+        anyhow::bail!("{} can't be edited, as it is in {format}", path.display());
+    }
+    let content = read_text_file(path)?;
+    let mut document: Document = content
+        .parse()
+        .with_context(|| format!("Parsing {}", path.display()))?;
+    if !edit(&mut document) {
+        return Ok(false);
+    }
+    fs::write(path, document.to_string()).with_context(|| format!("Writing {}", path.display()))?;
+    Ok(true)
+}
+
+/// The strings of the array at `key`, empty if it isn't set.
+fn strings<'a>(document: &'a Document, key: &str) -> Vec<&'a str> {
+    document
+        .get(key)
+        .and_then(Item::as_array)
+        .map(|array| array.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// Sets `key` to `value`, keeping the whitespace and comments around the previous value.
+fn set_value(document: &mut Document, key: &str, value: impl Into<Value>) {
+    let mut value = value.into();
+    if let Some(previous) = document.get(key).and_then(Item::as_value) {
+        *value.decor_mut() = previous.decor().clone();
+    }
+    document[key] = Item::Value(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(license: &str, bevy_version: &str, latest_version: &str) -> ExtraMetadata {
+        ExtraMetadata {
+            license: Some(license.to_string()),
+            bevy_version: Some(bevy_version.parse().unwrap()),
+            platforms: vec![],
+            latest_version: Some(latest_version.to_string()),
+        }
+    }
+
+    fn update(content: &str, metadata: &ExtraMetadata) -> Option<String> {
+        let mut document: Document = content.parse().unwrap();
+        update_document(&mut document, metadata).then(|| document.to_string())
+    }
+
+    #[test]
+    fn adds_missing_fields_after_the_others() {
+        let content = "name = \"bevy_plugin\"\n\
+            link = \"https://crates.io/crates/bevy_plugin\"\n\
+            description = \"A plugin\"\n\
+            \n\
+            [extra]\n\
+            banner = \"banner.png\"\n";
+        assert_eq!(
+            update(content, &metadata("MIT OR Apache-2.0", "0.13", "0.2.1")).unwrap(),
+            "name = \"bevy_plugin\"\n\
+            link = \"https://crates.io/crates/bevy_plugin\"\n\
+            description = \"A plugin\"\n\
+            licenses = [\"MIT\", \"Apache-2.0\"]\n\
+            bevy_versions = [\"0.13\"]\n\
+            latest_version = \"0.2.1\"\n\
+            \n\
+            [extra]\n\
+            banner = \"banner.png\"\n"
+        );
+    }
+
+    #[test]
+    fn keeps_fields_matching_the_metadata() {
+        let content = "name = \"bevy_plugin\" # the crate\n\
+            licenses = [ \"Apache-2.0\", \"MIT\" ]\n\
+            bevy_versions = [\"0.12\", \"0.13\"]\n\
+            latest_version = \"0.2.1\"\n";
+        assert_eq!(
+            update(content, &metadata("MIT OR Apache-2.0", "0.13", "0.2.1")),
+            None
+        );
+    }
+
+    #[test]
+    fn applies_fixes_in_place() {
+        let mut document: Document = "name = \"bevy_plugin\"\n\
+            link = \"http://github.com/old/bevy_plugin\" # moved\n\
+            licenses = [\"MIT\", \"Apache 2\"]\n\
+            image = \"missing.png\"\n"
+            .parse()
+            .unwrap();
+        let fixes = [
+            Fix::Replace {
+                key: "licenses",
+                old: "Apache 2".to_string(),
+                new: "Apache-2.0".to_string(),
+            },
+            Fix::Set {
+                key: "link",
+                value: "https://github.com/new/bevy_plugin".to_string(),
+            },
+            Fix::Remove { key: "image" },
+        ];
+        for fix in &fixes {
+            assert!(fix_document(&mut document, fix), "{}", fix);
+            assert!(!fix_document(&mut document, fix), "{} applied twice", fix);
+        }
+        assert_eq!(
+            document.to_string(),
+            "name = \"bevy_plugin\"\n\
+            link = \"https://github.com/new/bevy_plugin\" # moved\n\
+            licenses = [\"MIT\", \"Apache-2.0\"]\n"
+        );
+    }
+
+    #[test]
+    fn replaces_outdated_fields_in_place() {
+        let content = "name = \"bevy_plugin\"\n\
+            bevy_versions = [\"0.12\"] # checked by hand\n\
+            latest_version = \"0.1.0\"\n\
+            licenses = [\"MIT\"]\n";
+        assert_eq!(
+            update(content, &metadata("MIT", "0.13", "0.2.0")).unwrap(),
+            "name = \"bevy_plugin\"\n\
+            bevy_versions = [\"0.13\"] # checked by hand\n\
+            latest_version = \"0.2.0\"\n\
+            licenses = [\"MIT\"]\n"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Golden-file tests of the content written by `generate-assets generate` for the assets of
+//! `fixtures/assets`, with their metadata replayed from `fixtures/cassettes/metadata.json`.
+//!
+//! Each written file is compared with its snapshot in `tests/snapshots`. After a deliberate
+//! change of the output, review and accept the new snapshots with `cargo insta review`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(path)
+}
+
+/// Copies the files of `from` by name, or in the reverse order with `reversed`, which
+/// changes the order some file systems list them in.
+fn copy_dir(from: &Path, to: &Path, reversed: bool) {
+    fs::create_dir_all(to).unwrap();
+    let mut paths: Vec<_> = fs::read_dir(from)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    if reversed {
+        paths.reverse();
+    }
+    for path in paths {
+        let target = to.join(path.file_name().unwrap());
+        if path.is_dir() {
+            copy_dir(&path, &target, reversed);
+        } else {
+            fs::copy(&path, target).unwrap();
+        }
+    }
+}
+
+/// The files of `dir` and of its sub-folders, by path relative to `dir`.
+fn read_files(dir: &Path, files: &mut Vec<(String, String)>) {
+    read_files_at(dir, Path::new(""), files);
+    files.sort();
+}
+
+fn read_files_at(root: &Path, current: &Path, files: &mut Vec<(String, String)>) {
+    for entry in fs::read_dir(root.join(current)).unwrap() {
+        let path = current.join(entry.unwrap().file_name());
+        if root.join(&path).is_dir() {
+            read_files_at(root, &path, files);
+        } else {
+            let content = fs::read_to_string(root.join(&path)).unwrap();
+            files.push((path.to_string_lossy().replace('\\', "/"), content));
+        }
+    }
+}
+
+/// Runs `generate` with `args` on a copy of the fixture assets, and returns the content
+/// folder it wrote.
+///
+/// The copy is outside of this repository, so the assets don't get dates from its history.
+/// The crates.io database dump isn't recorded, so crates.io links are left without metadata.
+fn generate(name: &str, args: &[&str]) -> PathBuf {
+    generate_copy(name, args, false)
+}
+
+/// Like [`generate`], copying the fixture assets in the reverse order with `reversed`.
+fn generate_copy(name: &str, args: &[&str], reversed: bool) -> PathBuf {
+    let dir = copy_fixtures(name, reversed);
+    let command = [
+        "generate",
+        "--asset-dir",
+        "assets",
+        "--content-dir",
+        "content",
+    ];
+    run(&dir, &[&command, args].concat());
+    dir.join("content")
+}
+
+/// Copies the fixture assets to a new folder named after `name`, along with a configuration
+/// disabling crates.io.
+fn copy_fixtures(name: &str, reversed: bool) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "generate-assets-golden-{name}-{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    copy_dir(&fixture("assets"), &dir.join("assets"), reversed);
+    fs::write(
+        dir.join("generate_assets.toml"),
+        "[clients]\ncrates_io = false\n",
+    )
+    .unwrap();
+    dir
+}
+
+/// Runs the binary with `args` in `dir`, with the responses of the fixture cassette.
+fn run(dir: &Path, args: &[&str]) {
+    let output = Command::new(env!("CARGO_BIN_EXE_generate-assets"))
+        .current_dir(dir)
+        .arg("-qq")
+        .args(args)
+        .env("GENERATE_ASSETS_REPLAY", fixture("cassettes/metadata.json"))
+        .env_remove("GENERATE_ASSETS_RECORD")
+        .env("GITHUB_TOKEN", "fixture-token")
+        .env_remove("GITLAB_TOKEN")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed:\n{}",
+        args[0],
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Compares each file of `content` for which `filter` is true with its snapshot, named
+/// after `prefix` and its path.
+fn assert_snapshots(prefix: &str, content: &Path, filter: impl Fn(&str) -> bool) {
+    let mut files = vec![];
+    read_files(content, &mut files);
+    assert!(
+        !files.is_empty(),
+        "Nothing written to {}",
+        content.display()
+    );
+    for (path, content) in files.into_iter().filter(|(path, _)| filter(path)) {
+        let name = format!("{prefix}__{}", path.replace(['/', '.'], "_"));
+        insta::assert_snapshot!(name, content);
+    }
+}
+
+#[test]
+fn writes_zola_content_and_json() {
+    let content = generate("zola", &[]);
+    assert_snapshots("zola", &content, |_| true);
+    fs::remove_dir_all(content.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn writes_asset_pages() {
+    let content = generate("asset-pages", &["--asset-pages"]);
+    assert_snapshots("asset_pages", &content, |path| {
+        path.ends_with(".md") && !path.ends_with("_index.md")
+    });
+    fs::remove_dir_all(content.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn writes_the_same_files_again() {
+    let first = generate("first-run", &[]);
+    let second = generate_copy("second-run", &[], true);
+    let mut first_files = vec![];
+    read_files(&first, &mut first_files);
+    let mut second_files = vec![];
+    read_files(&second, &mut second_files);
+    assert_eq!(first_files, second_files);
+    fs::remove_dir_all(first.parent().unwrap()).unwrap();
+    fs::remove_dir_all(second.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn emits_the_same_files_from_a_snapshot() {
+    let generated = generate("generated", &["--asset-pages"]);
+    let dir = copy_fixtures("scanned", false);
+    run(
+        &dir,
+        &["scan", "--asset-dir", "assets", "--output", "snapshot.json"],
+    );
+    run(
+        &dir,
+        &[
+            "emit",
+            "snapshot.json",
+            "--content-dir",
+            "content",
+            "--asset-pages",
+        ],
+    );
+
+    let mut generated_files = vec![];
+    read_files(&generated, &mut generated_files);
+    let mut emitted_files = vec![];
+    read_files(&dir.join("content"), &mut emitted_files);
+    assert_eq!(generated_files, emitted_files);
+    fs::remove_dir_all(generated.parent().unwrap()).unwrap();
+    fs::remove_dir_all(dir).unwrap();
+}
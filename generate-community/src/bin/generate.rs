@@ -4,8 +4,7 @@ use std::{
     collections::BTreeMap,
     fs::{self, File},
     io::{self, prelude::*},
-    path::{Path, PathBuf},
-    str::FromStr,
+    path::Path,
 };
 
 use generate_community::*;
@@ -17,10 +16,7 @@ fn main() -> io::Result<()> {
     let _ = fs::create_dir(content_dir.clone());
     let mut people_root_section = parse_members(&community_dir)?;
 
-    let mut roles_path = PathBuf::from_str(&community_dir).unwrap();
-    roles_path.push("_roles.toml");
-    let roles: Roles = toml::de::from_str(&fs::read_to_string(&roles_path).unwrap()).unwrap();
-    let role_map = roles.into_map();
+    let role_map = parse_roles(&community_dir)?.into_map();
     people_root_section.apply_roles(&role_map);
 
     people_root_section.write(Path::new(&content_dir), Path::new(&content_sub_dir), 0)?;
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use generate_community::*;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -5,65 +7,111 @@ fn main() -> Result<(), String> {
     let community_dir = std::env::args().nth(1).unwrap();
 
     let people_root_section = parse_members(&community_dir).map_err(|err| err.to_string())?;
+    let roles = parse_roles(&community_dir).map_err(|err| err.to_string())?;
+
+    let mut errors = vec![];
+    validate_section(&people_root_section, &mut errors);
+    validate_roles(&roles, &people_root_section, &mut errors);
 
-    validate_section(&people_root_section)?;
+    for error in &errors {
+        eprintln!("{error}");
+    }
+    if !errors.is_empty() {
+        Err(format!("{} errors found", errors.len()))?;
+    }
 
     Ok(())
 }
 
 const MAX_BIO_LENGTH: usize = 180;
 
-fn validate_section(section: &Section) -> Result<(), String> {
-    section
-        .content
-        .iter()
-        .map(validate_node)
-        .find(|valid| valid.is_err())
-        .unwrap_or(Ok(()))
+fn validate_section(section: &Section, errors: &mut Vec<String>) {
+    for node in &section.content {
+        validate_node(node, errors);
+    }
 }
 
-fn validate_node(node: &CommunityNode) -> Result<(), String> {
-    match node {
-        CommunityNode::Section(section) => validate_section(section)?,
-        CommunityNode::Member(member) => {
-            match member.profile_picture.as_ref() {
-                Some(ProfilePicture::File(file)) => {
-                    if !member
-                        .original_path
-                        .as_ref()
-                        .unwrap()
-                        .clone()
-                        .with_file_name(file)
-                        .exists()
-                    {
-                        Err(format!(
-                            "{:?}: Profile Picture set to a file, but file not found",
-                            member.original_path.as_ref().unwrap()
-                        ))?;
-                    }
-                }
-                Some(ProfilePicture::GitHub) => {
-                    if member.github.is_none() {
-                        Err(format!(
-                            "{:?}: Profile Picture set to GitHub, but no GitHub profile found",
-                            member.original_path.as_ref().unwrap()
-                        ))?;
-                    }
-                }
-                None => (),
-            };
+fn validate_node(node: &CommunityNode, errors: &mut Vec<String>) {
+    let member = match node {
+        CommunityNode::Section(section) => return validate_section(section, errors),
+        CommunityNode::Member(member) => member,
+    };
+    let path = member.original_path.as_ref().unwrap();
+    let mut error = |message: String| errors.push(format!("{path:?}: {message}"));
 
-            if let Some(bio) = &member.bio {
-                let grapheme_count = bio.graphemes(true).count();
-                if grapheme_count > MAX_BIO_LENGTH {
-                    Err(format!("Bio is longer than the maximum allowed length of {}. It is currently {} characters long.", MAX_BIO_LENGTH, grapheme_count))?;
-                }
-            }
+    match member.profile_picture.as_ref() {
+        Some(ProfilePicture::File(file)) if !path.with_file_name(file).exists() => {
+            error("Profile Picture set to a file, but file not found".to_string());
+        }
+        Some(ProfilePicture::GitHub) if member.github.is_none() => {
+            error("Profile Picture set to GitHub, but no GitHub profile found".to_string());
+        }
+        _ => (),
+    };
+
+    if let Some(bio) = &member.bio {
+        let grapheme_count = bio.graphemes(true).count();
+        if grapheme_count > MAX_BIO_LENGTH {
+            error(format!("Bio is longer than the maximum allowed length of {MAX_BIO_LENGTH}. It is currently {grapheme_count} characters long."));
+        }
+    }
+
+    if member.roles.is_some() {
+        error("Roles must be defined in the _roles.toml file".to_string());
+    }
 
-            if member.roles.is_some() {
-                Err("Roles must be defined in the roles.toml file")?;
+    // The social links are usernames, the pages are linked to by the templates
+    let usernames = [
+        ("github", &member.github),
+        ("twitter", &member.twitter),
+        ("itch-io", &member.itch_io),
+        ("steam-developer", &member.steam_developer),
+        ("discord", &member.discord),
+    ];
+    for (field, username) in usernames {
+        match username.as_deref() {
+            Some("") => error(format!("{field} is empty")),
+            Some(username) if username.contains(['/', ' ']) => {
+                error(format!(
+                    "{field} must be a username, not a link: {username:?}"
+                ));
             }
+            _ => (),
+        }
+    }
+    if let Some(userid) = &member.discord_userid {
+        if userid.is_empty() || !userid.chars().all(|c| c.is_ascii_digit()) {
+            error(format!("discord-userid must be a number: {userid:?}"));
+        }
+    }
+    if let Some(website) = &member.website {
+        let is_link = ["https://", "http://"]
+            .iter()
+            .any(|scheme| website.starts_with(scheme) && website.len() > scheme.len());
+        if !is_link {
+            error(format!("website must be an http(s) link: {website:?}"));
+        }
+    }
+}
+
+/// Checks every role of `_roles.toml` is given to the GitHub username of a member.
+fn validate_roles(roles: &Roles, section: &Section, errors: &mut Vec<String>) {
+    let mut members = HashSet::new();
+    collect_github_usernames(section, &mut members);
+    for id in roles.ids() {
+        if !members.contains(id) {
+            errors.push(format!(
+                "_roles.toml: {id:?} has a role, but no member has this GitHub username"
+            ));
+        }
+    }
+}
+
+fn collect_github_usernames<'a>(section: &'a Section, usernames: &mut HashSet<&'a str>) {
+    for node in &section.content {
+        match node {
+            CommunityNode::Section(section) => collect_github_usernames(section, usernames),
+            CommunityNode::Member(member) => usernames.extend(member.github.as_deref()),
         }
     }
-    Ok(())
 }
@@ -1,5 +1,10 @@
-use serde::Deserialize;
-use std::{collections::HashMap, fs, io, path::PathBuf, str::FromStr};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 #[derive(Deserialize, Debug, Clone)]
 #[serde(deny_unknown_fields, rename_all = "kebab-case")]
@@ -54,6 +59,15 @@ impl Roles {
         }
         map
     }
+
+    /// The GitHub usernames given a role, once per role.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.project_lead
+            .iter()
+            .chain(&self.maintainer)
+            .chain(self.sme.iter().map(|sme| &sme.id))
+            .map(String::as_str)
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -93,10 +107,17 @@ where
     let buf = String::deserialize(deserializer)?;
     let mut details = buf.split('@');
     details.next();
-    Ok(Some(Mastodon {
-        username: details.next().unwrap().to_string(),
-        instance: details.next().unwrap().to_string(),
-    }))
+    match (details.next(), details.next(), details.next()) {
+        (Some(username), Some(instance), None) if !username.is_empty() && !instance.is_empty() => {
+            Ok(Some(Mastodon {
+                username: username.to_string(),
+                instance: instance.to_string(),
+            }))
+        }
+        _ => Err(serde::de::Error::custom(format!(
+            "invalid Mastodon account {buf:?}, expected @username@instance"
+        ))),
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -173,10 +194,7 @@ fn visit_dirs(dir: PathBuf, section: &mut Section) -> io::Result<()> {
             if path.is_dir() {
                 let folder = path.file_name().unwrap();
                 let (order, sort_order_reversed) = if path.join("_category.toml").exists() {
-                    let from_file: toml::Value = toml::de::from_str(
-                        &fs::read_to_string(path.join("_category.toml")).unwrap(),
-                    )
-                    .unwrap();
+                    let from_file: toml::Value = read_toml(&path.join("_category.toml"))?;
                     (
                         from_file
                             .get("order")
@@ -208,8 +226,7 @@ fn visit_dirs(dir: PathBuf, section: &mut Section) -> io::Result<()> {
                 {
                     continue;
                 }
-                let mut member: Member =
-                    toml::de::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+                let mut member: Member = read_toml(&path)?;
                 member.original_path = Some(path);
                 section.content.push(CommunityNode::Member(member));
             }
@@ -218,6 +235,21 @@ fn visit_dirs(dir: PathBuf, section: &mut Section) -> io::Result<()> {
     Ok(())
 }
 
+/// Reads the TOML file at `path`, with the path in the error if it isn't valid.
+fn read_toml<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    toml::de::from_str(&fs::read_to_string(path)?).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {err}", path.display()),
+        )
+    })
+}
+
+/// Reads the roles of the members from the `_roles.toml` file of `community_dir`.
+pub fn parse_roles(community_dir: &str) -> io::Result<Roles> {
+    read_toml(&Path::new(community_dir).join("_roles.toml"))
+}
+
 pub fn parse_members(community_dir: &str) -> io::Result<Section> {
     let mut people_root_section = Section {
         name: "People".to_string(),
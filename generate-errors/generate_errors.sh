@@ -3,12 +3,16 @@
 # Switch to script's directory, letting it be called from any folder.
 cd $(dirname $0)
 
+# The Bevy release the error codes are documented for, `latest` being the last release.
+# Set `BEVY_RELEASE` to a tag, like `v0.14.0`, to pin another one.
+BEVY_RELEASE=${BEVY_RELEASE:-latest}
+
 # Only download the `errors` folder from the main Bevy repository.
 git init bevy
 cd bevy
 git remote add origin https://github.com/bevyengine/bevy
 git sparse-checkout set "errors"
-git pull --depth=1 origin latest
+git pull --depth=1 origin "$BEVY_RELEASE"
 cd ..
 
 cargo run --bin generate -- bevy/errors ../content/learn
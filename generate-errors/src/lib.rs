@@ -17,11 +17,14 @@ pub struct Section {
 pub struct ErrorCode {
     pub code: String,
     pub content: String,
+    /// The first paragraph of the page, indexed by the search of the website.
+    pub description: String,
 }
 
 #[derive(Serialize)]
 pub struct FrontMatterErrorCode {
     pub title: String,
+    pub description: String,
     pub weight: usize,
 }
 
@@ -29,11 +32,70 @@ impl From<&ErrorCode> for FrontMatterErrorCode {
     fn from(asset: &ErrorCode) -> Self {
         FrontMatterErrorCode {
             title: asset.code.clone(),
+            description: asset.description.clone(),
             weight: 0,
         }
     }
 }
 
+/// The attributes rustdoc accepts on the Rust code blocks of the error pages.
+const RUST_ATTRIBUTES: &[&str] = &["should_panic", "no_run", "ignore", "compile_fail"];
+
+/// Checks the code blocks of the page of an error are closed, and their Rust code blocks
+/// have attributes rustdoc knows and some code, returning one message per invalid block.
+pub fn validate_code_blocks(content: &str) -> Vec<String> {
+    let mut errors = vec![];
+    // The line the open code block starts at, with its info string
+    let mut open: Option<(usize, &str)> = None;
+    let mut is_empty = true;
+    for (i, line) in content.lines().enumerate() {
+        let line_number = i + 1;
+        let Some(info) = line.trim_start().strip_prefix("```") else {
+            if !line.trim().is_empty() {
+                is_empty = false;
+            }
+            continue;
+        };
+        match open.take() {
+            None => {
+                open = Some((line_number, info.trim()));
+                is_empty = true;
+            }
+            Some((start, info)) => {
+                let mut attributes = info.split(',').map(str::trim);
+                if attributes.next() == Some("rust") {
+                    for attribute in attributes {
+                        let is_edition = attribute.starts_with("edition");
+                        if !is_edition && !RUST_ATTRIBUTES.contains(&attribute) {
+                            errors.push(format!(
+                                "line {start}: unknown attribute `{attribute}` of a Rust code block"
+                            ));
+                        }
+                    }
+                    if is_empty {
+                        errors.push(format!("line {start}: empty Rust code block"));
+                    }
+                }
+            }
+        }
+    }
+    if let Some((start, _)) = open {
+        errors.push(format!("line {start}: code block isn't closed"));
+    }
+    errors
+}
+
+/// The first paragraph of `content`, on one line.
+fn first_paragraph(content: &str) -> String {
+    content
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty() && !line.starts_with("```"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 fn visit_dirs(dir: PathBuf, section: &mut Section) -> io::Result<()> {
     assert!(dir.is_dir(), "The path to the errors is not a directory");
     let error_code_pattern = Regex::new(r"B[0-9]{4}\.md").unwrap();
@@ -52,11 +114,19 @@ fn visit_dirs(dir: PathBuf, section: &mut Section) -> io::Result<()> {
             }
 
             let error_code = read_to_string(path.clone())?;
+            let errors = validate_code_blocks(&error_code);
+            if !errors.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), errors.join(", ")),
+                ));
+            }
 
             let code = file_name.trim_end_matches(".md").to_owned();
+            let content = error_code.trim_start_matches(&format!("# {}", code.clone()));
             section.content.push(ErrorCode {
-                content: error_code
-                    .trim_start_matches(&format!("# {}", code.clone()))
+                description: first_paragraph(content),
+                content: content
                     .replace("```rust,*", "```rust")
                     .lines()
                     .map(|line| {
@@ -95,6 +165,34 @@ pub fn parse_errors(errors_dir: &str) -> io::Result<Section> {
     Ok(errors_root_section)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_code_blocks() {
+        let page = "# B0001\n\nSome text.\n\n```rust,should_panic\nfn main() {}\n```\n";
+        assert!(validate_code_blocks(page).is_empty());
+        assert_eq!(
+            validate_code_blocks("```rust,no_rub\nfn main() {}\n```\n\n```rust\n```\n"),
+            [
+                "line 1: unknown attribute `no_rub` of a Rust code block",
+                "line 5: empty Rust code block",
+            ]
+        );
+        assert_eq!(
+            validate_code_blocks("```text\nsome output\n"),
+            ["line 1: code block isn't closed"]
+        );
+    }
+
+    #[test]
+    fn describes_errors_by_their_first_paragraph() {
+        let content = "\n\nA system has\n`Query` conflicts.\n\n```rust\nfn main() {}\n```\n";
+        assert_eq!(first_paragraph(content), "A system has `Query` conflicts.");
+    }
+}
+
 #[cfg(test)]
 mod test_regex {
     use regex::Regex;
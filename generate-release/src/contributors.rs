@@ -0,0 +1,34 @@
+use crate::github_client::GithubClient;
+use crate::helpers::{
+    get_first_time_contributors, get_release_prs, write_contributors,
+    write_first_time_contributors, RELEASE_POST_CONTRIBUTORS_INTRO,
+};
+use std::path::PathBuf;
+
+/// Generates the contributors section of a release post, welcoming the first-time contributors
+pub fn generate_contributors(
+    from: &str,
+    to: &str,
+    path: PathBuf,
+    client: &mut GithubClient,
+) -> anyhow::Result<()> {
+    let prs = get_release_prs(client, from, to)?;
+
+    println!(
+        "Found {} contributors from {} to {}",
+        prs.authors.len(),
+        from,
+        to
+    );
+
+    let first_time = get_first_time_contributors(client, from, &prs)?;
+
+    let mut output = String::new();
+
+    write_contributors(&mut output, RELEASE_POST_CONTRIBUTORS_INTRO, &prs.authors)?;
+    write_first_time_contributors(&mut output, &first_time)?;
+
+    std::fs::write(path, output)?;
+
+    Ok(())
+}
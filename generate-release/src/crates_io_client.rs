@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CratesIoVersionsResponse {
+    pub versions: Vec<CratesIoVersion>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CratesIoVersion {
+    pub num: String,
+    pub created_at: DateTime<Utc>,
+    pub yanked: bool,
+}
+
+pub struct CratesIoClient {
+    agent: ureq::Agent,
+}
+
+impl CratesIoClient {
+    pub fn new() -> Self {
+        // crates.io requires a user agent telling who makes the requests
+        let agent: ureq::Agent = ureq::AgentBuilder::new()
+            .user_agent(
+                "bevy-website-generate-release (https://github.com/bevyengine/bevy-website)",
+            )
+            .build();
+
+        Self { agent }
+    }
+
+    /// Gets the versions of a crate, newest first.
+    pub fn get_versions(&self, crate_name: &str) -> anyhow::Result<Vec<CratesIoVersion>> {
+        let request = self.agent.get(&format!(
+            "https://crates.io/api/v1/crates/{crate_name}/versions"
+        ));
+        let response: CratesIoVersionsResponse = request.call()?.into_json()?;
+        Ok(response.versions)
+    }
+}
@@ -5,11 +5,13 @@ use chrono::{DateTime, NaiveDate, TimeZone, Utc};
 use serde::Deserialize;
 
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubBranchesResponse {
     pub name: String,
     pub commit: GithubBranchesCommitResponse,
 }
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubBranchesCommitResponse {
     pub sha: String,
 }
@@ -18,11 +20,13 @@ pub struct GithubBranchesCommitResponse {
 pub struct GithubCommitResponse {
     pub sha: String,
     pub commit: GithubCommitContent,
+    #[allow(dead_code)]
     pub author: Option<GithubUser>,
 }
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct Committer {
+    #[allow(dead_code)]
     pub name: String,
     pub date: String,
 }
@@ -35,23 +39,34 @@ pub struct GithubCommitContent {
     pub committer: Committer,
 }
 
+/// An author of a commit, by their GitHub login, or by the name in the commit if it has no
+/// GitHub user.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CommitAuthor {
+    Login(String),
+    Name(String),
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct GithubUser {
     pub login: String,
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubCommitBranchResponse {
     pub name: String,
     pub commit: GithubCommitBranchCommitResponse,
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubCommitBranchCommitResponse {
     pub sha: String,
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubPullRequestResponse {
     pub title: String,
     pub number: i32,
@@ -63,6 +78,7 @@ pub struct GithubPullRequestResponse {
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct GithubCompareResponse {
+    #[allow(dead_code)]
     pub base_commit: GithubCommitResponse,
     pub commits: Vec<GithubCommitResponse>,
 }
@@ -73,6 +89,7 @@ pub struct GithubLabel {
 }
 
 #[derive(Deserialize, Clone, Debug)]
+#[allow(dead_code)]
 pub struct GithubUserSearchResponse {
     pub items: Vec<GithubUser>,
 }
@@ -236,7 +253,7 @@ impl GithubClient {
             .collect())
     }
 
-    pub fn get_contributors(&self, commit_sha: &str) -> anyhow::Result<Vec<String>> {
+    pub fn get_contributors(&self, commit_sha: &str) -> anyhow::Result<Vec<CommitAuthor>> {
         let query = format!(
             r#"
 query {{
@@ -271,14 +288,14 @@ query {{
         };
         for node in nodes {
             if let Some(login) = &node["user"]["login"].as_str() {
-                logins.push(login.to_string());
+                logins.push(CommitAuthor::Login(login.to_string()));
                 continue;
             } else if node["user"].is_null() {
                 // In some situations, github doesn't have a github user associated with a commit,
                 // so instead we need to get the name associated with the commit
                 if let Some(name) = &node["name"].as_str() {
                     println!("\x1b[93mUser not found, using name instead.\n{json}\x1b[0m");
-                    logins.push(name.to_string());
+                    logins.push(CommitAuthor::Name(name.to_string()));
                     continue;
                 }
             }
@@ -287,6 +304,17 @@ query {{
         Ok(logins)
     }
 
+    /// Whether `login` authored a commit of the repo before `date`, in the ISO 8601 format.
+    pub fn has_commits_before(&self, login: &str, date: &str) -> anyhow::Result<bool> {
+        let request = self
+            .get("commits")
+            .query("author", login)
+            .query("until", date)
+            .query("per_page", "1");
+        let commits: Vec<GithubCommitResponse> = request.call()?.into_json()?;
+        Ok(!commits.is_empty())
+    }
+
     pub fn get_commit(&self, git_ref: &str) -> anyhow::Result<GithubCommitResponse> {
         let request = self.get(&format!("commits/{git_ref}"));
         Ok(request.call()?.into_json()?)
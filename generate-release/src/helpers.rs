@@ -1,6 +1,174 @@
-use crate::github_client::{GithubClient, GithubCommitResponse, GithubIssuesResponse};
+use crate::github_client::{
+    CommitAuthor, GithubClient, GithubCommitResponse, GithubIssuesResponse,
+};
 use anyhow::{bail, Context};
 use regex::Regex;
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+};
+
+/// The PRs merged between two git refs, sorted by area labels, and the people that authored them.
+pub struct ReleasePrs {
+    /// The title of each PR, by number.
+    pub titles: BTreeMap<i32, String>,
+    /// The numbers of the PRs of each area label.
+    pub areas: BTreeMap<String, Vec<i32>>,
+    /// The authors and co-authors of the PRs, by login or by name without a GitHub user.
+    pub authors: HashSet<String>,
+    /// The GitHub logins of the authors.
+    pub logins: HashSet<String>,
+}
+
+pub fn get_release_prs(
+    client: &mut GithubClient,
+    from: &str,
+    to: &str,
+) -> anyhow::Result<ReleasePrs> {
+    let mut release_prs = ReleasePrs {
+        titles: BTreeMap::new(),
+        areas: BTreeMap::new(),
+        authors: HashSet::new(),
+        logins: HashSet::new(),
+    };
+
+    let merged_prs = get_merged_prs(client, from, to, None)?;
+    for (pr, commit, title) in &merged_prs {
+        let contributors = get_contributors(client, commit, pr)?;
+        for c in contributors {
+            match c {
+                CommitAuthor::Login(login) => {
+                    release_prs.authors.insert(login.clone());
+                    release_prs.logins.insert(login);
+                }
+                CommitAuthor::Name(name) => {
+                    release_prs.authors.insert(name);
+                }
+            }
+        }
+
+        release_prs.titles.insert(pr.number, title.to_string());
+
+        let area = get_pr_area(pr);
+        release_prs.areas.entry(area).or_default().push(pr.number);
+
+        release_prs.authors.insert(pr.user.login.clone());
+        release_prs.logins.insert(pr.user.login.clone());
+        println!(
+            "[{title}](https://github.com/bevyengine/{}/pull/{})",
+            client.repo, pr.number
+        );
+    }
+
+    println!(
+        "Found {} merged prs from {} to {}",
+        merged_prs.len(),
+        from,
+        to,
+    );
+
+    Ok(release_prs)
+}
+
+/// The introduction of the contributors section of the release post, see [`write_contributors`].
+pub const RELEASE_POST_CONTRIBUTORS_INTRO: &str =
+    "Bevy is made by a [large group of people](/community/people/). ";
+
+/// Writes the contributors section of a release, thanking every author in random order after
+/// `intro`, which is empty or ends with a space.
+pub fn write_contributors(
+    output: &mut String,
+    intro: &str,
+    authors: &HashSet<String>,
+) -> std::fmt::Result {
+    writeln!(output, "## Contributors\n")?;
+    writeln!(output, "{intro}A huge thanks to the {} contributors that made this release (and associated docs) possible! In random order:\n", authors.len())?;
+    for author in authors {
+        writeln!(output, "- @{author}")?;
+    }
+    writeln!(output)
+}
+
+/// The authors with a GitHub login and without a commit before `from`, sorted by login.
+pub fn get_first_time_contributors(
+    client: &mut GithubClient,
+    from: &str,
+    prs: &ReleasePrs,
+) -> anyhow::Result<Vec<String>> {
+    // Commits can only be searched by login, the co-authors without a GitHub user are left out
+    for name in prs.authors.difference(&prs.logins) {
+        println!(
+            "\x1b[93m{name} has no GitHub user, check if it's their first contribution\x1b[0m"
+        );
+    }
+
+    // Contributors are returning if they authored a commit before the one we start from
+    let base_commit = client.get_commit(from)?;
+    let base_commit_date = &base_commit.commit.committer.date;
+    let mut first_time = vec![];
+    for login in &prs.logins {
+        match client.has_commits_before(login, base_commit_date) {
+            Ok(true) => {}
+            Ok(false) => first_time.push(login.clone()),
+            Err(err) => println!("\x1b[93mCommits of {login} not found: {err}\x1b[0m"),
+        }
+    }
+    first_time.sort_by_key(|author| author.to_lowercase());
+
+    println!("Found {} first-time contributors", first_time.len());
+
+    Ok(first_time)
+}
+
+/// Writes the section welcoming the first-time contributors, following [`write_contributors`].
+pub fn write_first_time_contributors(
+    output: &mut String,
+    first_time: &[String],
+) -> std::fmt::Result {
+    writeln!(output, "### First-Time Contributors\n")?;
+    writeln!(
+        output,
+        "A special welcome to the {} people that contributed to Bevy for the first time:\n",
+        first_time.len()
+    )?;
+    for author in first_time {
+        writeln!(output, "- @{author}")?;
+    }
+    writeln!(output)
+}
+
+/// Writes the list of the PRs of each area label, followed by the links to the PRs.
+///
+/// `area_heading` is the markdown heading of the areas, like `##`.
+pub fn write_changelog(
+    output: &mut String,
+    prs: &ReleasePrs,
+    repo: &str,
+    area_heading: &str,
+) -> std::fmt::Result {
+    for (area, numbers) in &prs.areas {
+        writeln!(output)?;
+        writeln!(output, "{area_heading} {area}")?;
+        writeln!(output)?;
+
+        for pr_number in numbers {
+            let Some(pr_title) = prs.titles.get(pr_number) else {
+                continue;
+            };
+            writeln!(output, "- [{pr_title}][{pr_number}]")?;
+        }
+    }
+
+    writeln!(output)?;
+
+    for pr in prs.titles.keys() {
+        writeln!(
+            output,
+            "[{pr}]: https://github.com/bevyengine/{repo}/pull/{pr}"
+        )?;
+    }
+    Ok(())
+}
 
 pub fn get_merged_prs(
     client: &GithubClient,
@@ -88,7 +256,7 @@ pub fn get_contributors(
     client: &mut GithubClient,
     commit: &GithubCommitResponse,
     pr: &GithubIssuesResponse,
-) -> anyhow::Result<Vec<String>> {
+) -> anyhow::Result<Vec<CommitAuthor>> {
     // Find authors and co-authors
     // TODO this could probably be done with multiple threads to speed it up
     match client.get_contributors(&commit.sha) {
@@ -1,22 +1,29 @@
 use changelog::generate_changelog;
 use clap::{Parser as ClapParser, Subcommand};
+use contributors::generate_contributors;
 use migration_guide::generate_migration_guide;
+use newsletter::{generate_newsletter, NewsletterSources};
 use release_notes::generate_release_note;
 use release_notes_website::generate_release_notes_website;
+use release_post::generate_release_post;
 use std::path::PathBuf;
 
 mod changelog;
+mod contributors;
+mod crates_io_client;
 mod github_client;
 mod helpers;
 mod markdown;
 mod migration_guide;
+mod newsletter;
 mod release_notes;
 mod release_notes_website;
+mod release_post;
 
 /// Generates markdown files used for a bevy releases.
 ///
 /// Migration Guide:
-/// * Gets all merged PRs with the `C-Breaking-Change` label.
+/// * Gets all merged PRs with the `M-Needs-Migration-Guide` or `C-Breaking-Change` label, or a migration guide section.
 /// * For each PR:
 ///     * Generate the title with a link to the relevant PR and
 ///     * Generate the migration guide section. This parses the markdown and generates valid makrdown that should pass markdownlint rules.
@@ -27,11 +34,28 @@ mod release_notes_website;
 /// * Sort each PR per area label
 /// * Generate the list of merge PR
 ///
+/// Release post:
+/// * Same as the release notes, as the skeleton of the blog post of the release
+/// * Adds a section to fill for each area label, listing its PRs to pick the highlights from
+/// * Welcomes the first-time contributors, like the contributors command
+///
+/// Contributors:
+/// * Collect each author of the merged PRs
+/// * Welcome the ones without a commit before the PRs as first-time contributors
+///
+/// Newsletter:
+/// * Gets all PRs merged between two dates, sorted per area label
+/// * List the assets added since the `assets.json` of generate-assets of the previous newsletter
+/// * List the releases of the tracked crates of the ecosystem
+///
 /// Requires a valid `GITHUB_TOKEN` environment variable, you can use a .env file or use your preferred method of passing env arguments.
 ///
 /// Example used to generate for 0.9:
 /// cargo run -- migration-guide --from v0.9.0 --to main --title "0.9 to 0.10" --weight 6
 /// cargo run -- release-note --from v0.9.0 --to main
+/// cargo run -- release-post --version 0.10 --from v0.9.0 --to main
+/// cargo run -- contributors --from v0.9.0 --to main
+/// cargo run -- newsletter --from 2024-03-01 --to 2024-03-31 --assets assets.json --crates crates.txt
 /// cargo run -- release-note-website --from bd4f611f7576c55739b466c6f0039e8421dab57e --to HEAD
 #[derive(ClapParser)]
 #[command(author, version, about)]
@@ -76,6 +100,23 @@ enum Commands {
         #[arg(short, long)]
         path: Option<std::path::PathBuf>,
     },
+    ReleasePost {
+        /// The version being released, like 0.10
+        #[arg(short, long)]
+        version: String,
+
+        /// The name of the branch / tag to start from
+        #[arg(short, long)]
+        from: String,
+
+        /// The name of the branch / tag to end on
+        #[arg(short, long)]
+        to: String,
+
+        /// Path used to output the generated file. Defaults to ./release-post.md
+        #[arg(short, long)]
+        path: Option<std::path::PathBuf>,
+    },
     ReleaseNoteWebsite {
         /// The name of the branch / tag to start from
         #[arg(short, long)]
@@ -89,6 +130,45 @@ enum Commands {
         #[arg(short, long)]
         path: Option<std::path::PathBuf>,
     },
+    Contributors {
+        /// The name of the branch / tag to start from
+        #[arg(short, long)]
+        from: String,
+
+        /// The name of the branch / tag to end on
+        #[arg(short, long)]
+        to: String,
+
+        /// Path used to output the generated file. Defaults to ./contributors.md
+        #[arg(short, long)]
+        path: Option<std::path::PathBuf>,
+    },
+    Newsletter {
+        /// The first day of the newsletter, like 2024-03-01
+        #[arg(short, long)]
+        from: String,
+
+        /// The last day of the newsletter, like 2024-03-31
+        #[arg(short, long)]
+        to: String,
+
+        /// The `assets.json` file written by generate-assets, to list the new assets
+        #[arg(long)]
+        assets: Option<PathBuf>,
+
+        /// The `assets.json` file of the previous newsletter. Without it, the new assets are
+        /// the ones added between the two dates
+        #[arg(long)]
+        previous_assets: Option<PathBuf>,
+
+        /// A file listing the crates of the ecosystem to list the releases of, one per line
+        #[arg(long)]
+        crates: Option<PathBuf>,
+
+        /// Path used to output the generated file. Defaults to ./newsletter.md
+        #[arg(short, long)]
+        path: Option<std::path::PathBuf>,
+    },
     Changelog {
         /// The name of the branch / tag to start from
         #[arg(short, long)]
@@ -139,12 +219,48 @@ fn main() -> anyhow::Result<()> {
             path.unwrap_or_else(|| PathBuf::from("./release-notes.md")),
             &mut client,
         )?,
+        Commands::ReleasePost {
+            version,
+            from,
+            to,
+            path,
+        } => generate_release_post(
+            &version,
+            &from,
+            &to,
+            path.unwrap_or_else(|| PathBuf::from("./release-post.md")),
+            &mut client,
+        )?,
         Commands::ReleaseNoteWebsite { from, to, path } => generate_release_notes_website(
             &from,
             &to,
             path.unwrap_or_else(|| PathBuf::from("./release-notes-website.md")),
             &mut client,
         )?,
+        Commands::Contributors { from, to, path } => generate_contributors(
+            &from,
+            &to,
+            path.unwrap_or_else(|| PathBuf::from("./contributors.md")),
+            &mut client,
+        )?,
+        Commands::Newsletter {
+            from,
+            to,
+            assets,
+            previous_assets,
+            crates,
+            path,
+        } => generate_newsletter(
+            &from,
+            &to,
+            NewsletterSources {
+                assets,
+                previous_assets,
+                crates,
+            },
+            path.unwrap_or_else(|| PathBuf::from("./newsletter.md")),
+            &mut client,
+        )?,
         Commands::Changelog { from, to, path } => generate_changelog(
             &from,
             &to,
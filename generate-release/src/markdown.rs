@@ -40,18 +40,14 @@ pub fn write_markdown_section(
         let mut list_item_level = 0;
         for event in markdown.by_ref() {
             match event {
-                Event::Start(Tag::Heading(level, _, _)) => {
-                    if level <= heading_level {
-                        // go until next heading
-                        break;
-                    }
+                Event::Start(Tag::Heading(level, _, _)) if level <= heading_level => {
+                    // go until next heading
+                    break;
                 }
                 Event::Start(Tag::List(_)) => list_item_level += 1,
                 Event::End(Tag::List(_)) => list_item_level -= 1,
-                Event::End(Tag::Heading(level, _, _)) => {
-                    if level == heading_level {
-                        println!("!!! end of heading !!!");
-                    }
+                Event::End(Tag::Heading(level, _, _)) if level == heading_level => {
+                    println!("!!! end of heading !!!");
                 }
                 Event::Start(Tag::Link(_, _, _)) => {
                     write!(output, "[")?;
@@ -97,9 +93,8 @@ fn write_markdown_event(
                 lang.to_string()
             }
         )?,
-        Event::End(Tag::CodeBlock(CodeBlockKind::Fenced(_))) => writeln!(output, "```")?,
         Event::Start(Tag::CodeBlock(CodeBlockKind::Indented)) => writeln!(output, "\n```",)?,
-        Event::End(Tag::CodeBlock(CodeBlockKind::Indented)) => writeln!(output, "```")?,
+        Event::End(Tag::CodeBlock(_)) => writeln!(output, "```")?,
         Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => write!(output, "_")?,
         Event::Start(Tag::Strong) | Event::End(Tag::Strong) => write!(output, "**",)?,
         Event::Start(Tag::Heading(_, _, _)) => {
@@ -109,7 +104,10 @@ fn write_markdown_event(
         }
         Event::End(Tag::Heading(_, _, _)) => writeln!(output, "__")?,
         // FIXME List currently always assume they are unordered
-        Event::Start(Tag::List(_)) => writeln!(output)?,
+        Event::Start(Tag::List(_))
+        | Event::End(Tag::Item | Tag::Paragraph | Tag::BlockQuote)
+        | Event::Start(Tag::Paragraph)
+        | Event::SoftBreak => writeln!(output)?,
         Event::End(Tag::List(_)) => {}
         Event::Start(Tag::Item) => {
             // Add indentation
@@ -118,14 +116,9 @@ fn write_markdown_event(
             }
             write!(output, "- ")?;
         }
-        Event::End(Tag::Item) => writeln!(output)?,
-        Event::Start(Tag::Paragraph) => writeln!(output)?,
-        Event::End(Tag::Paragraph) => writeln!(output)?,
         Event::Text(text) => write!(output, "{text}")?,
         Event::Code(text) => write!(output, "`{text}`")?,
-        Event::SoftBreak => writeln!(output)?,
         Event::Start(Tag::BlockQuote) => write!(output, "\n> ")?,
-        Event::End(Tag::BlockQuote) => writeln!(output)?,
         Event::Html(html) => write!(output, "{html}")?,
         Event::Rule => writeln!(output, "---")?,
         _ => println!("\x1b[93mUnknown event: {event:?}\x1b[0m"),
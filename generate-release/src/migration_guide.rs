@@ -5,6 +5,9 @@ use crate::{
 };
 use std::{collections::BTreeMap, fmt::Write, path::PathBuf};
 
+/// Labels of the PRs that need a migration guide, even if they don't have its section yet.
+const MIGRATION_GUIDE_LABELS: &[&str] = &["M-Needs-Migration-Guide", "C-Breaking-Change"];
+
 pub fn generate_migration_guide(
     title: &str,
     weight: i32,
@@ -43,14 +46,14 @@ As a result, the Minimum Supported Rust Version (MSRV) is "the latest stable rel
         };
 
         let has_migration_guide_section = body.to_lowercase().contains("## migration guide");
-        let has_breaking_label = pr
+        let has_migration_guide_label = pr
             .labels
             .iter()
-            .any(|l| l.name.contains("C-Breaking-Change"));
+            .any(|l| MIGRATION_GUIDE_LABELS.contains(&l.name.as_str()));
 
-        // We want to check for PRs with the breaking label but without the guide section
+        // We want to check for PRs with the label but without the guide section
         // to make it easier to track down missing guides
-        if has_migration_guide_section || has_breaking_label {
+        if has_migration_guide_section || has_migration_guide_label {
             let area = get_pr_area(pr);
             areas
                 .entry(area)
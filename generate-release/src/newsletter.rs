@@ -0,0 +1,182 @@
+use crate::{
+    crates_io_client::CratesIoClient,
+    github_client::{GithubClient, GithubIssuesResponse},
+    helpers::get_pr_area,
+};
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate};
+use generate_assets::diff::{read_index, IndexDiff};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    path::PathBuf,
+};
+
+/// Parses a `YYYY-MM-DD` date, or the date of an RFC 3339 date and time.
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()
+        .or_else(|| Some(DateTime::parse_from_rfc3339(date).ok()?.date_naive()))
+}
+
+/// The sources of the newsletter besides the PRs of Bevy.
+pub struct NewsletterSources {
+    /// The `assets.json` file of generate-assets with the current assets.
+    pub assets: Option<PathBuf>,
+    /// The `assets.json` file of the previous newsletter, the assets not in it being the new ones.
+    /// Without it, the new assets are the ones added in the date range.
+    pub previous_assets: Option<PathBuf>,
+    /// A file listing the crates of the ecosystem to list the releases of, one per line.
+    pub crates: Option<PathBuf>,
+}
+
+/// Generates the draft of the "This Month in Bevy" post, listing the PRs merged between two dates
+/// sorted by area labels, the new assets and the releases of the crates of the ecosystem
+pub fn generate_newsletter(
+    from: &str,
+    to: &str,
+    sources: NewsletterSources,
+    path: PathBuf,
+    client: &mut GithubClient,
+) -> anyhow::Result<()> {
+    let from_date = NaiveDate::parse_from_str(from, "%Y-%m-%d")?;
+    let to_date = NaiveDate::parse_from_str(to, "%Y-%m-%d")?;
+    // The range includes both dates
+    let in_range = |date: NaiveDate| from_date <= date && date <= to_date;
+
+    let mut output = String::new();
+
+    writeln!(
+        &mut output,
+        r#"+++
+title = "This Month in Bevy: {}"
+date = {}
+authors = ["Bevy Contributors"]
+draft = true
++++
+
+<!-- TODO introduction -->
+
+<!-- more -->"#,
+        from_date.format("%B %Y"),
+        to_date,
+    )?;
+
+    println!("Getting list of all merged PRs from {from} to {to}");
+    let mut areas = BTreeMap::<String, Vec<GithubIssuesResponse>>::new();
+    let mut authors = HashSet::new();
+    let prs = client.get_merged_prs(from, None)?;
+    for pr in prs {
+        let merged_at = pr
+            .pull_request
+            .as_ref()
+            .and_then(|pull_request| pull_request.merged_at.as_deref())
+            .and_then(parse_date);
+        if !merged_at.is_some_and(in_range) {
+            continue;
+        }
+        authors.insert(pr.user.login.clone());
+        areas.entry(get_pr_area(&pr)).or_default().push(pr);
+    }
+    let pr_count: usize = areas.values().map(Vec::len).sum();
+    println!("Found {pr_count} merged PRs from {} authors", authors.len());
+
+    writeln!(&mut output, "\n## Engine\n")?;
+    writeln!(
+        &mut output,
+        "{pr_count} PRs from {} contributors were merged this month.",
+        authors.len()
+    )?;
+    for (area, mut prs) in areas {
+        writeln!(&mut output, "\n### {area}\n")?;
+        prs.sort_by_key(|pr| pr.closed_at);
+        for pr in prs {
+            writeln!(
+                &mut output,
+                "- [{}](https://github.com/bevyengine/{}/pull/{}) by @{}",
+                pr.title, client.repo, pr.number, pr.user.login
+            )?;
+        }
+    }
+
+    if let Some(assets_path) = &sources.assets {
+        let assets = read_index(assets_path)?;
+        let previous_assets = match &sources.previous_assets {
+            Some(previous_path) => Some(read_index(previous_path)?),
+            None => None,
+        };
+        let new_assets = match &previous_assets {
+            Some(previous_assets) => IndexDiff::new(previous_assets, &assets).added,
+            None => assets
+                .assets
+                .iter()
+                .filter(|asset| {
+                    asset
+                        .added
+                        .as_deref()
+                        .and_then(parse_date)
+                        .is_some_and(in_range)
+                })
+                .collect(),
+        };
+        println!("Found {} new assets", new_assets.len());
+
+        writeln!(&mut output, "\n## New Assets\n")?;
+        if new_assets.is_empty() {
+            writeln!(&mut output, "No asset was added this month.")?;
+        }
+        for asset in new_assets {
+            writeln!(
+                &mut output,
+                "- [{}]({}) in {}: {}",
+                asset.name,
+                asset.link,
+                asset.sections.join(" / "),
+                asset.description
+            )?;
+        }
+    }
+
+    if let Some(crates_path) = &sources.crates {
+        let crates = std::fs::read_to_string(crates_path)
+            .with_context(|| format!("Reading {crates_path:?}"))?;
+        let crates_io = CratesIoClient::new();
+
+        writeln!(&mut output, "\n## Ecosystem Releases\n")?;
+        let mut count = 0;
+        for crate_name in crates
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let versions = match crates_io.get_versions(crate_name) {
+                Ok(versions) => versions,
+                Err(err) => {
+                    println!("\x1b[93mVersions of {crate_name} not found: {err}\x1b[0m");
+                    continue;
+                }
+            };
+            // Versions are listed newest first, the newest of the month being the one to mention
+            let Some(version) = versions
+                .iter()
+                .find(|version| !version.yanked && in_range(version.created_at.date_naive()))
+            else {
+                continue;
+            };
+            writeln!(
+                &mut output,
+                "- [{crate_name} {}](https://crates.io/crates/{crate_name}/{})",
+                version.num, version.num
+            )?;
+            count += 1;
+        }
+        println!("Found {count} crates released");
+        if count == 0 {
+            writeln!(&mut output, "No tracked crate was released this month.")?;
+        }
+    }
+
+    std::fs::write(path, output)?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use crate::github_client::GithubClient;
+use crate::helpers::{
+    get_first_time_contributors, get_release_prs, write_changelog, write_contributors,
+    write_first_time_contributors, RELEASE_POST_CONTRIBUTORS_INTRO,
+};
+use std::{fmt::Write, path::PathBuf};
+
+/// Generates the skeleton of the blog post of a release: a section to fill per area label,
+/// the list of contributors welcoming the first-time ones and the full changelog of the PRs sorted by area labels
+pub fn generate_release_post(
+    version: &str,
+    from: &str,
+    to: &str,
+    path: PathBuf,
+    client: &mut GithubClient,
+) -> anyhow::Result<()> {
+    let prs = get_release_prs(client, from, to)?;
+    let first_time = get_first_time_contributors(client, from, &prs)?;
+
+    let mut output = String::new();
+
+    writeln!(
+        &mut output,
+        r#"+++
+title = "Bevy {version}"
+date = {}
+authors = ["Bevy Contributors"]
+[extra]
+image = "TODO"
+show_image = true
++++
+
+Thanks to **{}** contributors, **{}** pull requests, community reviewers, and our [**generous sponsors**](/community/donate), we're happy to announce the **Bevy {version}** release on [crates.io](https://crates.io/crates/bevy)!
+
+For those who don't know, Bevy is a refreshingly simple data-driven game engine built in Rust. You can check out our [Quick Start Guide](/learn/quick-start) to try it today. It's free and open source forever! You can grab the full [source code](https://github.com/bevyengine/bevy) on GitHub. Check out [Bevy Assets](https://bevyengine.org/assets) for a collection of community-developed plugins, games, and learning resources.
+
+Since our last release a few months ago we've added a _ton_ of new features, bug fixes, and quality of life tweaks, but here are some of the highlights:
+
+<!-- TODO highlights -->
+
+<!-- more -->"#,
+        chrono::Utc::now().format("%Y-%m-%d"),
+        prs.authors.len(),
+        prs.titles.len(),
+    )?;
+
+    // One section per area, listing its PRs to pick the highlights from
+    for (area, numbers) in &prs.areas {
+        writeln!(&mut output)?;
+        writeln!(&mut output, "## {area}")?;
+        writeln!(&mut output)?;
+        writeln!(&mut output, "<!-- TODO")?;
+        for pr_number in numbers {
+            let Some(pr_title) = prs.titles.get(pr_number) else {
+                continue;
+            };
+            writeln!(&mut output, "- {pr_title} (#{pr_number})")?;
+        }
+        writeln!(&mut output, "-->")?;
+    }
+
+    writeln!(&mut output)?;
+    writeln!(&mut output, "## What's Next?\n")?;
+    writeln!(&mut output, "<!-- TODO -->\n")?;
+
+    write_contributors(&mut output, RELEASE_POST_CONTRIBUTORS_INTRO, &prs.authors)?;
+    write_first_time_contributors(&mut output, &first_time)?;
+
+    writeln!(&mut output, "## Full Changelog\n")?;
+    writeln!(
+        &mut output,
+        "The changes mentioned above are only the most appealing, highest impact changes that we've made this cycle."
+    )?;
+    writeln!(
+        &mut output,
+        "Innumerable bug fixes, documentation changes and API usability tweaks made it in too."
+    )?;
+    writeln!(
+        &mut output,
+        "For a complete list of changes, check out the PRs listed below."
+    )?;
+
+    write_changelog(&mut output, &prs, &client.repo, "###")?;
+
+    std::fs::write(path, output)?;
+
+    Ok(())
+}
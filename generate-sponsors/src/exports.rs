@@ -0,0 +1,166 @@
+use crate::sponsor::Donation;
+use anyhow::Context;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{io::Read, path::Path};
+
+/// A row of the export of the subscriptions of the Stripe dashboard. Donors can ask to be
+/// anonymous, or give a link, with the `anonymous` and `link` metadata of their subscription.
+#[derive(Deserialize, Debug)]
+struct StripeSubscription {
+    #[serde(rename = "Customer Name")]
+    name: String,
+    /// The amount of each payment, in dollars.
+    #[serde(rename = "Amount")]
+    amount: f64,
+    /// How often the amount is paid, `month` or `year`.
+    #[serde(rename = "Interval")]
+    interval: String,
+    /// The status of the subscription, `active` while it's paid.
+    #[serde(rename = "Status")]
+    status: String,
+    #[serde(rename = "anonymous (metadata)", default)]
+    anonymous: Option<String>,
+    #[serde(rename = "link (metadata)", default)]
+    link: Option<String>,
+}
+
+/// A row of the export of the donations of Every.org.
+#[derive(Deserialize, Debug)]
+struct EveryOrgDonation {
+    #[serde(rename = "First Name", default)]
+    first_name: String,
+    #[serde(rename = "Last Name", default)]
+    last_name: String,
+    /// The amount of the donation, in dollars.
+    #[serde(rename = "Amount")]
+    amount: f64,
+    /// `Monthly` for recurring donations, `One-time` otherwise.
+    #[serde(rename = "Frequency")]
+    frequency: String,
+    /// Whether the donor shared their name, `Yes` or `No`.
+    #[serde(rename = "Share Info", default)]
+    share_info: Option<String>,
+}
+
+/// Whether `value` is a yes of a CSV export, like `true` or `Yes`.
+fn is_yes(value: Option<&str>) -> bool {
+    value.is_some_and(|value| {
+        ["true", "yes", "1"]
+            .iter()
+            .any(|yes| value.trim().eq_ignore_ascii_case(yes))
+    })
+}
+
+fn read_rows<T: DeserializeOwned>(reader: impl Read) -> anyhow::Result<Vec<T>> {
+    csv::Reader::from_reader(reader)
+        .deserialize()
+        .collect::<Result<_, _>>()
+        .map_err(Into::into)
+}
+
+/// Reads the donations of the export of the Stripe subscriptions at `path`.
+pub fn read_stripe_export(path: &Path) -> anyhow::Result<Vec<Donation>> {
+    let file = std::fs::File::open(path).with_context(|| format!("Opening {path:?}"))?;
+    let rows = read_rows(file).with_context(|| format!("Reading {path:?}"))?;
+    Ok(stripe_donations(rows))
+}
+
+fn stripe_donations(rows: Vec<StripeSubscription>) -> Vec<Donation> {
+    rows.into_iter()
+        .map(|row| Donation {
+            monthly_amount: if row.interval.eq_ignore_ascii_case("year") {
+                row.amount / 12.0
+            } else {
+                row.amount
+            },
+            active: row.status.eq_ignore_ascii_case("active"),
+            anonymous: is_yes(row.anonymous.as_deref()),
+            link: row.link.filter(|link| !link.is_empty()),
+            name: row.name,
+            source: "stripe",
+        })
+        .collect()
+}
+
+/// Reads the donations of the export of Every.org at `path`. One-time donations are read as
+/// past donations.
+pub fn read_every_org_export(path: &Path) -> anyhow::Result<Vec<Donation>> {
+    let file = std::fs::File::open(path).with_context(|| format!("Opening {path:?}"))?;
+    let rows = read_rows(file).with_context(|| format!("Reading {path:?}"))?;
+    Ok(every_org_donations(rows))
+}
+
+fn every_org_donations(rows: Vec<EveryOrgDonation>) -> Vec<Donation> {
+    rows.into_iter()
+        .map(|row| {
+            let name = format!("{} {}", row.first_name.trim(), row.last_name.trim());
+            let name = name.trim().to_string();
+            Donation {
+                anonymous: name.is_empty()
+                    || row
+                        .share_info
+                        .as_deref()
+                        .is_some_and(|share| !is_yes(Some(share))),
+                name,
+                link: None,
+                monthly_amount: row.amount,
+                active: row.frequency.eq_ignore_ascii_case("monthly"),
+                source: "every.org",
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_stripe_subscriptions() {
+        let csv = "\
+id,Customer Name,Amount,Interval,Status,anonymous (metadata),link (metadata)
+sub_1,Dead Money,250.00,month,active,,https://deadmoney.gg/
+sub_2,Jane Doe,300.00,year,canceled,true,
+";
+        let donations = stripe_donations(read_rows(csv.as_bytes()).unwrap());
+        assert_eq!(
+            donations,
+            [
+                Donation {
+                    name: "Dead Money".to_string(),
+                    link: Some("https://deadmoney.gg/".to_string()),
+                    monthly_amount: 250.0,
+                    active: true,
+                    anonymous: false,
+                    source: "stripe",
+                },
+                Donation {
+                    name: "Jane Doe".to_string(),
+                    link: None,
+                    monthly_amount: 25.0,
+                    active: false,
+                    anonymous: true,
+                    source: "stripe",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_every_org_donations() {
+        let csv = "\
+First Name,Last Name,Amount,Frequency,Share Info
+John,Hainline,25,Monthly,Yes
+Jane,Doe,100,One-time,No
+";
+        let donations = every_org_donations(read_rows(csv.as_bytes()).unwrap());
+        let summary: Vec<_> = donations
+            .iter()
+            .map(|d| (d.name.as_str(), d.active, d.anonymous))
+            .collect();
+        assert_eq!(
+            summary,
+            [("John Hainline", true, false), ("Jane Doe", false, true)]
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::sponsor::Donation;
+use anyhow::bail;
+use serde::Deserialize;
+
+const SPONSORSHIPS_QUERY: &str = "query($login: String!, $cursor: String) {
+    organization(login: $login) {
+        sponsorshipsAsMaintainer(first: 100, after: $cursor, activeOnly: false) {
+            pageInfo { hasNextPage endCursor }
+            nodes {
+                isActive
+                isOneTimePayment
+                privacyLevel
+                tier { monthlyPriceInDollars }
+                sponsorEntity {
+                    ... on User { login name websiteUrl }
+                    ... on Organization { login name websiteUrl }
+                }
+            }
+        }
+    }
+}";
+
+#[derive(Deserialize, Debug)]
+struct GraphqlResponse {
+    data: Option<GraphqlData>,
+    #[serde(default)]
+    errors: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GraphqlData {
+    organization: Option<GithubOrganization>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubOrganization {
+    sponsorships_as_maintainer: GithubSponsorships,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubSponsorships {
+    page_info: GithubPageInfo,
+    nodes: Vec<GithubSponsorship>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubPageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubSponsorship {
+    is_active: bool,
+    is_one_time_payment: bool,
+    /// `PUBLIC`, or `PRIVATE` for the sponsors that asked to be anonymous.
+    privacy_level: String,
+    tier: Option<GithubTier>,
+    /// Missing for the sponsors that deleted their account.
+    sponsor_entity: Option<GithubSponsor>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubTier {
+    monthly_price_in_dollars: u32,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GithubSponsor {
+    login: String,
+    name: Option<String>,
+    website_url: Option<String>,
+}
+
+impl From<GithubSponsorship> for Donation {
+    fn from(sponsorship: GithubSponsorship) -> Self {
+        let sponsor = sponsorship.sponsor_entity;
+        let name = sponsor
+            .as_ref()
+            .map(|sponsor| {
+                sponsor
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| sponsor.login.clone())
+            })
+            .unwrap_or_default();
+        Donation {
+            anonymous: sponsorship.privacy_level != "PUBLIC" || name.is_empty(),
+            link: sponsor.map(|sponsor| {
+                sponsor
+                    .website_url
+                    .filter(|url| !url.is_empty())
+                    .unwrap_or_else(|| format!("https://github.com/{}", sponsor.login))
+            }),
+            name,
+            monthly_amount: sponsorship
+                .tier
+                .map_or(0.0, |tier| f64::from(tier.monthly_price_in_dollars)),
+            active: sponsorship.is_active && !sponsorship.is_one_time_payment,
+            source: "github",
+        }
+    }
+}
+
+/// Gets the current and past GitHub sponsors of the organization `login`.
+pub fn get_github_sponsors(token: &str, login: &str) -> anyhow::Result<Vec<Donation>> {
+    let agent: ureq::Agent = ureq::AgentBuilder::new()
+        .user_agent("bevy-website-generate-sponsors")
+        .build();
+
+    let mut donations = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let response: GraphqlResponse = agent
+            .post("https://api.github.com/graphql")
+            .set("Authorization", &format!("bearer {token}"))
+            .send_json(ureq::json!({
+                "query": SPONSORSHIPS_QUERY,
+                "variables": { "login": login, "cursor": cursor },
+            }))?
+            .into_json()?;
+        if !response.errors.is_empty() {
+            bail!("GitHub returned errors: {:?}", response.errors);
+        }
+        let Some(organization) = response.data.and_then(|data| data.organization) else {
+            bail!("The organization {login} wasn't found on GitHub");
+        };
+
+        let sponsorships = organization.sponsorships_as_maintainer;
+        println!("Found {} GitHub sponsorships", sponsorships.nodes.len());
+        donations.extend(sponsorships.nodes.into_iter().map(Donation::from));
+        if !sponsorships.page_info.has_next_page {
+            break;
+        }
+        cursor = sponsorships.page_info.end_cursor;
+    }
+    Ok(donations)
+}
@@ -0,0 +1,92 @@
+use clap::Parser;
+use exports::{read_every_org_export, read_stripe_export};
+use github::get_github_sponsors;
+use sponsor::{merge_sponsors, SponsorFile, TierFile};
+use std::path::PathBuf;
+
+mod exports;
+mod github;
+mod sponsor;
+
+/// Generates the list of sponsors of the donate page, `content/donate/foundation-sponsors.toml`.
+///
+/// * Gets the sponsors of GitHub Sponsors, and the donors of the Stripe and Every.org exports.
+/// * Rounds the amount of each sponsor down to its tier, yearly donations counting monthly.
+/// * Lists the sponsors that asked to be anonymous as "Anonymous", without link or logo.
+/// * Keeps the sponsors of other sources, and the logos, links and styles set by hand.
+/// * Lists the sponsors missing from their source as past sponsors.
+/// * Sets the logo of new sponsors to the one named after them in `static/assets/sponsors`,
+///   like `dead_money.png`.
+///
+/// Getting the GitHub sponsors requires a valid `GITHUB_TOKEN` environment variable with the
+/// `read:org` scope, you can use a .env file or use your preferred method of passing env arguments.
+///
+/// Example:
+/// cargo run -- --github bevyengine --stripe subscriptions.csv --every-org donations.csv
+#[derive(Parser)]
+#[command(author, version, about)]
+struct Args {
+    /// The organization to get the GitHub sponsors of
+    #[arg(long)]
+    github: Option<String>,
+
+    /// The CSV export of the subscriptions of the Stripe dashboard
+    #[arg(long)]
+    stripe: Option<PathBuf>,
+
+    /// The CSV export of the donations of Every.org
+    #[arg(long)]
+    every_org: Option<PathBuf>,
+
+    /// The tiers sponsors are sorted into
+    #[arg(long, default_value = "../content/donate/tiers.toml")]
+    tiers: PathBuf,
+
+    /// The folder of the logos of the sponsors
+    #[arg(long, default_value = "../static/assets/sponsors")]
+    logos: PathBuf,
+
+    /// Path of the generated file, updated if it exists
+    #[arg(
+        short,
+        long,
+        default_value = "../content/donate/foundation-sponsors.toml"
+    )]
+    path: PathBuf,
+}
+
+fn main() -> anyhow::Result<()> {
+    let _ = dotenvy::dotenv();
+
+    let args = Args::parse();
+
+    let mut donations = vec![];
+    if let Some(login) = &args.github {
+        let token = std::env::var("GITHUB_TOKEN").expect("GITHUB_TOKEN not found");
+        donations.extend(get_github_sponsors(&token, login)?);
+    }
+    if let Some(path) = &args.stripe {
+        donations.extend(read_stripe_export(path)?);
+    }
+    if let Some(path) = &args.every_org {
+        donations.extend(read_every_org_export(path)?);
+    }
+    println!("Found {} donations", donations.len());
+
+    let tiers: TierFile = toml::from_str(&std::fs::read_to_string(&args.tiers)?)?;
+    let existing: SponsorFile = if args.path.exists() {
+        toml::from_str(&std::fs::read_to_string(&args.path)?)?
+    } else {
+        SponsorFile::default()
+    };
+
+    let sponsor = merge_sponsors(&donations, existing.sponsor, &tiers.tier, &args.logos);
+    println!(
+        "Writing {} sponsors, {} of them current",
+        sponsor.len(),
+        sponsor.iter().filter(|sponsor| !sponsor.past).count()
+    );
+    std::fs::write(&args.path, toml::to_string(&SponsorFile { sponsor })?)?;
+
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+/// A donation read from one of the sources, before it's written as a [`Sponsor`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Donation {
+    pub name: String,
+    pub link: Option<String>,
+    /// The amount donated each month in dollars, or once for one-time donations.
+    pub monthly_amount: f64,
+    /// Whether the donation is still recurring.
+    pub active: bool,
+    /// Whether the donor asked not to be named.
+    pub anonymous: bool,
+    pub source: &'static str,
+}
+
+/// An entry of the sponsor files of the donate page, like `foundation-sponsors.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Sponsor {
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub past: bool,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    pub amount: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SponsorFile {
+    #[serde(default)]
+    pub sponsor: Vec<Sponsor>,
+}
+
+/// A tier of `tiers.toml`, which has more fields read by the templates.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Tier {
+    pub amount: u32,
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct TierFile {
+    #[serde(default)]
+    pub tier: Vec<Tier>,
+}
+
+/// The name anonymous donors are listed with.
+pub const ANONYMOUS: &str = "Anonymous";
+
+/// The extensions of the logos looked for in the logo folder.
+const LOGO_EXTENSIONS: &[&str] = &["svg", "png", "webp", "jpg"];
+
+/// Rounds `monthly_amount` down to the amount of its tier, so the exact amount of a donation
+/// isn't published. Amounts below every tier are rounded down to the dollar.
+pub fn tier_amount(monthly_amount: f64, tiers: &[Tier]) -> u32 {
+    tiers
+        .iter()
+        .map(|tier| tier.amount)
+        .filter(|amount| f64::from(*amount) <= monthly_amount)
+        .max()
+        .unwrap_or(monthly_amount.max(0.0) as u32)
+}
+
+/// Turns `donations` into sponsors, merged with the `existing` sponsors:
+/// * The donations of the same donor are summed, a donor being past if none is active.
+/// * The existing sponsors of the fetched sources are replaced, keeping their logos and links,
+///   and are past sponsors if they aren't in their source anymore.
+/// * The existing sponsors of other sources, like direct sponsors, are kept as is.
+/// * Anonymous donors are listed as [`ANONYMOUS`], without a link or logo, replacing all the
+///   anonymous sponsors of their source.
+///
+/// Sponsors are sorted with the current ones first, by amount.
+pub fn merge_sponsors(
+    donations: &[Donation],
+    existing: Vec<Sponsor>,
+    tiers: &[Tier],
+    logo_dir: &Path,
+) -> Vec<Sponsor> {
+    // Summed by name, in the order they were read
+    let mut donors: Vec<Donation> = vec![];
+    for donation in donations {
+        let key = |d: &Donation| (d.anonymous, d.source, d.name.to_lowercase());
+        match donors.iter_mut().find(|donor| key(donor) == key(donation)) {
+            Some(donor) => {
+                if donation.active {
+                    donor.monthly_amount = if donor.active {
+                        donor.monthly_amount + donation.monthly_amount
+                    } else {
+                        donation.monthly_amount
+                    };
+                    donor.active = true;
+                } else if !donor.active {
+                    donor.monthly_amount = donor.monthly_amount.max(donation.monthly_amount);
+                }
+                donor.link = donor.link.take().or_else(|| donation.link.clone());
+            }
+            None => donors.push(donation.clone()),
+        }
+    }
+
+    let sources: Vec<&str> = donations.iter().map(|donation| donation.source).collect();
+    let (replaced_sponsors, mut sponsors): (Vec<_>, Vec<_>) =
+        existing.into_iter().partition(|sponsor| {
+            sponsor
+                .source
+                .as_deref()
+                .is_some_and(|source| sources.contains(&source))
+        });
+    // Anonymous sponsors can't be matched to their donor, they are all listed again
+    let key = |source: &str, name: &str| (source.to_string(), name.to_lowercase());
+    let mut replaced: HashMap<(String, String), Vec<Sponsor>> = HashMap::new();
+    for sponsor in replaced_sponsors {
+        if sponsor.name == ANONYMOUS {
+            continue;
+        }
+        let source = sponsor.source.as_deref().unwrap_or_default();
+        replaced
+            .entry(key(source, &sponsor.name))
+            .or_default()
+            .push(sponsor);
+    }
+
+    for donor in donors {
+        let amount = tier_amount(donor.monthly_amount, tiers);
+        if donor.anonymous {
+            sponsors.push(Sponsor {
+                past: !donor.active,
+                name: ANONYMOUS.to_string(),
+                image: None,
+                link: None,
+                amount,
+                style: None,
+                source: Some(donor.source.to_string()),
+                recipient: None,
+                account: None,
+            });
+            continue;
+        }
+        let previous = replaced
+            .get_mut(&key(donor.source, &donor.name))
+            .and_then(Vec::pop);
+        let previous = previous.as_ref();
+        let image = previous
+            .and_then(|sponsor| sponsor.image.clone())
+            .or_else(|| find_logo(&donor.name, logo_dir));
+        sponsors.push(Sponsor {
+            past: !donor.active,
+            image,
+            link: previous
+                .and_then(|sponsor| sponsor.link.clone())
+                .or(donor.link),
+            amount,
+            style: previous.and_then(|sponsor| sponsor.style.clone()),
+            source: Some(donor.source.to_string()),
+            recipient: previous.and_then(|sponsor| sponsor.recipient.clone()),
+            account: previous.and_then(|sponsor| sponsor.account.clone()),
+            name: donor.name,
+        });
+    }
+
+    // Sponsors that aren't in their source anymore stopped donating
+    sponsors.extend(replaced.into_values().flatten().map(|sponsor| Sponsor {
+        past: true,
+        ..sponsor
+    }));
+
+    sponsors.sort_by(|a, b| {
+        a.past
+            .cmp(&b.past)
+            .then(b.amount.cmp(&a.amount))
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+    sponsors
+}
+
+/// The file name of the logo of `name` in `logo_dir`, named after it in lowercase with
+/// underscores, like `dead_money.png`.
+fn find_logo(name: &str, logo_dir: &Path) -> Option<String> {
+    let stem: String = name
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace() || *c == '_' || *c == '-')
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    LOGO_EXTENSIONS
+        .iter()
+        .map(|extension| format!("{stem}.{extension}"))
+        .find(|file| logo_dir.join(file).is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiers() -> Vec<Tier> {
+        [5, 10, 25, 50]
+            .into_iter()
+            .map(|amount| Tier { amount })
+            .collect()
+    }
+
+    fn donation(name: &str, monthly_amount: f64, active: bool) -> Donation {
+        Donation {
+            name: name.to_string(),
+            link: None,
+            monthly_amount,
+            active,
+            anonymous: false,
+            source: "stripe",
+        }
+    }
+
+    fn sponsor(name: &str, amount: u32, source: &str) -> Sponsor {
+        Sponsor {
+            past: false,
+            name: name.to_string(),
+            image: None,
+            link: None,
+            amount,
+            style: None,
+            source: Some(source.to_string()),
+            recipient: None,
+            account: None,
+        }
+    }
+
+    #[test]
+    fn rounds_amounts_down_to_their_tier() {
+        assert_eq!(tier_amount(60.0, &tiers()), 50);
+        assert_eq!(tier_amount(25.0, &tiers()), 25);
+        assert_eq!(tier_amount(24.99, &tiers()), 10);
+        assert_eq!(tier_amount(3.5, &tiers()), 3);
+    }
+
+    #[test]
+    fn merges_donations_with_the_existing_sponsors() {
+        let mut anonymous = donation("Jane Doe", 10.0, true);
+        anonymous.anonymous = true;
+        let donations = [
+            donation("Dead Money", 200.0, true),
+            donation("dead money", 100.0, true),
+            donation("Past Donor", 30.0, false),
+            anonymous,
+        ];
+        let mut dead_money = sponsor("Dead Money", 250, "stripe");
+        dead_money.image = Some("dead_money.png".to_string());
+        let existing = vec![
+            dead_money,
+            sponsor("Cancelled", 25, "stripe"),
+            sponsor("Direct Sponsor", 100, "direct"),
+        ];
+
+        let sponsors = merge_sponsors(&donations, existing, &tiers(), Path::new("missing"));
+        let summary: Vec<_> = sponsors
+            .iter()
+            .map(|s| (s.name.as_str(), s.amount, s.past, s.image.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Direct Sponsor", 100, false, None),
+                ("Dead Money", 50, false, Some("dead_money.png")),
+                ("Anonymous", 10, false, None),
+                ("Cancelled", 25, true, None),
+                ("Past Donor", 25, true, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rerunning_on_its_output_keeps_the_same_sponsors() {
+        let mut anonymous = donation("Jane Doe", 10.0, true);
+        anonymous.anonymous = true;
+        let mut other_anonymous = donation("John Doe", 25.0, true);
+        other_anonymous.anonymous = true;
+        let donations = [
+            donation("Dead Money", 300.0, true),
+            donation("Past Donor", 30.0, false),
+            anonymous,
+            other_anonymous,
+        ];
+        // Two existing sponsors of the same name are both kept
+        let existing = vec![
+            sponsor("Sam", 25, "stripe"),
+            sponsor("Sam", 10, "stripe"),
+            sponsor("Sam", 5, "github"),
+        ];
+
+        let first = merge_sponsors(&donations, existing, &tiers(), Path::new("missing"));
+        let second = merge_sponsors(&donations, first.clone(), &tiers(), Path::new("missing"));
+        assert_eq!(second, first);
+
+        let summary: Vec<_> = second
+            .iter()
+            .map(|s| (s.name.as_str(), s.amount, s.past))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Dead Money", 50, false),
+                ("Anonymous", 25, false),
+                ("Anonymous", 10, false),
+                ("Sam", 5, false),
+                ("Past Donor", 25, true),
+                ("Sam", 25, true),
+                ("Sam", 10, true),
+            ]
+        );
+    }
+}
@@ -5,19 +5,49 @@ echo Generating WASM example list...
 # Switch to script's directory, letting it be called from any folder.
 cd $(dirname $0)
 
-# If Bevy folder already exists, pull the latest changes.
+# The Bevy release the examples are built from, `latest` being the last release.
+# Set `BEVY_RELEASE` to a tag, like `v0.14.0`, to pin another one.
+BEVY_RELEASE=${BEVY_RELEASE:-latest}
+
+# Removes the pages of the examples in the example list folder $1 that aren't matched by the
+# patterns of `include.txt`, when it exists, or are matched by the ones of `exclude.txt`.
+# Patterns are globs of `<category>/<example>`, one per line, like `2d/*` or `games/breakout`.
+filter_examples() {
+    for example in "$1"/*/*/; do
+        example=${example%/}
+        name=${example#"$1"/}
+        if [[ -f ../include.txt ]] && ! matches "$name" ../include.txt; then
+            rm -r "$example"
+        elif [[ -f ../exclude.txt ]] && matches "$name" ../exclude.txt; then
+            rm -r "$example"
+        fi
+    done
+}
+
+# Whether the example $1 is matched by a pattern of the file $2, ignoring comments.
+matches() {
+    while read -r pattern; do
+        if [[ -n $pattern && $pattern != \#* && $1 == $pattern ]]; then
+            return 0
+        fi
+    done < "$2"
+    return 1
+}
+
+# If Bevy folder already exists, fetch the release.
 if [[ -d bevy ]]; then
-    echo Bevy folder already exists, attempting to fetch latest changes.
+    echo Bevy folder already exists, attempting to fetch $BEVY_RELEASE.
 
     cd bevy
 
-    # Attempts to fetch the latest commits, which should only happen every Bevy release.
-    git pull --depth=1
+    # Only fetches commits when the release changed, which should only happen every Bevy release.
+    git fetch --depth=1 origin "$BEVY_RELEASE"
+    git checkout --force FETCH_HEAD
 else
     echo Bevy folder does not exist, cloning repository.
 
-    # Clone Bevy's latest branch from scratch, only downloading the latest commit.
-    git clone --depth=1 --branch=latest https://github.com/bevyengine/bevy bevy
+    # Clone the release from scratch, only downloading its commit.
+    git clone --depth=1 --branch="$BEVY_RELEASE" https://github.com/bevyengine/bevy bevy
 
     cd bevy
 fi
@@ -27,6 +57,7 @@ rm -rf ../../content/examples
 # HACK: build in ./content before moving examples to correct folder in order for `extra.code_path`
 # to be formatted correctly.
 cargo run -p example-showcase -- build-website-list --content-folder content --api webgl2
+filter_examples content
 mv content ../../content/examples
 
 # Build WebGPU example list.
@@ -34,6 +65,7 @@ rm -rf ../../content/examples-webgpu
 # HACK: build in ./content before moving examples to correct folder in order for `extra.code_path`
 # to be formatted correctly.
 cargo run -p example-showcase -- build-website-list --content-folder content --api webgpu
+filter_examples content
 mv content ../../content/examples-webgpu
 
 # Remove Markdown files from assets so that they don't get picked up by Zola.
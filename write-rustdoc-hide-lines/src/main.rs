@@ -45,7 +45,7 @@ fn main() -> ExitCode {
     }
 }
 
-fn check(folders: impl Iterator<Item = PathBuf> + ExactSizeIterator) -> ExitCode {
+fn check(folders: impl ExactSizeIterator<Item = PathBuf>) -> ExitCode {
     if folders.len() == 0 {
         eprintln!("Did not check any files because no folder arguments were passed.");
 
@@ -85,7 +85,7 @@ fn check(folders: impl Iterator<Item = PathBuf> + ExactSizeIterator) -> ExitCode
     }
 }
 
-fn format(folders: impl Iterator<Item = PathBuf> + ExactSizeIterator) -> ExitCode {
+fn format(folders: impl ExactSizeIterator<Item = PathBuf>) -> ExitCode {
     if folders.len() == 0 {
         eprintln!("Did not format any files because no folder arguments were passed.");
 